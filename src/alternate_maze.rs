@@ -0,0 +1,243 @@
+use std::fmt::{Debug, Formatter};
+use rand::rngs::SmallRng;
+use rand::{RngCore, SeedableRng};
+use crate::maze::{Action, Coord, ScoreType};
+
+#[derive(Clone)]
+pub struct PlayerInfo {
+    pub coord: Coord,
+    pub game_score: i32,
+}
+
+/// 終局で手番のプレイヤーが勝っているときの評価値 (どの盤面の得点差よりも大きい)
+pub const WIN_SCORE: ScoreType = 1_000_000;
+pub const LOSE_SCORE: ScoreType = -WIN_SCORE;
+pub const DRAW_SCORE: ScoreType = 0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WinningStatus {
+    Win,
+    Lose,
+    Draw,
+    None,
+}
+
+/// 二人のキャラクターが交互に動いて得点を取り合う迷路
+///
+/// `players[0]` が常に手番のプレイヤーで、`is_first` はそれが先手かどうかを表す。
+#[derive(Clone)]
+pub struct AlternateMazeState<const H: usize, const W: usize, const END_TURN: i32> {
+    points: [[i32; W]; H],
+    turn: i32,
+    players: [PlayerInfo; 2],
+    is_first: bool,
+}
+
+impl<const H: usize, const W: usize, const END_TURN: i32> AlternateMazeState<H, W, END_TURN> {
+    pub fn new(seed: u64) -> Self {
+        let mut rng_for_construct = if seed < u64::MAX {
+            SmallRng::seed_from_u64(seed)
+        } else {
+            SmallRng::from_entropy()
+        };
+        let players = Self::initial_players();
+        let mut points = [[0; W]; H];
+        for (y, row) in points.iter_mut().enumerate() {
+            for (x, point) in row.iter_mut().enumerate() {
+                if players.iter().any(|player| player.coord.y == y && player.coord.x == x) { continue; }
+                *point = (rng_for_construct.next_u32() % 10) as i32;
+            }
+        }
+        Self {
+            points,
+            turn: 0,
+            players,
+            is_first: true,
+        }
+    }
+
+    /// 乱数を使わずに、与えた得点の配置から盤面を作る (キャラクターの位置は `new` と同じで、そのマスの得点は 0 にする)
+    pub fn from_points(mut points: [[i32; W]; H]) -> Self {
+        assert!(points.iter().flatten().all(|point| (0..=9).contains(point)), "points must be in 0..=9");
+        let players = Self::initial_players();
+        for player in &players {
+            points[player.coord.y][player.coord.x] = 0;
+        }
+        Self {
+            points,
+            turn: 0,
+            players,
+            is_first: true,
+        }
+    }
+
+    /// 先手は中央の左隣、後手は中央の右隣から始める (幅が 2 なら後手は右端)
+    fn initial_players() -> [PlayerInfo; 2] {
+        assert!(W >= 2 && H >= 1, "the board must have at least two columns");
+        [
+            PlayerInfo { coord: Coord { x: W / 2 - 1, y: H / 2 }, game_score: 0 },
+            PlayerInfo { coord: Coord { x: (W / 2 + 1).min(W - 1), y: H / 2 }, game_score: 0 },
+        ]
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.turn == END_TURN
+    }
+
+    pub fn is_first(&self) -> bool {
+        self.is_first
+    }
+
+    /// 手番のプレイヤーの番号 (先手なら 0、後手なら 1)
+    pub fn current_player(&self) -> u8 {
+        if self.is_first { 0 } else { 1 }
+    }
+
+    pub fn advance(&mut self, action: Action) {
+        let player = &mut self.players[0];
+        let (dx, dy) = action.delta();
+        player.coord.x = (player.coord.x as i32 + dx) as usize;
+        player.coord.y = (player.coord.y as i32 + dy) as usize;
+        let point = &mut self.points[player.coord.y][player.coord.x];
+        if *point > 0 {
+            player.game_score += *point;
+            *point = 0;
+        }
+        self.turn += 1;
+        self.players.swap(0, 1);
+        self.is_first = !self.is_first;
+    }
+
+    pub fn legal_actions(&self) -> Vec<Action> {
+        let player = &self.players[0];
+        let mut actions = Vec::new();
+        for &action in &Action::ALL[..4] {
+            let (dx, dy) = action.delta();
+            let ty = player.coord.y as i32 + dy;
+            let tx = player.coord.x as i32 + dx;
+            if ty >= 0 && ty < H as i32 && tx >= 0 && tx < W as i32 {
+                actions.push(action);
+            }
+        }
+        actions
+    }
+
+    /// 手番のプレイヤーから見た勝敗
+    pub fn winning_status(&self) -> WinningStatus {
+        if !self.is_done() {
+            return WinningStatus::None;
+        }
+        match self.players[0].game_score.cmp(&self.players[1].game_score) {
+            std::cmp::Ordering::Greater => WinningStatus::Win,
+            std::cmp::Ordering::Less => WinningStatus::Lose,
+            std::cmp::Ordering::Equal => WinningStatus::Draw,
+        }
+    }
+
+    /// 手番のプレイヤーから見た得点差
+    pub fn teban_score(&self) -> ScoreType {
+        (self.players[0].game_score - self.players[1].game_score) as ScoreType
+    }
+
+    /// 手番のプレイヤーから見た評価値 (終局なら `WIN_SCORE`、`LOSE_SCORE`、`DRAW_SCORE`、それ以外は `teban_score`)
+    pub fn teban_evaluation(&self) -> ScoreType {
+        match self.winning_status() {
+            WinningStatus::Win => WIN_SCORE,
+            WinningStatus::Lose => LOSE_SCORE,
+            WinningStatus::Draw => DRAW_SCORE,
+            WinningStatus::None => self.teban_score(),
+        }
+    }
+
+    /// 二人がすでに取った得点と盤面に残っている得点の合計 (`teban_score` はこの値の符号を変えた値からこの値までに収まる)
+    pub fn total_points(&self) -> i32 {
+        self.players[0].game_score + self.players[1].game_score + self.points.iter().flatten().sum::<i32>()
+    }
+
+    /// 先手、後手の順に並べたプレイヤーの情報
+    pub fn players_in_order(&self) -> [&PlayerInfo; 2] {
+        if self.is_first {
+            [&self.players[0], &self.players[1]]
+        } else {
+            [&self.players[1], &self.players[0]]
+        }
+    }
+}
+
+impl<const H: usize, const W: usize, const END_TURN: i32> Debug for AlternateMazeState<H, W, END_TURN> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let [first, second] = self.players_in_order();
+        let s = (0..H).map(|h| {
+            (0..W).map(|w| {
+                if first.coord.y == h && first.coord.x == w {
+                    "A"
+                } else if second.coord.y == h && second.coord.x == w {
+                    "B"
+                } else if self.points[h][w] > 0 {
+                    ["0", "1", "2", "3", "4", "5", "6", "7", "8", "9"][self.points[h][w] as usize]
+                } else {
+                    "."
+                }
+            }).collect::<Vec<_>>().join("")
+        }).collect::<Vec<_>>().join("\n");
+        writeln!(f, "turn:\t{}\nscore(A):\t{}\nscore(B):\t{}\n{}", self.turn, first.game_score, second.game_score, s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::rngs::SmallRng;
+    use rand::{RngCore, SeedableRng};
+    use crate::search::alpha_beta::negamax_score;
+    use super::*;
+
+    /// 幅 2 や 3 の盤面でも二人とも盤面内の別のマスから始まる
+    #[test]
+    fn narrow_board_starts_in_bounds() {
+        fn check<const H: usize, const W: usize>() {
+            let state = AlternateMazeState::<H, W, 4>::new(0);
+            let [first, second] = state.players_in_order();
+            assert!(first.coord.x < W && second.coord.x < W);
+            assert!(first.coord.y < H && second.coord.y < H);
+            assert_ne!(first.coord, second.coord);
+        }
+        check::<1, 2>();
+        check::<2, 2>();
+        check::<3, 3>();
+        check::<5, 5>();
+    }
+
+    /// 幅 2 の盤面で終局まで指しても盤面の外に出ない
+    #[test]
+    fn narrow_board_stays_in_bounds() {
+        let mut state = AlternateMazeState::<3, 2, 10>::new(1);
+        while !state.is_done() {
+            let action = state.legal_actions()[state.turn as usize % state.legal_actions().len()];
+            state.advance(action);
+        }
+        for player in state.players_in_order() {
+            assert!(player.coord.x < 2 && player.coord.y < 3);
+        }
+    }
+
+    /// 手番のプレイヤーが勝って終わった局面では、評価値が `WIN_SCORE` になり、探索の評価値もそれに一致する
+    #[test]
+    fn terminal_win_is_win_score() {
+        let mut rng_for_action = SmallRng::seed_from_u64(0);
+        let mut rng_for_construct = SmallRng::seed_from_u64(0);
+        let mut win_number = 0;
+        for _ in 0..50 {
+            let mut state = AlternateMazeState::<5, 5, 10>::new(rng_for_construct.next_u64());
+            while !state.is_done() {
+                let legal_actions = state.legal_actions();
+                state.advance(legal_actions[rng_for_action.next_u32() as usize % legal_actions.len()]);
+            }
+            if state.teban_score() > 0 {
+                assert_eq!(state.teban_evaluation(), WIN_SCORE);
+                assert_eq!(negamax_score(&state, -ScoreType::MAX, ScoreType::MAX, 3), WIN_SCORE);
+                win_number += 1;
+            }
+        }
+        assert!(win_number > 0);
+    }
+}
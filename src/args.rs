@@ -0,0 +1,134 @@
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ArgsError {
+    UnknownFlag(String),
+    MissingValue(String),
+    InvalidValue(String, String),
+    /// フラグ、与えた値、許される最小値
+    OutOfRange(String, String, String),
+}
+
+impl Display for ArgsError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ArgsError::UnknownFlag(flag) => write!(f, "unknown flag: {flag}"),
+            ArgsError::MissingValue(flag) => write!(f, "missing value for {flag}"),
+            ArgsError::InvalidValue(flag, value) => write!(f, "invalid value for {flag}: {value}"),
+            ArgsError::OutOfRange(flag, value, min) => write!(f, "value for {flag} must be at least {min}: {value}"),
+        }
+    }
+}
+
+impl std::error::Error for ArgsError {}
+
+/// `--flag value` 形式のコマンドライン引数
+#[derive(Debug, Clone, Default)]
+pub struct Args {
+    flags: Vec<String>,
+    values: HashMap<String, String>,
+}
+
+impl Args {
+    /// `flags` にないフラグや値のないフラグがあればエラーにする
+    pub fn parse<I: IntoIterator<Item = String>>(args: I, flags: &[&str]) -> Result<Self, ArgsError> {
+        let mut values = HashMap::new();
+        let mut args = args.into_iter();
+        while let Some(flag) = args.next() {
+            if !flags.contains(&flag.as_str()) {
+                return Err(ArgsError::UnknownFlag(flag));
+            }
+            match args.next() {
+                Some(value) if !value.starts_with("--") => { values.insert(flag, value); }
+                _ => return Err(ArgsError::MissingValue(flag)),
+            }
+        }
+        Ok(Self { flags: flags.iter().map(|flag| flag.to_string()).collect(), values })
+    }
+
+    /// プログラム名を除いた `std::env::args` を解釈し、失敗したら使い方を表示して終了する
+    pub fn from_env(flags: &[&str]) -> Self {
+        Self::parse(std::env::args().skip(1), flags).unwrap_or_else(|error| exit_with_usage(&error, &flags.iter().map(|flag| flag.to_string()).collect::<Vec<_>>()))
+    }
+
+    /// 指定されなかったフラグは `default` にする
+    pub fn get<T: FromStr>(&self, flag: &str, default: T) -> Result<T, ArgsError> {
+        match self.values.get(flag) {
+            Some(value) => value.parse().map_err(|_| ArgsError::InvalidValue(flag.to_string(), value.clone())),
+            None => Ok(default),
+        }
+    }
+
+    /// `get` に失敗したら使い方を表示して終了する
+    pub fn get_or_exit<T: FromStr>(&self, flag: &str, default: T) -> T {
+        self.get(flag, default).unwrap_or_else(|error| exit_with_usage(&error, &self.flags))
+    }
+
+    /// `get` と同じく解釈し、`min` より小さい値はエラーにする (ゲーム数やビーム幅などの 0 や負の値を探索に渡さないため)
+    pub fn get_at_least<T: FromStr + PartialOrd + Display>(&self, flag: &str, default: T, min: T) -> Result<T, ArgsError> {
+        let value = self.get(flag, default)?;
+        if value < min {
+            return Err(ArgsError::OutOfRange(flag.to_string(), value.to_string(), min.to_string()));
+        }
+        Ok(value)
+    }
+
+    /// `get_at_least` に失敗したら使い方を表示して終了する
+    pub fn get_at_least_or_exit<T: FromStr + PartialOrd + Display>(&self, flag: &str, default: T, min: T) -> T {
+        self.get_at_least(flag, default, min).unwrap_or_else(|error| exit_with_usage(&error, &self.flags))
+    }
+}
+
+fn exit_with_usage(error: &ArgsError, flags: &[String]) -> ! {
+    let program = std::env::args().next().unwrap_or_default();
+    let usage = flags.iter().map(|flag| format!("[{flag} <value>]")).collect::<Vec<_>>().join(" ");
+    eprintln!("error: {error}");
+    eprintln!("usage: {program} {usage}");
+    std::process::exit(2)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FLAGS: [&str; 2] = ["--games", "--beam-width"];
+
+    fn parse(args: &[&str]) -> Result<Args, ArgsError> {
+        Args::parse(args.iter().map(|arg| arg.to_string()), &FLAGS)
+    }
+
+    #[test]
+    fn parses_values_and_defaults() {
+        let args = parse(&["--games", "10"]).unwrap();
+        assert_eq!(args.get("--games", 100), Ok(10));
+        assert_eq!(args.get("--beam-width", 5), Ok(5));
+    }
+
+    #[test]
+    fn rejects_missing_value() {
+        assert_eq!(parse(&["--games"]).unwrap_err(), ArgsError::MissingValue("--games".to_string()));
+        assert_eq!(parse(&["--games", "--beam-width", "3"]).unwrap_err(), ArgsError::MissingValue("--games".to_string()));
+    }
+
+    #[test]
+    fn rejects_unknown_flag() {
+        assert_eq!(parse(&["--depth", "3"]).unwrap_err(), ArgsError::UnknownFlag("--depth".to_string()));
+    }
+
+    #[test]
+    fn rejects_non_numeric_value() {
+        let args = parse(&["--games", "ten"]).unwrap();
+        assert_eq!(args.get("--games", 100), Err(ArgsError::InvalidValue("--games".to_string(), "ten".to_string())));
+    }
+
+    #[test]
+    fn rejects_values_below_minimum() {
+        let args = parse(&["--games", "0", "--beam-width", "-3"]).unwrap();
+        assert_eq!(args.get_at_least("--games", 100, 1), Err(ArgsError::OutOfRange("--games".to_string(), "0".to_string(), "1".to_string())));
+        assert_eq!(args.get_at_least("--beam-width", 5, 1), Err(ArgsError::OutOfRange("--beam-width".to_string(), "-3".to_string(), "1".to_string())));
+        assert_eq!(args.get_at_least("--beam-width", 5usize, 1), Err(ArgsError::InvalidValue("--beam-width".to_string(), "-3".to_string())));
+        assert_eq!(parse(&[]).unwrap().get_at_least("--games", 100, 1), Ok(100));
+    }
+}
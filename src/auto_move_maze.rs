@@ -0,0 +1,126 @@
+use std::fmt::{Debug, Formatter};
+use rand::rngs::SmallRng;
+use rand::{RngCore, SeedableRng};
+use crate::maze::{Coord, ScoreType};
+
+/// 最初にキャラクターの配置だけを決め、あとはキャラクターが自動で動く迷路
+#[derive(Clone)]
+pub struct AutoMoveMazeState<const H: usize, const W: usize, const END_TURN: i32, const CHARACTER_N: usize> {
+    points: [[i32; W]; H],
+    turn: i32,
+    characters: [Coord; CHARACTER_N],
+    pub game_score: i32,
+}
+
+#[allow(non_upper_case_globals)]
+impl<const H: usize, const W: usize, const END_TURN: i32, const CHARACTER_N: usize> AutoMoveMazeState<H, W, END_TURN, CHARACTER_N> {
+    const dx: [i32; 4] = [1, -1, 0, 0];
+    const dy: [i32; 4] = [0, 0, 1, -1];
+
+    pub fn new(seed: u64) -> Self {
+        let mut rng_for_construct = if seed < u64::MAX {
+            SmallRng::seed_from_u64(seed)
+        } else {
+            SmallRng::from_entropy()
+        };
+        let mut points = [[0; W]; H];
+        for row in points.iter_mut() {
+            for point in row.iter_mut() {
+                *point = (rng_for_construct.next_u32() % 9 + 1) as i32;
+            }
+        }
+        Self {
+            points,
+            turn: 0,
+            characters: std::array::from_fn(|_| Coord { x: 0, y: 0 }),
+            game_score: 0,
+        }
+    }
+
+    pub fn set_character(&mut self, character_id: usize, y: usize, x: usize) {
+        self.characters[character_id] = Coord { x, y };
+    }
+
+    /// 全てのキャラクターをランダムな位置に置く
+    pub fn init(&mut self, rng: &mut SmallRng) {
+        for character_id in 0..CHARACTER_N {
+            let y = rng.next_u32() as usize % H;
+            let x = rng.next_u32() as usize % W;
+            self.set_character(character_id, y, x);
+        }
+    }
+
+    /// ランダムに選んだキャラクターを 1 人ランダムな位置に移す
+    pub fn transition(&mut self, rng: &mut SmallRng) {
+        let character_id = rng.next_u32() as usize % CHARACTER_N;
+        let y = rng.next_u32() as usize % H;
+        let x = rng.next_u32() as usize % W;
+        self.set_character(character_id, y, x);
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.turn == END_TURN
+    }
+
+    /// 隣接するマスのうち最も得点が高いマスへ動く
+    fn move_player(&mut self, character_id: usize) {
+        let character = &self.characters[character_id];
+        let mut best_point = i32::MIN;
+        let mut best_action = 0;
+        for action in 0..4 {
+            let ty = character.y as i32 + Self::dy[action];
+            let tx = character.x as i32 + Self::dx[action];
+            if ty >= 0 && ty < H as i32 && tx >= 0 && tx < W as i32 {
+                let point = self.points[ty as usize][tx as usize];
+                if point > best_point {
+                    best_point = point;
+                    best_action = action;
+                }
+            }
+        }
+        let character = &mut self.characters[character_id];
+        character.x = (character.x as i32 + Self::dx[best_action]) as usize;
+        character.y = (character.y as i32 + Self::dy[best_action]) as usize;
+    }
+
+    pub fn advance(&mut self) {
+        for character_id in 0..CHARACTER_N {
+            self.move_player(character_id);
+        }
+        for character in &self.characters {
+            let point = &mut self.points[character.y][character.x];
+            self.game_score += *point;
+            *point = 0;
+        }
+        self.turn += 1;
+    }
+
+    /// 今の配置からゲームを最後まで進めたときの得点
+    pub fn get_score(&self) -> ScoreType {
+        let mut state = self.clone();
+        for character in &self.characters {
+            state.points[character.y][character.x] = 0;
+        }
+        while !state.is_done() {
+            state.advance();
+        }
+        state.game_score as ScoreType
+    }
+}
+
+impl<const H: usize, const W: usize, const END_TURN: i32, const CHARACTER_N: usize> Debug for AutoMoveMazeState<H, W, END_TURN, CHARACTER_N> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let s = (0..H).map(|h| {
+            (0..W).map(|w| {
+                if self.characters.iter().any(|character| character.y == h && character.x == w) {
+                    "@"
+                } else if self.points[h][w] > 0 {
+                    ["0", "1", "2", "3", "4", "5", "6", "7", "8", "9"][self.points[h][w] as usize]
+                } else {
+                    "."
+                }
+            }).collect::<Vec<_>>().join("")
+        }).collect::<Vec<_>>().join("\n");
+        writeln!(f, "turn:\t{}\nscore:\t{}\n{}", self.turn, self.game_score, s)
+    }
+}
@@ -1,185 +1,139 @@
-use std::cmp::Ordering;
-use std::collections::BinaryHeap;
-use std::fmt::{Debug, Formatter};
 use std::time::{Duration, Instant};
 use rand::rngs::SmallRng;
 use rand::{RngCore, SeedableRng};
+use game_search_algorithm::SingleGameState;
+use game_search_algorithm::args::Args;
+use game_search_algorithm::evaluate::evaluate_ai;
+use game_search_algorithm::search::budget::SearchBudget;
+use game_search_algorithm::search::chokudai::{
+    chokudai_search_action_with_budget, chokudai_search_action_with_config, chokudai_search_action_with_node_limit,
+    chokudai_search_action_with_stats, chokudai_search_action_with_window, chokudai_search_adaptive, chokudai_search_plan,
+    parallel_chokudai_search_action,
+};
+use game_search_algorithm::search::config::SearchConfig;
+use game_search_algorithm::search::stats::SearchStats;
+use game_search_algorithm::maze::{Action, MazeParams, MazeState, ZobristTable};
 
 const H: usize = 30;
 const W: usize = 30;
 const END_TURN: i32 = 100;
+const PARAMS: MazeParams = MazeParams { h: H, w: W, end_turn: END_TURN };
 
-type Action = usize;
-
-type ScoreType = i64;
+type State = MazeState;
 
-#[derive(Clone)]
-struct Coord {
-    x: usize,
-    y: usize,
-}
+const FLAGS: [&str; 4] = ["--games", "--beam-width", "--time-ms", "--node-limit"];
 
-#[derive(Clone)]
-struct MazeState {
-    points: [[i32; W]; H],
-    turn: i32,
-    character: Coord,
-    game_score: i32,
-    evaluated_score: ScoreType,
-    first_action: Option<Action>,
+fn test_ai_score(game_number: i32, ai: impl Fn(&State) -> Action) {
+    let stats = evaluate_ai(game_number, PARAMS, ai);
+    println!("Score:\t{}\t(std {:.2}, min {}, max {}, median {})", stats.mean, stats.std_dev, stats.min, stats.max, stats.median);
 }
 
-#[allow(non_upper_case_globals)]
-impl MazeState {
-    const dx: [i32; 4] = [1, -1, 0, 0];
-    const dy: [i32; 4] = [0, 0, 1, -1];
-
-    fn new(seed: u64) -> Self {
-        let mut rng_for_construct = if seed < u64::MAX {
-            SmallRng::seed_from_u64(seed)
-        } else {
-            SmallRng::from_entropy()
-        };
-        let y = rng_for_construct.next_u32() as usize % H;
-        let x = rng_for_construct.next_u32() as usize % W;
-        let character = Coord { x, y };
-        let mut points = [[0; W]; H];
-        for y in 0..H {
-            for x in 0..W {
-                if y == character.y && x == character.x { continue; }
-                points[y][x] = (rng_for_construct.next_u32() % 10) as i32;
-            }
-        }
-        Self {
-            points,
-            turn: 0,
-            character,
-            game_score: 0,
-            evaluated_score: 0,
-            first_action: None,
-        }
-    }
-
-    fn is_done(&self) -> bool {
-        self.turn == END_TURN
-    }
-
-    fn advance(&mut self, action: Action) {
-        self.character.x = (self.character.x as i32 + Self::dx[action]) as usize;
-        self.character.y = (self.character.y as i32 + Self::dy[action]) as usize;
-        if self.points[self.character.y][self.character.x] > 0 {
-            self.game_score += self.points[self.character.y][self.character.x];
-            self.points[self.character.y][self.character.x] = 0;
-        }
-        self.turn += 1;
-    }
-
-    fn evaluate_score(&mut self) {
-        self.evaluated_score = self.game_score as ScoreType
-    }
-
-    fn legal_actions(&self) -> Vec<Action> {
-        let mut actions = Vec::new();
-        for action in 0..4 {
-            let ty = self.character.y as i32 + Self::dy[action];
-            let tx = self.character.x as i32 + Self::dx[action];
-            if ty >= 0 && ty < H as i32 && tx >= 0 && tx < W as i32 {
-                actions.push(action);
-            }
+/// 制限時間内の 1 手あたりの平均の手間を表示する
+fn test_search_stats(game_number: i32, beam_width: i32, time_threshold: Duration) {
+    let mut rng_for_construct = SmallRng::seed_from_u64(0);
+    let mut total = SearchStats::default();
+    let mut move_number = 0;
+    for _ in 0..game_number {
+        let mut state = State::new(rng_for_construct.next_u64(), PARAMS);
+        while !state.is_done() {
+            let (action, stats) = chokudai_search_action_with_stats(&state, beam_width, END_TURN as usize, SearchBudget::Time(time_threshold), None);
+            total.nodes_expanded += stats.nodes_expanded;
+            total.passes_completed += stats.passes_completed;
+            total.elapsed += stats.elapsed;
+            move_number += 1;
+            state.advance(action);
         }
-        actions
     }
+    println!("Nodes/move:\t{:.1}", total.nodes_expanded as f64 / move_number as f64);
+    println!("Passes/move:\t{:.1}", total.passes_completed as f64 / move_number as f64);
+    println!("ms/move:\t{:.3}", total.elapsed.as_secs_f64() * 1000.0 / move_number as f64);
 }
 
-impl Eq for MazeState {}
-
-impl PartialEq<Self> for MazeState {
-    fn eq(&self, other: &Self) -> bool {
-        self.evaluated_score.eq(&other.evaluated_score)
-    }
-}
-
-impl PartialOrd<Self> for MazeState {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        self.evaluated_score.partial_cmp(&other.evaluated_score)
-    }
-}
-
-impl Ord for MazeState {
-    fn cmp(&self, other: &Self) -> Ordering {
-        self.evaluated_score.cmp(&other.evaluated_score)
+/// 同じノード数の上限で探索したときに、Zobrist ハッシュで重複を除くと深さ 1 つあたりに積む状態がどれだけ減り、1 手がどれだけ速くなるかを測る
+fn test_zobrist_dedup(game_number: i32, beam_width: i32, node_limit: usize) {
+    let table = ZobristTable::new(H, W, END_TURN, 0);
+    let hash = |state: &State| state.hash(&table);
+    let mut rng_for_construct = SmallRng::seed_from_u64(0);
+    let (mut plain_total, mut deduped_total) = (SearchStats::default(), SearchStats::default());
+    let mut depth_sum = 0;
+    for _ in 0..game_number {
+        let mut state = State::new(rng_for_construct.next_u64(), PARAMS);
+        while !state.is_done() {
+            let budget = SearchBudget::Nodes(node_limit);
+            let (action, plain) = chokudai_search_action_with_stats(&state, beam_width, END_TURN as usize, budget, None);
+            let (_, deduped) = chokudai_search_action_with_stats(&state, beam_width, END_TURN as usize, budget, Some(&hash));
+            plain_total.states_pushed += plain.states_pushed;
+            plain_total.elapsed += plain.elapsed;
+            deduped_total.states_pushed += deduped.states_pushed;
+            deduped_total.elapsed += deduped.elapsed;
+            depth_sum += plain.max_depth_reached;
+            state.advance(action);
+        }
     }
+    println!(
+        "Pushed/depth:\t{:.1} (plain) vs {:.1} (zobrist)",
+        plain_total.states_pushed as f64 / depth_sum as f64,
+        deduped_total.states_pushed as f64 / depth_sum as f64,
+    );
+    println!("Speed-up:\t{:.2}x", plain_total.elapsed.as_secs_f64() / deduped_total.elapsed.as_secs_f64());
 }
 
-impl Debug for MazeState {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        let s = (0..H).map(|h| {
-            (0..W).map(|w| {
-                if self.character.y == h && self.character.x == w {
-                    "@"
-                } else if self.points[h][w] > 0 {
-                    ["0", "1", "2", "3", "4", "5", "6", "7", "8", "9"][self.points[h][w] as usize]
-                } else {
-                    "."
-                }
-            }).collect::<Vec<_>>().join("")
-        }).collect::<Vec<_>>().join("\n");
-        writeln!(f, "turn:\t{}\nscore:\t{}\n{}", self.turn, self.game_score, s)
-    }
+/// 長い制限時間で探索したとき、`window` の有無でビームに溜まる状態の数を
+/// `(beam_depth + 1) * beam_width * (1 + 行動の数)` (残した分と 1 回の走査で積む分) と比べる
+fn test_window_bounds_beam(beam_width: i32, time_threshold: Duration) {
+    let state = State::new(0, PARAMS);
+    let beam_depth = END_TURN as usize;
+    let bound = (beam_depth + 1) * beam_width as usize * (1 + 4);
+    let (_, unbounded) = chokudai_search_plan(&state, beam_width, beam_depth, SearchBudget::Time(time_threshold), None, false);
+    let (_, windowed) = chokudai_search_plan(&state, beam_width, beam_depth, SearchBudget::Time(time_threshold), None, true);
+    println!("Peak states:\t{} (window {}, bound {bound})", unbounded.peak_states, windowed.peak_states);
 }
 
-type State = MazeState;
-
-fn chokudai_search_action_with_time_threshold(state: &State, beam_width: i32, beam_depth: usize, time_threshold: Duration) -> Action {
-    let time_keeper = Instant::now();
-    let mut beam = vec![BinaryHeap::new(); beam_depth + 1];
-    beam[0].push(state.clone());
-    loop {
-        for t in 0..beam_depth {
-            for _ in 0..beam_width {
-                if beam[t].is_empty() { break; }
-                if beam[t].peek().unwrap().is_done() { break; }
-                let now_state = beam[t].pop().unwrap();
-                let legal_actions = now_state.legal_actions();
-                for action in legal_actions {
-                    let mut next_state = now_state.clone();
-                    next_state.advance(action);
-                    next_state.evaluate_score();
-                    if t == 0 {
-                        next_state.first_action = Some(action);
-                    }
-                    beam[t + 1].push(next_state);
-                }
+/// 上下左右だけの盤面と斜めにも動ける盤面で、制限時間付きの Chokudai サーチの得点と 1 手あたりの時間を別々に測る
+fn test_diagonal_score(game_number: i32, config: &SearchConfig) {
+    for allow_diagonal in [false, true] {
+        let mut rng_for_construct = SmallRng::seed_from_u64(0);
+        let mut score_mean = 0.0;
+        let mut elapsed = Duration::ZERO;
+        let mut move_number = 0;
+        for _ in 0..game_number {
+            let seed = rng_for_construct.next_u64();
+            let mut state = if allow_diagonal { State::new_with_diagonals(seed, PARAMS) } else { State::new(seed, PARAMS) };
+            while !state.is_done() {
+                let start = Instant::now();
+                let action = chokudai_search_action_with_config(&state, config, None);
+                elapsed += start.elapsed();
+                move_number += 1;
+                state.advance(action);
             }
+            score_mean += state.game_score() as f64;
         }
-        if time_keeper.elapsed() >= time_threshold {
-            break;
-        }
-    }
-    for t in (0..=beam_depth).rev() {
-        if let Some(state) = beam[t].peek() {
-            return state.first_action.unwrap();
-        }
+        score_mean /= game_number as f64;
+        let direction_number = if allow_diagonal { 8 } else { 4 };
+        println!("Score ({direction_number} directions):\t{score_mean}\t({:.3} ms/move)", elapsed.as_secs_f64() * 1000.0 / move_number as f64);
     }
-    debug_assert!(false);
-    Action::MAX
-}
-
-fn test_ai_score(game_number: i32) {
-    let mut rng_for_construct = SmallRng::seed_from_u64(0);
-    let mut score_mean = 0.0;
-    for _ in 0..game_number {
-        let mut state = MazeState::new(rng_for_construct.next_u64());
-        while !state.is_done() {
-            state.advance(chokudai_search_action_with_time_threshold(&state, 1, END_TURN as usize, Duration::from_millis(10)));
-        }
-        let score = state.game_score;
-        score_mean += score as f64;
-    }
-    score_mean /= game_number as f64;
-    println!("Score:\t{score_mean}");
 }
 
 fn main() {
-    test_ai_score(100);
-}
\ No newline at end of file
+    let args = Args::from_env(&FLAGS);
+    let game_number = args.get_at_least_or_exit("--games", 100, 1);
+    let beam_width = args.get_at_least_or_exit("--beam-width", 1, 1);
+    let time_threshold = Duration::from_millis(args.get_or_exit("--time-ms", 10));
+    let node_limit = args.get_at_least_or_exit("--node-limit", 1000, 1);
+    let table = ZobristTable::new(H, W, END_TURN, 0);
+    let hash = |state: &State| state.hash(&table);
+    let beam_depth = END_TURN as usize;
+    let config = SearchConfig::new().beam_width(beam_width as usize).beam_depth(beam_depth).time_limit(time_threshold).build();
+    test_ai_score(game_number, |state| chokudai_search_action_with_config(state, &config, None));
+    test_ai_score(game_number, |state| chokudai_search_action_with_config(state, &config, Some(&hash)));
+    test_ai_score(game_number, |state| chokudai_search_action_with_node_limit(state, beam_width, beam_depth, node_limit));
+    test_ai_score(game_number, |state| chokudai_search_action_with_budget(state, beam_width, beam_depth, SearchBudget::Either(time_threshold, node_limit), None));
+    test_ai_score(game_number, |state| chokudai_search_adaptive(state, beam_depth, time_threshold));
+    let thread_number = std::thread::available_parallelism().map_or(1, |thread_number| thread_number.get());
+    test_ai_score(game_number, |state| parallel_chokudai_search_action(state, beam_width, beam_depth, SearchBudget::Time(time_threshold), thread_number, false));
+    test_search_stats(5, beam_width, time_threshold);
+    test_zobrist_dedup(5, beam_width, node_limit);
+    test_ai_score(game_number, |state| chokudai_search_action_with_window(state, beam_width, beam_depth, SearchBudget::Time(time_threshold)));
+    test_window_bounds_beam(beam_width, Duration::from_millis(500));
+    test_diagonal_score(20, &config);
+}
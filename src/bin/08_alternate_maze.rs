@@ -0,0 +1,36 @@
+use rand::rngs::SmallRng;
+use rand::{RngCore, SeedableRng};
+use game_search_algorithm::alternate_maze::{AlternateMazeState, WinningStatus};
+use game_search_algorithm::maze::Action;
+
+const H: usize = 3;
+const W: usize = 3;
+const END_TURN: i32 = 4;
+
+type State = AlternateMazeState<H, W, END_TURN>;
+
+fn random_action(state: &State, rng: &mut SmallRng) -> Action {
+    let legal_actions = state.legal_actions();
+    legal_actions[rng.next_u32() as usize % legal_actions.len()]
+}
+
+fn play_game(seed: u64) {
+    let mut rng = SmallRng::from_entropy();
+    let mut state = State::new(seed);
+    println!("{:?}", state);
+    while !state.is_done() {
+        state.advance(random_action(&state, &mut rng));
+        println!("{:?}", state);
+    }
+    let winning_status = state.winning_status();
+    let winner = match (winning_status, state.is_first()) {
+        (WinningStatus::Draw, _) => "DRAW",
+        (WinningStatus::Win, true) | (WinningStatus::Lose, false) => "A",
+        _ => "B",
+    };
+    println!("winner:\t{winner}");
+}
+
+fn main() {
+    play_game(121321);
+}
@@ -0,0 +1,44 @@
+use rand::rngs::SmallRng;
+use rand::{RngCore, SeedableRng};
+use game_search_algorithm::alternate_maze::{AlternateMazeState, WinningStatus};
+use game_search_algorithm::maze::Action;
+use game_search_algorithm::search::mini_max::mini_max_action;
+
+const H: usize = 3;
+const W: usize = 3;
+const END_TURN: i32 = 4;
+
+type State = AlternateMazeState<H, W, END_TURN>;
+
+fn random_action(state: &State, rng: &mut SmallRng) -> Action {
+    let legal_actions = state.legal_actions();
+    legal_actions[rng.next_u32() as usize % legal_actions.len()]
+}
+
+fn test_first_player_win_rate(game_number: i32) {
+    let mut rng_for_action = SmallRng::seed_from_u64(0);
+    let mut rng_for_construct = SmallRng::seed_from_u64(0);
+    let mut first_player_win_rate = 0.0;
+    for _ in 0..game_number {
+        let mut state = State::new(rng_for_construct.next_u64());
+        while !state.is_done() {
+            if state.is_first() {
+                state.advance(mini_max_action(&state, END_TURN as usize));
+            } else {
+                state.advance(random_action(&state, &mut rng_for_action));
+            }
+        }
+        let win_rate_point = match (state.winning_status(), state.is_first()) {
+            (WinningStatus::Draw, _) => 0.5,
+            (WinningStatus::Win, true) | (WinningStatus::Lose, false) => 1.0,
+            _ => 0.0,
+        };
+        first_player_win_rate += win_rate_point;
+    }
+    first_player_win_rate /= game_number as f64;
+    println!("Win rate:\t{first_player_win_rate}");
+}
+
+fn main() {
+    test_first_player_win_rate(100);
+}
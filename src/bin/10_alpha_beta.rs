@@ -0,0 +1,113 @@
+use rand::rngs::SmallRng;
+use rand::{RngCore, SeedableRng};
+use game_search_algorithm::alternate_maze::AlternateMazeState;
+use game_search_algorithm::maze::Action;
+use game_search_algorithm::search::alpha_beta::{alpha_beta_action_with_count, ordered_alpha_beta_action_with_count};
+use game_search_algorithm::search::mini_max::mini_max_action_with_count;
+
+const H: usize = 5;
+const W: usize = 5;
+const END_TURN: i32 = 10;
+
+type State = AlternateMazeState<H, W, END_TURN>;
+
+fn random_action(state: &State, rng: &mut SmallRng) -> Action {
+    let legal_actions = state.legal_actions();
+    legal_actions[rng.next_u32() as usize % legal_actions.len()]
+}
+
+fn greedy_action(state: &State) -> Action {
+    let mut best_score = None;
+    let mut best_action = None;
+    for action in state.legal_actions() {
+        let mut next_state = state.clone();
+        next_state.advance(action);
+        let score = -next_state.teban_score();
+        if best_score.is_none_or(|best_score| score > best_score) {
+            best_score = Some(score);
+            best_action = Some(action);
+        }
+    }
+    best_action.unwrap()
+}
+
+fn test_win_rate_against_greedy(game_number: i32, depth: usize) {
+    let mut rng_for_construct = SmallRng::seed_from_u64(0);
+    let mut win_number = 0.0;
+    for i in 0..game_number {
+        let mut state = State::new(rng_for_construct.next_u64());
+        let alpha_beta_is_first = i % 2 == 0;
+        while !state.is_done() {
+            if state.is_first() == alpha_beta_is_first {
+                state.advance(alpha_beta_action_with_count(&state, depth).0);
+            } else {
+                state.advance(greedy_action(&state));
+            }
+        }
+        let [first, second] = state.players_in_order();
+        let (alpha_beta_score, greedy_score) = if alpha_beta_is_first {
+            (first.game_score, second.game_score)
+        } else {
+            (second.game_score, first.game_score)
+        };
+        if alpha_beta_score > greedy_score {
+            win_number += 1.0;
+        } else if alpha_beta_score == greedy_score {
+            win_number += 0.5;
+        }
+    }
+    println!("Win rate against greedy:\t{}", win_number / game_number as f64);
+}
+
+/// アルファ・ベータ法とミニマックス法が同じ手を選んだ局面の数と、探索した節点の数を比べる
+fn compare_with_mini_max(game_number: i32, depth: usize) {
+    let mut rng_for_action = SmallRng::seed_from_u64(0);
+    let mut rng_for_construct = SmallRng::seed_from_u64(0);
+    let mut position_number = 0;
+    let mut same_action_number = 0;
+    let mut alpha_beta_node_count = 0;
+    let mut mini_max_node_count = 0;
+    for _ in 0..game_number {
+        let mut state = State::new(rng_for_construct.next_u64());
+        while !state.is_done() {
+            let (alpha_beta_action, alpha_beta_count) = alpha_beta_action_with_count(&state, depth);
+            let (mini_max_action, mini_max_count) = mini_max_action_with_count(&state, depth);
+            position_number += 1;
+            if alpha_beta_action == mini_max_action {
+                same_action_number += 1;
+            }
+            alpha_beta_node_count += alpha_beta_count;
+            mini_max_node_count += mini_max_count;
+            state.advance(random_action(&state, &mut rng_for_action));
+        }
+    }
+    println!("Same action:\t{same_action_number}/{position_number}");
+    println!("Nodes (alpha-beta):\t{alpha_beta_node_count}");
+    println!("Nodes (mini-max):\t{mini_max_node_count}");
+}
+
+/// 手の並べ替えとキラームーブで、探索する節点がどれだけ減るかを測る
+fn compare_move_ordering(game_number: i32, depth: usize) {
+    let mut rng_for_action = SmallRng::seed_from_u64(0);
+    let mut rng_for_construct = SmallRng::seed_from_u64(0);
+    let mut unordered_node_count = 0;
+    let mut ordered_node_count = 0;
+    for _ in 0..game_number {
+        let mut state = State::new(rng_for_construct.next_u64());
+        while !state.is_done() {
+            let (_, unordered_count) = alpha_beta_action_with_count(&state, depth);
+            let (_, ordered_count) = ordered_alpha_beta_action_with_count(&state, depth);
+            unordered_node_count += unordered_count;
+            ordered_node_count += ordered_count;
+            state.advance(random_action(&state, &mut rng_for_action));
+        }
+    }
+    println!("Nodes (unordered):\t{unordered_node_count}");
+    println!("Nodes (ordered):\t{ordered_node_count}\t({:.1}%)", ordered_node_count as f64 / unordered_node_count as f64 * 100.0);
+}
+
+fn main() {
+    compare_with_mini_max(100, END_TURN as usize);
+    compare_move_ordering(100, END_TURN as usize);
+    test_win_rate_against_greedy(100, END_TURN as usize);
+}
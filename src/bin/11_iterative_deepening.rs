@@ -0,0 +1,48 @@
+use std::time::Duration;
+use rand::rngs::SmallRng;
+use rand::{RngCore, SeedableRng};
+use game_search_algorithm::alternate_maze::{AlternateMazeState, WinningStatus};
+use game_search_algorithm::search::iterative_deepening::{alpha_beta_iterative_deepening_action, alpha_beta_iterative_deepening_action_with_depth};
+
+const H: usize = 7;
+const W: usize = 7;
+const END_TURN: i32 = 20;
+
+type State = AlternateMazeState<H, W, END_TURN>;
+
+fn test_first_player_win_rate(game_number: i32, first_time_threshold: Duration, second_time_threshold: Duration) {
+    let mut rng_for_construct = SmallRng::seed_from_u64(0);
+    let mut first_player_win_rate = 0.0;
+    for _ in 0..game_number {
+        let mut state = State::new(rng_for_construct.next_u64());
+        while !state.is_done() {
+            if state.is_first() {
+                state.advance(alpha_beta_iterative_deepening_action(&state, first_time_threshold));
+            } else {
+                state.advance(alpha_beta_iterative_deepening_action(&state, second_time_threshold));
+            }
+        }
+        let win_rate_point = match (state.winning_status(), state.is_first()) {
+            (WinningStatus::Draw, _) => 0.5,
+            (WinningStatus::Win, true) | (WinningStatus::Lose, false) => 1.0,
+            _ => 0.0,
+        };
+        first_player_win_rate += win_rate_point;
+    }
+    first_player_win_rate /= game_number as f64;
+    println!("Win rate:\t{first_player_win_rate}");
+}
+
+/// 制限時間ごとに読み切れた深さを表示する
+fn test_completed_depth(short_time_threshold: Duration, long_time_threshold: Duration) {
+    let state = State::new(0);
+    let (_, short_depth) = alpha_beta_iterative_deepening_action_with_depth(&state, short_time_threshold);
+    let (_, long_depth) = alpha_beta_iterative_deepening_action_with_depth(&state, long_time_threshold);
+    println!("Depth ({short_time_threshold:?}):\t{short_depth}");
+    println!("Depth ({long_time_threshold:?}):\t{long_depth}");
+}
+
+fn main() {
+    test_completed_depth(Duration::from_millis(1), Duration::from_millis(100));
+    test_first_player_win_rate(20, Duration::from_millis(10), Duration::from_millis(1));
+}
@@ -0,0 +1,84 @@
+use rand::rngs::SmallRng;
+use rand::{RngCore, SeedableRng};
+use game_search_algorithm::alternate_maze::{AlternateMazeState, WinningStatus};
+use game_search_algorithm::maze::Action;
+use game_search_algorithm::search::playout::playout;
+
+const H: usize = 5;
+const W: usize = 5;
+const END_TURN: i32 = 10;
+
+type State = AlternateMazeState<H, W, END_TURN>;
+
+fn random_action(state: &State, rng: &mut SmallRng) -> Action {
+    let legal_actions = state.legal_actions();
+    legal_actions[rng.next_u32() as usize % legal_actions.len()]
+}
+
+fn primitive_montecarlo_action(state: &State, playout_number: usize) -> Action {
+    let mut rng_for_playout = SmallRng::seed_from_u64(0);
+    let mut best_action = None;
+    let mut best_value_mean = f64::NEG_INFINITY;
+    for action in state.legal_actions() {
+        let mut next_state = state.clone();
+        next_state.advance(action);
+        let mut value_mean = 0.0;
+        for _ in 0..playout_number {
+            value_mean -= playout(&mut next_state.clone(), &mut rng_for_playout) as f64;
+        }
+        value_mean /= playout_number as f64;
+        if best_action.is_none() || value_mean > best_value_mean {
+            best_action = Some(action);
+            best_value_mean = value_mean;
+        }
+    }
+    best_action.unwrap()
+}
+
+fn test_first_player_win_rate(game_number: i32) {
+    let mut rng_for_action = SmallRng::seed_from_u64(0);
+    let mut rng_for_construct = SmallRng::seed_from_u64(0);
+    let mut first_player_win_rate = 0.0;
+    for _ in 0..game_number {
+        let mut state = State::new(rng_for_construct.next_u64());
+        while !state.is_done() {
+            if state.is_first() {
+                state.advance(primitive_montecarlo_action(&state, 1000));
+            } else {
+                state.advance(random_action(&state, &mut rng_for_action));
+            }
+        }
+        let win_rate_point = match (state.winning_status(), state.is_first()) {
+            (WinningStatus::Draw, _) => 0.5,
+            (WinningStatus::Win, true) | (WinningStatus::Lose, false) => 1.0,
+            _ => 0.0,
+        };
+        first_player_win_rate += win_rate_point;
+    }
+    first_player_win_rate /= game_number as f64;
+    println!("Win rate:\t{first_player_win_rate}");
+}
+
+fn main() {
+    test_first_player_win_rate(100);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 先手の真上にだけ 9 点がある盤面では、十分な回数のプレイアウトで 1 手で最も得点が増える手 (上) を選ぶ
+    #[test]
+    fn picks_greedy_optimal_action_on_trivial_board() {
+        let mut points = [[0; W]; H];
+        points[H / 2 - 1][W / 2 - 1] = 9;
+        let state = State::from_points(points);
+        let greedy_action = state.legal_actions().into_iter().max_by_key(|&action| {
+            let mut next_state = state.clone();
+            next_state.advance(action);
+            -next_state.teban_score()
+        }).unwrap();
+        assert_eq!(greedy_action, Action::Up);
+        assert_eq!(primitive_montecarlo_action(&state, 1000), greedy_action);
+    }
+}
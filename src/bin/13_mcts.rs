@@ -0,0 +1,173 @@
+use rand::rngs::SmallRng;
+use rand::{RngCore, SeedableRng};
+use game_search_algorithm::alternate_maze::{AlternateMazeState, WinningStatus};
+use game_search_algorithm::maze::Action;
+use game_search_algorithm::search::playout::playout;
+
+const H: usize = 5;
+const W: usize = 5;
+const END_TURN: i32 = 10;
+
+const EXPAND_THRESHOLD: u32 = 10;
+
+type State = AlternateMazeState<H, W, END_TURN>;
+
+fn random_action(state: &State, rng: &mut SmallRng) -> Action {
+    let legal_actions = state.legal_actions();
+    legal_actions[rng.next_u32() as usize % legal_actions.len()]
+}
+
+/// 手番のプレイヤーから見た勝ち点 (勝ち 1、引き分け 0.5、負け 0)
+fn win_rate_point(state: &State) -> f64 {
+    match state.winning_status() {
+        WinningStatus::Win => 1.0,
+        WinningStatus::Lose => 0.0,
+        _ => 0.5,
+    }
+}
+
+/// 終局までランダムに進め、`state` の手番のプレイヤーから見た勝ち点を返す
+fn playout_win_rate_point(state: &State, rng: &mut SmallRng) -> f64 {
+    match playout(&mut state.clone(), rng).signum() {
+        1 => 1.0,
+        -1 => 0.0,
+        _ => 0.5,
+    }
+}
+
+struct Node {
+    state: State,
+    w: f64,
+    n: u32,
+    child_nodes: Vec<(Action, Node)>,
+}
+
+impl Node {
+    fn new(state: State) -> Self {
+        Self {
+            state,
+            w: 0.0,
+            n: 0,
+            child_nodes: Vec::new(),
+        }
+    }
+
+    /// このノードの手番のプレイヤーから見た価値を返す
+    fn evaluate(&mut self, c: f64, rng: &mut SmallRng) -> f64 {
+        if self.state.is_done() {
+            let value = win_rate_point(&self.state);
+            self.w += value;
+            self.n += 1;
+            return value;
+        }
+        if self.child_nodes.is_empty() {
+            let value = playout_win_rate_point(&self.state, rng);
+            self.w += value;
+            self.n += 1;
+            if self.n == EXPAND_THRESHOLD {
+                self.expand();
+            }
+            return value;
+        }
+        let value = 1.0 - self.next_child_node(c).evaluate(c, rng);
+        self.w += value;
+        self.n += 1;
+        value
+    }
+
+    fn expand(&mut self) {
+        for action in self.state.legal_actions() {
+            let mut state = self.state.clone();
+            state.advance(action);
+            self.child_nodes.push((action, Node::new(state)));
+        }
+    }
+
+    fn ucb1(&self, t: f64, c: f64) -> f64 {
+        if self.n == 0 {
+            return f64::INFINITY;
+        }
+        1.0 - self.w / self.n as f64 + c * (2.0 * t.ln() / self.n as f64).sqrt()
+    }
+
+    /// 未訪問の子ノードを優先し、全て訪問済みなら UCB1 が最大の子ノードを選ぶ
+    fn next_child_node(&mut self, c: f64) -> &mut Node {
+        let t = self.child_nodes.iter().map(|(_, child)| child.n as f64).sum::<f64>();
+        let mut best_value = f64::NEG_INFINITY;
+        let mut best_index = 0;
+        for (i, (_, child)) in self.child_nodes.iter().enumerate() {
+            let ucb1_value = child.ucb1(t, c);
+            if ucb1_value > best_value {
+                best_value = ucb1_value;
+                best_index = i;
+            }
+        }
+        &mut self.child_nodes[best_index].1
+    }
+}
+
+fn mcts_action(state: &State, playout_number: u32, c: f64, rng: &mut SmallRng) -> Action {
+    let mut root_node = Node::new(state.clone());
+    root_node.expand();
+    for _ in 0..playout_number {
+        root_node.evaluate(c, rng);
+    }
+    let mut best_action_searched_number = 0;
+    let mut best_action = None;
+    for (action, child) in &root_node.child_nodes {
+        if best_action.is_none() || child.n > best_action_searched_number {
+            best_action_searched_number = child.n;
+            best_action = Some(*action);
+        }
+    }
+    best_action.unwrap()
+}
+
+fn test_first_player_win_rate(game_number: i32) {
+    let mut rng_for_action = SmallRng::seed_from_u64(0);
+    let mut rng_for_construct = SmallRng::seed_from_u64(0);
+    let mut first_player_win_rate = 0.0;
+    for _ in 0..game_number {
+        let mut state = State::new(rng_for_construct.next_u64());
+        while !state.is_done() {
+            if state.is_first() {
+                state.advance(mcts_action(&state, 3000, 1.0, &mut rng_for_action));
+            } else {
+                state.advance(random_action(&state, &mut rng_for_action));
+            }
+        }
+        let win_rate_point = match (state.winning_status(), state.is_first()) {
+            (WinningStatus::Draw, _) => 0.5,
+            (WinningStatus::Win, true) | (WinningStatus::Lose, false) => 1.0,
+            _ => 0.0,
+        };
+        first_player_win_rate += win_rate_point;
+    }
+    first_player_win_rate /= game_number as f64;
+    println!("Win rate:\t{first_player_win_rate}");
+}
+
+fn main() {
+    test_first_player_win_rate(100);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 合法手の数だけ評価すると、UCB1 が未訪問の子ノードを優先するので根の子ノードはちょうど 1 回ずつ訪問される
+    #[test]
+    fn unvisited_children_come_first() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let mut root_node = Node::new(State::new(0));
+        root_node.expand();
+        let action_number = root_node.state.legal_actions().len();
+        assert_eq!(root_node.child_nodes.len(), action_number);
+        for _ in 0..action_number {
+            root_node.evaluate(1.0, &mut rng);
+        }
+        assert!(root_node.child_nodes.iter().all(|(_, child)| child.n == 1));
+        root_node.evaluate(1.0, &mut rng);
+        assert_eq!(root_node.child_nodes.iter().map(|(_, child)| child.n).sum::<u32>(), action_number as u32 + 1);
+    }
+}
@@ -0,0 +1,89 @@
+use rand::rngs::SmallRng;
+use rand::{RngCore, SeedableRng};
+use game_search_algorithm::auto_move_maze::AutoMoveMazeState;
+use game_search_algorithm::search::hill_climb::{hill_climb, hill_climb_from, hill_climb_with_strategy, HillClimbStrategy};
+use game_search_algorithm::search::random_restart::random_restart;
+
+const H: usize = 5;
+const W: usize = 5;
+const END_TURN: i32 = 5;
+const CHARACTER_N: usize = 3;
+
+type State = AutoMoveMazeState<H, W, END_TURN, CHARACTER_N>;
+
+fn random_action(state: &State, rng: &mut SmallRng) -> State {
+    let mut now_state = state.clone();
+    now_state.init(rng);
+    now_state
+}
+
+fn test_ai_score(game_number: i32) {
+    let mut rng_for_action = SmallRng::seed_from_u64(0);
+    let mut rng_for_construct = SmallRng::seed_from_u64(0);
+    let mut random_score_mean = 0.0;
+    let mut hill_climb_score_mean = 0.0;
+    for _ in 0..game_number {
+        let state = State::new(rng_for_construct.next_u64());
+        random_score_mean += random_action(&state, &mut rng_for_action).get_score() as f64;
+        hill_climb_score_mean += hill_climb(&state, 10000, &mut rng_for_action).get_score() as f64;
+    }
+    random_score_mean /= game_number as f64;
+    hill_climb_score_mean /= game_number as f64;
+    println!("Random:\t{random_score_mean}");
+    println!("Hill climb:\t{hill_climb_score_mean}");
+}
+
+/// 同じ初期配置から最初の改善と最良改善で山登りし、得点と収束までの回数と 1 回の移動で計算する得点の数を比べる
+fn test_compare_strategies(game_number: i32) {
+    let rng_for_construct = SmallRng::seed_from_u64(0);
+    for strategy in [HillClimbStrategy::FirstImproving, HillClimbStrategy::BestImproving] {
+        let mut rng_for_construct = rng_for_construct.clone();
+        let mut score_mean = 0.0;
+        let mut iteration_mean = 0.0;
+        let mut evaluation_per_iteration_mean = 0.0;
+        for _ in 0..game_number {
+            let seed = rng_for_construct.next_u64();
+            let state = State::new(seed);
+            let (state, iteration, evaluation_count) = hill_climb_with_strategy(&state, strategy, 10000, &mut SmallRng::seed_from_u64(seed));
+            score_mean += state.get_score() as f64;
+            iteration_mean += iteration as f64;
+            // 最後の走査 (どの近傍でも得点が上がらなかった走査) も 1 回と数える
+            evaluation_per_iteration_mean += evaluation_count as f64 / (iteration + 1) as f64;
+        }
+        score_mean /= game_number as f64;
+        iteration_mean /= game_number as f64;
+        evaluation_per_iteration_mean /= game_number as f64;
+        println!("{strategy:?}:\tscore {score_mean}\titerations {iteration_mean}\tevaluations per iteration {evaluation_per_iteration_mean:.1}");
+    }
+}
+
+/// 同じ乱数から始めて、やり直しが 1 回と 10 回のときの最良の得点を比べる
+fn test_random_restart_is_monotonic(game_number: i32) {
+    let mut rng_for_construct = SmallRng::seed_from_u64(0);
+    let mut first_score_mean = 0.0;
+    let mut last_score_mean = 0.0;
+    for _ in 0..game_number {
+        let seed = rng_for_construct.next_u64();
+        let state = State::new(seed);
+        let make_initial = |rng: &mut SmallRng| random_action(&state, rng);
+        let optimize = |state: State| {
+            let (state, _, _) = hill_climb_from(state, HillClimbStrategy::FirstImproving, 10000);
+            let score = state.get_score();
+            (state, score)
+        };
+        let scores = (1..=10).map(|restarts| {
+            random_restart(make_initial, optimize, restarts, &mut SmallRng::seed_from_u64(seed)).1
+        }).collect::<Vec<_>>();
+        first_score_mean += scores[0] as f64;
+        last_score_mean += scores[scores.len() - 1] as f64;
+    }
+    first_score_mean /= game_number as f64;
+    last_score_mean /= game_number as f64;
+    println!("Random restart:\t{first_score_mean} (1 start) <= {last_score_mean} (10 starts)");
+}
+
+fn main() {
+    test_ai_score(100);
+    test_compare_strategies(100);
+    test_random_restart_is_monotonic(20);
+}
@@ -0,0 +1,32 @@
+use rand::rngs::SmallRng;
+use rand::{RngCore, SeedableRng};
+use game_search_algorithm::auto_move_maze::AutoMoveMazeState;
+use game_search_algorithm::search::hill_climb::hill_climb;
+use game_search_algorithm::search::simulated_annealing::simulated_annealing;
+
+const H: usize = 5;
+const W: usize = 5;
+const END_TURN: i32 = 5;
+const CHARACTER_N: usize = 3;
+
+type State = AutoMoveMazeState<H, W, END_TURN, CHARACTER_N>;
+
+fn test_ai_score(game_number: i32) {
+    let mut rng_for_action = SmallRng::seed_from_u64(0);
+    let mut rng_for_construct = SmallRng::seed_from_u64(0);
+    let mut hill_climb_score_mean = 0.0;
+    let mut annealing_score_mean = 0.0;
+    for _ in 0..game_number {
+        let state = State::new(rng_for_construct.next_u64());
+        hill_climb_score_mean += hill_climb(&state, 10000, &mut rng_for_action).get_score() as f64;
+        annealing_score_mean += simulated_annealing(&state, 10000, 500.0, 10.0, &mut rng_for_action).get_score() as f64;
+    }
+    hill_climb_score_mean /= game_number as f64;
+    annealing_score_mean /= game_number as f64;
+    println!("Hill climb:\t{hill_climb_score_mean}");
+    println!("Simulated annealing:\t{annealing_score_mean}");
+}
+
+fn main() {
+    test_ai_score(100);
+}
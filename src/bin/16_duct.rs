@@ -0,0 +1,184 @@
+use rand::rngs::SmallRng;
+use rand::{RngCore, SeedableRng};
+use game_search_algorithm::maze::Action;
+use game_search_algorithm::simultaneous_maze::SimultaneousMazeState;
+
+const H: usize = 3;
+const W: usize = 3;
+const END_TURN: i32 = 4;
+
+const C: f64 = 1.0;
+const EXPAND_THRESHOLD: u32 = 5;
+
+type State = SimultaneousMazeState<H, W, END_TURN>;
+
+fn random_action(state: &State, player_id: usize, rng: &mut SmallRng) -> Action {
+    let legal_actions = state.legal_actions(player_id);
+    legal_actions[rng.next_u32() as usize % legal_actions.len()]
+}
+
+/// 終局まで二人ともランダムに進め、プレイヤー 0 から見た勝ち点を返す
+fn playout(state: &State, rng: &mut SmallRng) -> f64 {
+    let mut now_state = state.clone();
+    while !now_state.is_done() {
+        let action0 = random_action(&now_state, 0, rng);
+        let action1 = random_action(&now_state, 1, rng);
+        now_state.advance(action0, action1);
+    }
+    now_state.white_score()
+}
+
+/// 子ノードを二人の行動の組で持ち、プレイヤーごとに自分の行動で集計した UCB1 で行動を選ぶノード
+struct Node {
+    state: State,
+    w: f64,
+    n: u32,
+    child_nodes: Vec<Vec<Node>>,
+}
+
+impl Node {
+    fn new(state: State) -> Self {
+        Self {
+            state,
+            w: 0.0,
+            n: 0,
+            child_nodes: Vec::new(),
+        }
+    }
+
+    /// プレイヤー 0 から見た価値を返す
+    fn evaluate(&mut self, rng: &mut SmallRng) -> f64 {
+        if self.state.is_done() {
+            let value = self.state.white_score();
+            self.w += value;
+            self.n += 1;
+            return value;
+        }
+        if self.child_nodes.is_empty() {
+            let value = playout(&self.state, rng);
+            self.w += value;
+            self.n += 1;
+            if self.n == EXPAND_THRESHOLD {
+                self.expand();
+            }
+            return value;
+        }
+        let value = self.next_child_node().evaluate(rng);
+        self.w += value;
+        self.n += 1;
+        value
+    }
+
+    fn expand(&mut self) {
+        let legal_actions1 = self.state.legal_actions(1);
+        self.child_nodes = self.state.legal_actions(0).into_iter().map(|action0| {
+            legal_actions1.iter().map(|&action1| {
+                let mut state = self.state.clone();
+                state.advance(action0, action1);
+                Node::new(state)
+            }).collect()
+        }).collect();
+    }
+
+    /// 行動 `i` を選んだ子ノードを集計した `(w, n)` (`player_id` が 1 なら列で集計する)
+    fn action_stats(&self, player_id: usize, i: usize) -> (f64, f64) {
+        let children: Vec<&Node> = if player_id == 0 {
+            self.child_nodes[i].iter().collect()
+        } else {
+            self.child_nodes.iter().map(|row| &row[i]).collect()
+        };
+        (children.iter().map(|child| child.w).sum(), children.iter().map(|child| child.n as f64).sum())
+    }
+
+    fn best_index(&self, player_id: usize, t: f64) -> usize {
+        let action_number = if player_id == 0 { self.child_nodes.len() } else { self.child_nodes[0].len() };
+        let mut best_value = f64::NEG_INFINITY;
+        let mut best_index = 0;
+        for i in 0..action_number {
+            let (w, n) = self.action_stats(player_id, i);
+            let win_rate = if player_id == 0 { w / n } else { 1.0 - w / n };
+            let ucb1_value = win_rate + C * (2.0 * t.ln() / n).sqrt();
+            if ucb1_value > best_value {
+                best_value = ucb1_value;
+                best_index = i;
+            }
+        }
+        best_index
+    }
+
+    /// 未訪問の子ノードを優先し、全て訪問済みなら二人それぞれの UCB1 が最大の行動の組を選ぶ
+    fn next_child_node(&mut self) -> &mut Node {
+        for i in 0..self.child_nodes.len() {
+            if let Some(j) = self.child_nodes[i].iter().position(|child| child.n == 0) {
+                return &mut self.child_nodes[i][j];
+            }
+        }
+        let t = self.child_nodes.iter().flatten().map(|child| child.n as f64).sum::<f64>();
+        let best_i = self.best_index(0, t);
+        let best_j = self.best_index(1, t);
+        &mut self.child_nodes[best_i][best_j]
+    }
+}
+
+fn duct_action(state: &State, player_id: usize, playout_number: u32, rng: &mut SmallRng) -> Action {
+    let mut root_node = Node::new(state.clone());
+    root_node.expand();
+    for _ in 0..playout_number {
+        root_node.evaluate(rng);
+    }
+    let legal_actions = state.legal_actions(player_id);
+    let mut best_action_searched_number = 0.0;
+    let mut best_action = None;
+    for (i, &action) in legal_actions.iter().enumerate() {
+        let (_, n) = root_node.action_stats(player_id, i);
+        if best_action.is_none() || n > best_action_searched_number {
+            best_action_searched_number = n;
+            best_action = Some(action);
+        }
+    }
+    best_action.unwrap()
+}
+
+/// プレイヤー 0 を DUCT、プレイヤー 1 をランダムにして、プレイヤー 0 の勝率と平均得点差を求める
+fn duct_against_random(game_number: i32) -> (f64, f64) {
+    let mut rng_for_action = SmallRng::seed_from_u64(0);
+    let mut rng_for_construct = SmallRng::seed_from_u64(0);
+    let mut first_player_win_rate = 0.0;
+    let mut margin_mean = 0.0;
+    for _ in 0..game_number {
+        let mut state = State::new(rng_for_construct.next_u64());
+        while !state.is_done() {
+            let action0 = duct_action(&state, 0, 1000, &mut rng_for_action);
+            let action1 = random_action(&state, 1, &mut rng_for_action);
+            state.advance(action0, action1);
+        }
+        first_player_win_rate += state.white_score();
+        margin_mean += (state.players()[0].game_score - state.players()[1].game_score) as f64;
+    }
+    first_player_win_rate /= game_number as f64;
+    margin_mean /= game_number as f64;
+    (first_player_win_rate, margin_mean)
+}
+
+fn test_first_player_win_rate(game_number: i32) {
+    let (first_player_win_rate, margin_mean) = duct_against_random(game_number);
+    println!("Win rate:\t{first_player_win_rate}");
+    println!("Margin:\t{margin_mean}");
+}
+
+fn main() {
+    test_first_player_win_rate(100);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 決まった 20 個の盤面で、DUCT はランダムに平均で得点差をつけて勝ち越す
+    #[test]
+    fn beats_random_by_positive_margin() {
+        let (first_player_win_rate, margin_mean) = duct_against_random(20);
+        assert!(margin_mean > 0.0, "margin {margin_mean}");
+        assert!(first_player_win_rate > 0.5, "win rate {first_player_win_rate}");
+    }
+}
@@ -0,0 +1,186 @@
+use std::time::Duration;
+use rand::rngs::SmallRng;
+use rand::{RngCore, SeedableRng};
+use game_search_algorithm::alternate_maze::{AlternateMazeState, WinningStatus};
+use game_search_algorithm::maze::Action;
+use game_search_algorithm::time_keeper::TimeKeeper;
+
+const H: usize = 5;
+const W: usize = 5;
+const END_TURN: i32 = 10;
+
+type State = AlternateMazeState<H, W, END_TURN>;
+
+fn random_action(state: &State, rng: &mut SmallRng) -> Action {
+    let legal_actions = state.legal_actions();
+    legal_actions[rng.next_u32() as usize % legal_actions.len()]
+}
+
+/// 手番のプレイヤーから見た [0, 1] の価値 (終局なら勝ち 1、引き分け 0.5、負け 0)
+///
+/// 終局前は、得点差 `teban_score` を取りうる範囲 `[-total_points, total_points]` で最小最大正規化する。
+fn value(state: &State) -> f64 {
+    match state.winning_status() {
+        WinningStatus::Win => 1.0,
+        WinningStatus::Lose => 0.0,
+        WinningStatus::Draw => 0.5,
+        WinningStatus::None => {
+            let max_score = state.total_points() as f64;
+            if max_score == 0.0 {
+                return 0.5;
+            }
+            (state.teban_score() as f64 + max_score) / (2.0 * max_score)
+        }
+    }
+}
+
+struct Node {
+    state: State,
+    w: f64,
+    n: u32,
+    child_nodes: Vec<(Action, Node)>,
+}
+
+impl Node {
+    fn new(state: State) -> Self {
+        Self {
+            state,
+            w: 0.0,
+            n: 0,
+            child_nodes: Vec::new(),
+        }
+    }
+
+    /// プレイアウトの代わりに盤面の価値をそのまま使い、初めて訪れたときにすぐ展開する
+    fn evaluate(&mut self) -> f64 {
+        if self.state.is_done() {
+            let value = value(&self.state);
+            self.w += value;
+            self.n += 1;
+            return value;
+        }
+        if self.child_nodes.is_empty() {
+            let value = value(&self.state);
+            self.w += value;
+            self.n += 1;
+            self.expand();
+            return value;
+        }
+        let value = 1.0 - self.next_child_node().evaluate();
+        self.w += value;
+        self.n += 1;
+        value
+    }
+
+    fn expand(&mut self) {
+        for action in self.state.legal_actions() {
+            let mut state = self.state.clone();
+            state.advance(action);
+            self.child_nodes.push((action, Node::new(state)));
+        }
+    }
+
+    /// 未訪問の子ノードを優先し、全て訪問済みなら自分から見た価値の平均が最大の子ノードを選ぶ (探索項はない)
+    fn next_child_node(&mut self) -> &mut Node {
+        if let Some(index) = self.child_nodes.iter().position(|(_, child)| child.n == 0) {
+            return &mut self.child_nodes[index].1;
+        }
+        let mut best_value = f64::NEG_INFINITY;
+        let mut best_index = 0;
+        for (i, (_, child)) in self.child_nodes.iter().enumerate() {
+            let thunder_value = 1.0 - child.w / child.n as f64;
+            if thunder_value > best_value {
+                best_value = thunder_value;
+                best_index = i;
+            }
+        }
+        &mut self.child_nodes[best_index].1
+    }
+
+    fn best_action(&self) -> Action {
+        let mut best_action_searched_number = 0;
+        let mut best_action = None;
+        for (action, child) in &self.child_nodes {
+            if best_action.is_none() || child.n > best_action_searched_number {
+                best_action_searched_number = child.n;
+                best_action = Some(*action);
+            }
+        }
+        best_action.unwrap()
+    }
+}
+
+fn thunder_search_action(state: &State, playout_number: u32) -> Action {
+    let mut root_node = Node::new(state.clone());
+    root_node.expand();
+    for _ in 0..playout_number {
+        root_node.evaluate();
+    }
+    root_node.best_action()
+}
+
+fn thunder_search_action_with_time_threshold(state: &State, time_threshold: Duration) -> Action {
+    let time_keeper = TimeKeeper::new(time_threshold);
+    let mut root_node = Node::new(state.clone());
+    root_node.expand();
+    while !time_keeper.is_time_over() {
+        root_node.evaluate();
+    }
+    root_node.best_action()
+}
+
+fn test_first_player_win_rate(game_number: i32, ai: impl Fn(&State) -> Action) {
+    let mut rng_for_action = SmallRng::seed_from_u64(0);
+    let mut rng_for_construct = SmallRng::seed_from_u64(0);
+    let mut first_player_win_rate = 0.0;
+    for _ in 0..game_number {
+        let mut state = State::new(rng_for_construct.next_u64());
+        while !state.is_done() {
+            if state.is_first() {
+                let action = ai(&state);
+                assert!(state.legal_actions().contains(&action));
+                state.advance(action);
+            } else {
+                state.advance(random_action(&state, &mut rng_for_action));
+            }
+        }
+        let win_rate_point = match (state.winning_status(), state.is_first()) {
+            (WinningStatus::Draw, _) => 0.5,
+            (WinningStatus::Win, true) | (WinningStatus::Lose, false) => 1.0,
+            _ => 0.0,
+        };
+        first_player_win_rate += win_rate_point;
+    }
+    first_player_win_rate /= game_number as f64;
+    assert!(first_player_win_rate > 0.5, "win rate against random is {first_player_win_rate}");
+    println!("Win rate:\t{first_player_win_rate}");
+}
+
+fn main() {
+    test_first_player_win_rate(100, |state| thunder_search_action(state, 3000));
+    test_first_player_win_rate(100, |state| thunder_search_action_with_time_threshold(state, Duration::from_millis(1)));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 終局前の価値は [0, 1] に収まり、得点差が大きいほど高い
+    #[test]
+    fn value_is_min_max_scaled() {
+        let mut points = [[0; W]; H];
+        points[H / 2 - 1][W / 2 - 1] = 9;
+        points[H / 2 + 1][W / 2 + 1] = 1;
+        let state = State::from_points(points);
+        assert_eq!(value(&state), 0.5);
+        let mut taken = state.clone();
+        taken.advance(Action::Up);
+        let mut missed = state.clone();
+        missed.advance(Action::Down);
+        // 後手の番なので、先手が 9 点を取った盤面ほど後手から見た価値は低い
+        assert_eq!(value(&taken), (-9.0 + 10.0) / 20.0);
+        assert_eq!(value(&missed), 0.5);
+        taken.advance(Action::Down);
+        assert_eq!(value(&taken), (8.0 + 10.0) / 20.0);
+    }
+}
@@ -0,0 +1,46 @@
+use std::time::{Duration, Instant};
+use rand::rngs::SmallRng;
+use rand::{RngCore, SeedableRng};
+use game_search_algorithm::SingleGameState;
+use game_search_algorithm::maze::{MazeParams, MazeState};
+use game_search_algorithm::search::iterative_deepening::{iterative_deepening_action, iterative_deepening_action_with_undo};
+
+const H: usize = 30;
+const W: usize = 30;
+const END_TURN: i32 = 100;
+const PARAMS: MazeParams = MazeParams { h: H, w: W, end_turn: END_TURN };
+const DEPTH: usize = 6;
+
+type State = MazeState;
+
+/// 複製する盤面のバイト数 (`points` と `walls` の分)
+fn state_bytes() -> usize {
+    H * W * (size_of::<i32>() + size_of::<bool>()) + size_of::<State>()
+}
+
+fn main() {
+    let mut rng_for_construct = SmallRng::seed_from_u64(0);
+    let mut clone_elapsed = Duration::ZERO;
+    let mut undo_elapsed = Duration::ZERO;
+    let mut same_action_number = 0;
+    let mut position_number = 0;
+    for _ in 0..5 {
+        let mut state = State::new(rng_for_construct.next_u64(), PARAMS);
+        while !state.is_done() {
+            let time_keeper = Instant::now();
+            let clone_action = iterative_deepening_action(&state, DEPTH);
+            clone_elapsed += time_keeper.elapsed();
+            let time_keeper = Instant::now();
+            let undo_action = iterative_deepening_action_with_undo(&state, DEPTH);
+            undo_elapsed += time_keeper.elapsed();
+            if clone_action == undo_action {
+                same_action_number += 1;
+            }
+            position_number += 1;
+            state.advance(undo_action);
+        }
+    }
+    println!("Same action:\t{same_action_number}/{position_number}");
+    println!("Clone:\t{clone_elapsed:?}\t(live states up to {} bytes)", (DEPTH + 1) * state_bytes());
+    println!("Undo:\t{undo_elapsed:?}\t(live states up to {} bytes)", state_bytes());
+}
@@ -0,0 +1,47 @@
+use std::time::{Duration, Instant};
+use rand::rngs::SmallRng;
+use rand::{RngCore, SeedableRng};
+use game_search_algorithm::SingleGameState;
+use game_search_algorithm::maze::{MazeParams, MazeState};
+use game_search_algorithm::search::beam::beam_search_action;
+use game_search_algorithm::search::parallel_beam::parallel_beam_search_action;
+
+const H: usize = 30;
+const W: usize = 30;
+const END_TURN: i32 = 100;
+const PARAMS: MazeParams = MazeParams { h: H, w: W, end_turn: END_TURN };
+
+type State = MazeState;
+
+fn bench(game_number: i32, beam_width: usize, beam_depth: usize) {
+    let mut rng_for_construct = SmallRng::seed_from_u64(0);
+    let mut serial_elapsed = Duration::ZERO;
+    let mut parallel_elapsed = Duration::ZERO;
+    let mut serial_score_mean = 0.0;
+    let mut parallel_score_mean = 0.0;
+    for _ in 0..game_number {
+        let initial_state = State::new(rng_for_construct.next_u64(), PARAMS);
+        let mut state = initial_state.clone();
+        let time_keeper = Instant::now();
+        while !state.is_done() {
+            state.advance(beam_search_action(&state, beam_width, beam_depth));
+        }
+        serial_elapsed += time_keeper.elapsed();
+        serial_score_mean += state.game_score() as f64;
+        let mut state = initial_state;
+        let time_keeper = Instant::now();
+        while !state.is_done() {
+            state.advance(parallel_beam_search_action(&state, beam_width, beam_depth, Duration::MAX));
+        }
+        parallel_elapsed += time_keeper.elapsed();
+        parallel_score_mean += state.game_score() as f64;
+    }
+    serial_score_mean /= game_number as f64;
+    parallel_score_mean /= game_number as f64;
+    println!("Serial:\t{serial_score_mean}\t{serial_elapsed:?}");
+    println!("Parallel:\t{parallel_score_mean}\t{parallel_elapsed:?}");
+}
+
+fn main() {
+    bench(5, 200, 10);
+}
@@ -0,0 +1,49 @@
+use std::time::{Duration, Instant};
+use rand::rngs::SmallRng;
+use rand::{RngCore, SeedableRng};
+use game_search_algorithm::SingleGameState;
+use game_search_algorithm::maze::{MazeParams, MazeState};
+use game_search_algorithm::search::budget::SearchBudget;
+use game_search_algorithm::search::chokudai::{chokudai_search_action_with_node_limit, parallel_chokudai_search_action};
+
+const H: usize = 30;
+const W: usize = 30;
+const END_TURN: i32 = 100;
+const PARAMS: MazeParams = MazeParams { h: H, w: W, end_turn: END_TURN };
+
+type State = MazeState;
+
+/// 同じノード数の上限で、直列の Chokudai サーチと `thread_number` 個のスレッドで展開を分けた Chokudai サーチの得点と時間を比べる
+fn bench(game_number: i32, beam_width: i32, beam_depth: usize, node_limit: usize, thread_number: usize) {
+    let mut rng_for_construct = SmallRng::seed_from_u64(0);
+    let mut serial_elapsed = Duration::ZERO;
+    let mut parallel_elapsed = Duration::ZERO;
+    let mut serial_score_mean = 0.0;
+    let mut parallel_score_mean = 0.0;
+    for _ in 0..game_number {
+        let initial_state = State::new(rng_for_construct.next_u64(), PARAMS);
+        let mut state = initial_state.clone();
+        let time_keeper = Instant::now();
+        while !state.is_done() {
+            state.advance(chokudai_search_action_with_node_limit(&state, beam_width, beam_depth, node_limit));
+        }
+        serial_elapsed += time_keeper.elapsed();
+        serial_score_mean += state.game_score() as f64;
+        let mut state = initial_state;
+        let time_keeper = Instant::now();
+        while !state.is_done() {
+            state.advance(parallel_chokudai_search_action(&state, beam_width, beam_depth, SearchBudget::Nodes(node_limit), thread_number, true));
+        }
+        parallel_elapsed += time_keeper.elapsed();
+        parallel_score_mean += state.game_score() as f64;
+    }
+    serial_score_mean /= game_number as f64;
+    parallel_score_mean /= game_number as f64;
+    println!("Serial:\t{serial_score_mean}\t{serial_elapsed:?}");
+    println!("Parallel ({thread_number} threads):\t{parallel_score_mean}\t{parallel_elapsed:?}");
+}
+
+fn main() {
+    let thread_number = std::thread::available_parallelism().map_or(1, |thread_number| thread_number.get());
+    bench(1, 200, 10, 1_000, thread_number);
+}
@@ -0,0 +1,50 @@
+use std::time::{Duration, Instant};
+use rand::rngs::SmallRng;
+use rand::SeedableRng;
+use game_search_algorithm::evaluate::evaluate_ai;
+use game_search_algorithm::maze::{Action, MazeParams, MazeState};
+use game_search_algorithm::search::beam::beam_search_action;
+use game_search_algorithm::search::chokudai::chokudai_search_action;
+use game_search_algorithm::search::greedy::greedy_action;
+use game_search_algorithm::search::hill_climb::hill_climbing_action;
+use game_search_algorithm::search::mcts::mcts_action;
+use game_search_algorithm::search::random::random_action;
+use game_search_algorithm::search::simulated_annealing::simulated_annealing_action;
+
+const H: usize = 30;
+const W: usize = 30;
+const END_TURN: i32 = 100;
+const PARAMS: MazeParams = MazeParams { h: H, w: W, end_turn: END_TURN };
+const GAME_NUMBER: i32 = 20;
+
+type State = MazeState;
+
+/// 全ての AI を同じ seed の盤面で対戦させ、CSV の 1 行を出力する
+fn benchmark(name: &str, mut ai: impl FnMut(&State) -> Action) {
+    let mut elapsed = Duration::ZERO;
+    let mut move_number = 0;
+    let stats = evaluate_ai(GAME_NUMBER, PARAMS, |state: &State| {
+        let time_keeper = Instant::now();
+        let action = ai(state);
+        elapsed += time_keeper.elapsed();
+        move_number += 1;
+        action
+    });
+    let ms_per_move = elapsed.as_secs_f64() * 1000.0 / move_number as f64;
+    println!("{name},{},{},{},{},{},{ms_per_move}", stats.mean, stats.std_dev, stats.min, stats.max, stats.median);
+}
+
+fn main() {
+    println!("algorithm,mean_score,std_score,min_score,max_score,median_score,mean_ms_per_move");
+    let mut rng_for_action = SmallRng::seed_from_u64(0);
+    benchmark("random", |state| random_action(state, &mut rng_for_action));
+    benchmark("greedy", greedy_action);
+    benchmark("beam", |state| beam_search_action(state, 5, END_TURN as usize));
+    benchmark("chokudai", |state| chokudai_search_action(state, 1, END_TURN as usize, 5));
+    let mut rng_for_action = SmallRng::seed_from_u64(0);
+    benchmark("mcts", |state| mcts_action(state, 300, &mut rng_for_action));
+    let mut rng_for_action = SmallRng::seed_from_u64(0);
+    benchmark("simulated_annealing", |state| simulated_annealing_action(state, 10.0, 0.998, 1000, &mut rng_for_action));
+    let mut rng_for_action = SmallRng::seed_from_u64(0);
+    benchmark("hill_climbing", |state| hill_climbing_action(state, 10, &mut rng_for_action));
+}
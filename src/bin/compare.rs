@@ -0,0 +1,37 @@
+use rand::rngs::SmallRng;
+use rand::SeedableRng;
+use game_search_algorithm::args::Args;
+use game_search_algorithm::evaluate::{compare_algorithms, format_comparison, NamedAi};
+use game_search_algorithm::maze::{MazeParams, MazeState};
+use game_search_algorithm::search::beam::beam_search_action;
+use game_search_algorithm::search::chokudai::{chokudai_search_action, chokudai_search_action_with_config};
+use game_search_algorithm::search::config::SearchConfig;
+use game_search_algorithm::search::greedy::greedy_action;
+use game_search_algorithm::search::mcts::mcts_action;
+use game_search_algorithm::search::random::random_action;
+
+const H: usize = 30;
+const W: usize = 30;
+const END_TURN: i32 = 100;
+const PARAMS: MazeParams = MazeParams { h: H, w: W, end_turn: END_TURN };
+
+type State = MazeState;
+
+const FLAGS: [&str; 1] = ["--games"];
+
+fn main() {
+    let args = Args::from_env(&FLAGS);
+    let game_number = args.get_at_least_or_exit("--games", 20, 1);
+    let mut rng_for_random = SmallRng::seed_from_u64(0);
+    let mut rng_for_mcts = SmallRng::seed_from_u64(0);
+    let config = SearchConfig::default();
+    let algorithms: Vec<NamedAi> = vec![
+        ("random", Box::new(|state: &State| random_action(state, &mut rng_for_random))),
+        ("greedy", Box::new(greedy_action)),
+        ("beam", Box::new(|state: &State| beam_search_action(state, 5, END_TURN as usize))),
+        ("chokudai", Box::new(|state: &State| chokudai_search_action(state, 1, END_TURN as usize, 5))),
+        ("chokudai (10 ms)", Box::new(|state: &State| chokudai_search_action_with_config(state, &config, None))),
+        ("mcts", Box::new(|state: &State| mcts_action(state, 300, &mut rng_for_mcts))),
+    ];
+    print!("{}", format_comparison(&compare_algorithms(game_number, PARAMS, algorithms)));
+}
@@ -0,0 +1,47 @@
+use rand::rngs::SmallRng;
+use rand::{RngCore, SeedableRng};
+use game_search_algorithm::SingleGameState;
+use game_search_algorithm::maze::{MazeParams, MazeState};
+use game_search_algorithm::search::astar::astar_action_with_count;
+use game_search_algorithm::search::bfs::bfs_action_with_count;
+use game_search_algorithm::search::iterative_deepening::iterative_deepening_action;
+
+const H: usize = 3;
+const W: usize = 4;
+const END_TURN: i32 = 4;
+const PARAMS: MazeParams = MazeParams { h: H, w: W, end_turn: END_TURN };
+
+type State = MazeState;
+
+/// 全探索した反復深化の得点と一致すれば A* は最適
+fn test_ai_score(game_number: i32) {
+    let mut rng_for_construct = SmallRng::seed_from_u64(0);
+    let mut optimal_game_number = 0;
+    let mut astar_node_count = 0;
+    let mut bfs_node_count = 0;
+    for _ in 0..game_number {
+        let initial_state = State::new(rng_for_construct.next_u64(), PARAMS);
+        let mut state = initial_state.clone();
+        while !state.is_done() {
+            let (action, node_count) = astar_action_with_count(&state);
+            astar_node_count += node_count;
+            bfs_node_count += bfs_action_with_count(&state, usize::MAX).1;
+            state.advance(action.unwrap());
+        }
+        let astar_score = state.game_score();
+        let mut state = initial_state;
+        while !state.is_done() {
+            state.advance(iterative_deepening_action(&state, END_TURN as usize));
+        }
+        if astar_score == state.game_score() {
+            optimal_game_number += 1;
+        }
+    }
+    println!("Optimal games:\t{optimal_game_number}/{game_number}");
+    println!("Nodes (A*):\t{astar_node_count}");
+    println!("Nodes (BFS):\t{bfs_node_count}");
+}
+
+fn main() {
+    test_ai_score(100);
+}
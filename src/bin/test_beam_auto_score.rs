@@ -0,0 +1,44 @@
+use std::time::Duration;
+use rand::rngs::SmallRng;
+use rand::{RngCore, SeedableRng};
+use game_search_algorithm::SingleGameState;
+use game_search_algorithm::evaluate::RunningStats;
+use game_search_algorithm::maze::{MazeParams, MazeState};
+use game_search_algorithm::search::beam::beam_search_auto;
+
+const H: usize = 30;
+const W: usize = 30;
+const END_TURN: i32 = 100;
+const PARAMS: MazeParams = MazeParams { h: H, w: W, end_turn: END_TURN };
+
+const TIME_THRESHOLD: Duration = Duration::from_millis(10);
+
+type State = MazeState;
+
+fn test_ai_score(game_number: i32) {
+    let mut rng_for_construct = SmallRng::seed_from_u64(0);
+    let mut score_stats = RunningStats::new();
+    let mut search_number = 0;
+    let mut over_number = 0;
+    let mut elapsed_sum = Duration::ZERO;
+    for _ in 0..game_number {
+        let mut state = State::new(rng_for_construct.next_u64(), PARAMS);
+        while !state.is_done() {
+            let (action, stats) = beam_search_auto(&state, (END_TURN - state.turn()) as usize, TIME_THRESHOLD);
+            search_number += 1;
+            elapsed_sum += stats.elapsed;
+            if stats.elapsed > TIME_THRESHOLD {
+                over_number += 1;
+            }
+            state.advance(action);
+        }
+        score_stats.push(state.game_score());
+    }
+    println!("Score:\t{score_stats}");
+    println!("Mean elapsed:\t{:?}", elapsed_sum / search_number);
+    println!("Over time:\t{over_number}/{search_number}");
+}
+
+fn main() {
+    test_ai_score(20);
+}
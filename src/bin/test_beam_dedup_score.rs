@@ -0,0 +1,38 @@
+use rand::rngs::SmallRng;
+use rand::{RngCore, SeedableRng};
+use game_search_algorithm::SingleGameState;
+use game_search_algorithm::maze::{MazeParams, MazeState};
+use game_search_algorithm::search::beam::beam_search_action_with_count;
+
+const H: usize = 5;
+const W: usize = 5;
+const END_TURN: i32 = 10;
+const PARAMS: MazeParams = MazeParams { h: H, w: W, end_turn: END_TURN };
+
+type State = MazeState;
+
+fn test_ai_score(game_number: i32, use_hash: bool) {
+    let hash = |state: &State| state.zobrist_hash();
+    let hash: Option<&dyn Fn(&State) -> u64> = if use_hash { Some(&hash) } else { None };
+    let mut rng_for_construct = SmallRng::seed_from_u64(0);
+    let mut score_mean = 0.0;
+    let mut node_count_mean = 0.0;
+    for _ in 0..game_number {
+        let mut state = State::new(rng_for_construct.next_u64(), PARAMS);
+        while !state.is_done() {
+            let (action, node_count) = beam_search_action_with_count(&state, 100, END_TURN as usize, hash);
+            state.advance(action);
+            node_count_mean += node_count as f64;
+        }
+        let score = state.game_score();
+        score_mean += score as f64;
+    }
+    score_mean /= game_number as f64;
+    node_count_mean /= game_number as f64;
+    println!("Score:\t{score_mean}\tNodes:\t{node_count_mean}");
+}
+
+fn main() {
+    test_ai_score(100, false);
+    test_ai_score(100, true);
+}
@@ -0,0 +1,41 @@
+use std::time::Duration;
+use rand::rngs::SmallRng;
+use rand::{RngCore, SeedableRng};
+use game_search_algorithm::SingleGameState;
+use game_search_algorithm::maze::{MazeParams, MazeState};
+use game_search_algorithm::search::beam_stack::beam_stack_search_action;
+use game_search_algorithm::search::chokudai::chokudai_search_action;
+
+const H: usize = 30;
+const W: usize = 30;
+const END_TURN: i32 = 100;
+const PARAMS: MazeParams = MazeParams { h: H, w: W, end_turn: END_TURN };
+
+type State = MazeState;
+
+fn test_ai_score(game_number: i32) {
+    let mut rng_for_construct = SmallRng::seed_from_u64(0);
+    let mut chokudai_score_mean = 0.0;
+    let mut beam_stack_score_mean = 0.0;
+    for _ in 0..game_number {
+        let initial_state = State::new(rng_for_construct.next_u64(), PARAMS);
+        let mut state = initial_state.clone();
+        while !state.is_done() {
+            state.advance(chokudai_search_action(&state, 1, END_TURN as usize, 5));
+        }
+        chokudai_score_mean += state.game_score() as f64;
+        let mut state = initial_state;
+        while !state.is_done() {
+            state.advance(beam_stack_search_action(&state, 5, Duration::from_millis(2)));
+        }
+        beam_stack_score_mean += state.game_score() as f64;
+    }
+    chokudai_score_mean /= game_number as f64;
+    beam_stack_score_mean /= game_number as f64;
+    println!("Chokudai:\t{chokudai_score_mean}");
+    println!("Beam stack:\t{beam_stack_score_mean}");
+}
+
+fn main() {
+    test_ai_score(20);
+}
@@ -0,0 +1,41 @@
+use rand::rngs::SmallRng;
+use rand::{RngCore, SeedableRng};
+use game_search_algorithm::SingleGameState;
+use game_search_algorithm::maze::{MazeParams, MazeState};
+use game_search_algorithm::search::beam::beam_search_action;
+use game_search_algorithm::search::best_first::best_first_search_action;
+
+const H: usize = 30;
+const W: usize = 30;
+const END_TURN: i32 = 100;
+const PARAMS: MazeParams = MazeParams { h: H, w: W, end_turn: END_TURN };
+
+type State = MazeState;
+
+fn test_ai_score(game_number: i32, wall_density: f64) {
+    let mut rng_for_construct = SmallRng::seed_from_u64(0);
+    let mut beam_score_mean = 0.0;
+    let mut best_first_score_mean = 0.0;
+    for _ in 0..game_number {
+        let initial_state = State::new_with_walls(rng_for_construct.next_u64(), PARAMS, wall_density);
+        let mut state = initial_state.clone();
+        while !state.is_done() {
+            state.advance(beam_search_action(&state, 5, END_TURN as usize));
+        }
+        beam_score_mean += state.game_score() as f64;
+        let mut state = initial_state;
+        while !state.is_done() {
+            state.advance(best_first_search_action(&state, 500));
+        }
+        best_first_score_mean += state.game_score() as f64;
+    }
+    beam_score_mean /= game_number as f64;
+    best_first_score_mean /= game_number as f64;
+    println!("Beam (walls {wall_density}):\t{beam_score_mean}");
+    println!("Best-first (walls {wall_density}):\t{best_first_score_mean}");
+}
+
+fn main() {
+    test_ai_score(20, 0.0);
+    test_ai_score(20, 0.3);
+}
@@ -0,0 +1,47 @@
+use rand::rngs::SmallRng;
+use rand::{RngCore, SeedableRng};
+use game_search_algorithm::SingleGameState;
+use game_search_algorithm::evaluate::RunningStats;
+use game_search_algorithm::maze::{MazeParams, MazeState};
+use game_search_algorithm::search::bfs::bfs_action;
+use game_search_algorithm::search::iterative_deepening::iterative_deepening_action;
+
+const H: usize = 3;
+const W: usize = 4;
+const END_TURN: i32 = 4;
+const PARAMS: MazeParams = MazeParams { h: H, w: W, end_turn: END_TURN };
+
+type State = MazeState;
+
+/// 全探索した反復深化の得点と一致すれば BFS は最適
+fn test_ai_score(game_number: i32) {
+    let mut rng_for_construct = SmallRng::seed_from_u64(0);
+    let mut bfs_stats = RunningStats::new();
+    let mut optimal_stats = RunningStats::new();
+    let mut optimal_game_number = 0;
+    for _ in 0..game_number {
+        let initial_state = State::new(rng_for_construct.next_u64(), PARAMS);
+        let mut state = initial_state.clone();
+        while !state.is_done() {
+            state.advance(bfs_action(&state, 100_000));
+        }
+        let bfs_score = state.game_score();
+        let mut state = initial_state;
+        while !state.is_done() {
+            state.advance(iterative_deepening_action(&state, END_TURN as usize));
+        }
+        let optimal_score = state.game_score();
+        bfs_stats.push(bfs_score);
+        optimal_stats.push(optimal_score);
+        if bfs_score == optimal_score {
+            optimal_game_number += 1;
+        }
+    }
+    println!("BFS:\t{bfs_stats}");
+    println!("Optimal:\t{optimal_stats}");
+    println!("Optimal games:\t{optimal_game_number}/{game_number}");
+}
+
+fn main() {
+    test_ai_score(100);
+}
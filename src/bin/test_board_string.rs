@@ -0,0 +1,9 @@
+use game_search_algorithm::maze::MazeState;
+
+type State = MazeState;
+
+fn main() {
+    for s in ["4 0 0 9136/2763/@489", "4 0 0 91#6/27a3/@489", "4 0 0 913/2763/@489", "4 0 0 9136/2763/0489", "4 x 0 9136/2763/@489", "4 5 0 9136/2763/@489"] {
+        println!("{s}:\t{:?}", State::from_board_string(s).map(|state| state.to_board_string()).map_err(|e| e.to_string()));
+    }
+}
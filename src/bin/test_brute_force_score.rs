@@ -0,0 +1,42 @@
+use rand::rngs::SmallRng;
+use rand::{RngCore, SeedableRng};
+use game_search_algorithm::SingleGameState;
+use game_search_algorithm::maze::{Action, MazeParams, MazeState};
+use game_search_algorithm::search::beam::beam_search_action;
+use game_search_algorithm::search::brute_force::brute_force_best_score;
+use game_search_algorithm::search::greedy::greedy_action;
+
+const H: usize = 4;
+const W: usize = 4;
+const END_TURN: i32 = 8;
+const PARAMS: MazeParams = MazeParams { h: H, w: W, end_turn: END_TURN };
+
+type State = MazeState;
+
+fn play(mut state: State, ai: impl Fn(&State) -> Action) -> i32 {
+    while !state.is_done() {
+        state.advance(ai(&state));
+    }
+    state.game_score()
+}
+
+/// 小さい盤面で、貪欲法とビームサーチが全探索の最大値に届いた割合を表示する
+fn test_never_exceeds_optimum(game_number: i32) {
+    let mut rng_for_construct = SmallRng::seed_from_u64(0);
+    let mut greedy_optimal_number = 0;
+    let mut beam_optimal_number = 0;
+    for _ in 0..game_number {
+        let state = State::new(rng_for_construct.next_u64(), PARAMS);
+        let best_score = brute_force_best_score(&state);
+        let greedy_score = play(state.clone(), greedy_action);
+        let beam_score = play(state, |state| beam_search_action(state, 5, END_TURN as usize));
+        greedy_optimal_number += (greedy_score == best_score) as i32;
+        beam_optimal_number += (beam_score == best_score) as i32;
+    }
+    println!("Optimal (greedy):\t{greedy_optimal_number}/{game_number}");
+    println!("Optimal (beam):\t{beam_optimal_number}/{game_number}");
+}
+
+fn main() {
+    test_never_exceeds_optimum(100);
+}
@@ -0,0 +1,37 @@
+use rand::rngs::SmallRng;
+use rand::{RngCore, SeedableRng};
+use game_search_algorithm::SingleGameState;
+use game_search_algorithm::maze::{heuristic_game_score, heuristic_lookahead_sum, Action, MazeParams, MazeState};
+use game_search_algorithm::search::depth_limited::depth_limited_action;
+
+const H: usize = 30;
+const W: usize = 30;
+const END_TURN: i32 = 100;
+const PARAMS: MazeParams = MazeParams { h: H, w: W, end_turn: END_TURN };
+
+type State = MazeState;
+
+fn play(mut state: State, ai: impl Fn(&State) -> Action) -> i32 {
+    while !state.is_done() {
+        state.advance(ai(&state));
+    }
+    state.game_score()
+}
+
+fn test_ai_score(game_number: i32, name: &str, ai: impl Fn(&State) -> Action) {
+    let mut rng_for_construct = SmallRng::seed_from_u64(0);
+    let mut score_mean = 0.0;
+    for _ in 0..game_number {
+        let state = State::new(rng_for_construct.next_u64(), PARAMS);
+        score_mean += play(state, &ai) as f64;
+    }
+    score_mean /= game_number as f64;
+    println!("{name}:\t{score_mean}");
+}
+
+fn main() {
+    for depth in 1..=4 {
+        test_ai_score(20, &format!("Depth {depth} (game score)"), |state| depth_limited_action(state, depth, heuristic_game_score));
+    }
+    test_ai_score(20, "Depth 3 (lookahead sum)", |state| depth_limited_action(state, 3, heuristic_lookahead_sum));
+}
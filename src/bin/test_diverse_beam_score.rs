@@ -0,0 +1,48 @@
+use rand::rngs::SmallRng;
+use rand::{RngCore, SeedableRng};
+use game_search_algorithm::SingleGameState;
+use game_search_algorithm::evaluate::RunningStats;
+use game_search_algorithm::maze::{MazeParams, MazeState};
+use game_search_algorithm::search::beam::{beam_search_action, diverse_beam_search_action, spatially_diverse_beam_search_action};
+
+const H: usize = 30;
+const W: usize = 30;
+const END_TURN: i32 = 100;
+const PARAMS: MazeParams = MazeParams { h: H, w: W, end_turn: END_TURN };
+
+type State = MazeState;
+
+fn test_ai_score(game_number: i32, wall_density: f64) {
+    let key = |state: &State| (state.character().y * W + state.character().x) as u64;
+    let mut rng_for_construct = SmallRng::seed_from_u64(0);
+    let mut beam_stats = RunningStats::new();
+    let mut diverse_stats = RunningStats::new();
+    let mut spatially_diverse_stats = RunningStats::new();
+    let position = |state: &State| state.character().clone();
+    for _ in 0..game_number {
+        let initial_state = State::new_with_walls(rng_for_construct.next_u64(), PARAMS, wall_density);
+        let mut state = initial_state.clone();
+        while !state.is_done() {
+            state.advance(beam_search_action(&state, 5, END_TURN as usize));
+        }
+        beam_stats.push(state.game_score());
+        let mut state = initial_state.clone();
+        while !state.is_done() {
+            state.advance(diverse_beam_search_action(&state, 5, END_TURN as usize, 10, &key));
+        }
+        diverse_stats.push(state.game_score());
+        let mut state = initial_state;
+        while !state.is_done() {
+            state.advance(spatially_diverse_beam_search_action(&state, 5, 3.0, END_TURN as usize, &position));
+        }
+        spatially_diverse_stats.push(state.game_score());
+    }
+    println!("Beam (walls {wall_density}):\t{beam_stats}");
+    println!("Diverse beam (walls {wall_density}):\t{diverse_stats}");
+    println!("Spatially diverse beam (walls {wall_density}):\t{spatially_diverse_stats}");
+}
+
+fn main() {
+    test_ai_score(20, 0.0);
+    test_ai_score(20, 0.3);
+}
@@ -0,0 +1,55 @@
+use rand::rngs::SmallRng;
+use rand::{RngCore, SeedableRng};
+use game_search_algorithm::SingleGameState;
+use game_search_algorithm::evaluate::RunningStats;
+use game_search_algorithm::maze::{heuristic_game_score, heuristic_lookahead_sum, heuristic_reachable_points, HeuristicFn, MazeParams, MazeState, ScoreType};
+use game_search_algorithm::search::beam::{beam_search_action, beam_search_action_with_eval, beam_search_action_with_heuristic};
+use game_search_algorithm::search::chokudai::{chokudai_search_action, chokudai_search_action_with_eval};
+
+const H: usize = 30;
+const W: usize = 30;
+const END_TURN: i32 = 100;
+const PARAMS: MazeParams = MazeParams { h: H, w: W, end_turn: END_TURN };
+
+type State = MazeState;
+
+fn test_ai_score(game_number: i32, name: &str, ai: impl Fn(&State) -> <State as SingleGameState>::Action) {
+    let mut rng_for_construct = SmallRng::seed_from_u64(0);
+    let mut stats = RunningStats::new();
+    for _ in 0..game_number {
+        let mut state = State::new(rng_for_construct.next_u64(), PARAMS);
+        while !state.is_done() {
+            state.advance(ai(&state));
+        }
+        stats.push(state.game_score());
+    }
+    println!("{name}:\t{stats}");
+}
+
+fn main() {
+    let game_score = |state: &State| state.game_score() as ScoreType;
+    let zero = |_: &State| 0 as ScoreType;
+    test_ai_score(20, "Beam", |state| beam_search_action(state, 5, END_TURN as usize));
+    test_ai_score(20, "Beam (game score)", |state| beam_search_action_with_eval(state, 5, END_TURN as usize, &game_score));
+    test_ai_score(20, "Beam (zero)", |state| beam_search_action_with_eval(state, 5, END_TURN as usize, &zero));
+    test_ai_score(20, "Beam (normalized)", |state| {
+        let mut state = state.clone();
+        state.set_normalized(true);
+        beam_search_action(&state, 5, END_TURN as usize)
+    });
+    test_ai_score(20, "Beam (revisit penalty)", |state| {
+        let mut state = state.clone();
+        state.set_revisit_penalty(1);
+        beam_search_action(&state, 5, END_TURN as usize)
+    });
+    let heuristics: [(&str, HeuristicFn); 3] = [
+        ("game score", heuristic_game_score),
+        ("lookahead sum", heuristic_lookahead_sum),
+        ("reachable points", heuristic_reachable_points),
+    ];
+    for (heuristic_name, heuristic) in heuristics {
+        test_ai_score(20, &format!("Beam (heuristic: {heuristic_name})"), |state| beam_search_action_with_heuristic(state, 5, END_TURN as usize, Some(heuristic)));
+    }
+    test_ai_score(20, "Chokudai", |state| chokudai_search_action(state, 1, END_TURN as usize, 5));
+    test_ai_score(20, "Chokudai (game score)", |state| chokudai_search_action_with_eval(state, 1, END_TURN as usize, 5, &game_score));
+}
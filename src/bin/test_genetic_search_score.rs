@@ -0,0 +1,111 @@
+use rand::rngs::SmallRng;
+use rand::{Rng, RngCore, SeedableRng};
+use game_search_algorithm::SingleGameState;
+use game_search_algorithm::maze::{Action, MazeParams, MazeState};
+use game_search_algorithm::search::greedy::greedy_action;
+use game_search_algorithm::search::random::random_action;
+
+const H: usize = 30;
+const W: usize = 30;
+const END_TURN: i32 = 100;
+const PARAMS: MazeParams = MazeParams { h: H, w: W, end_turn: END_TURN };
+const TOURNAMENT_SIZE: usize = 3;
+
+type State = MazeState;
+
+/// `actions` を先頭から実行した最終スコアを返す
+///
+/// 途中で合法でなくなった行動や足りない行動はランダムな合法手で埋め、ゲーム終了以降の行動は切り捨てる。
+fn play_actions(state: &State, actions: &mut Vec<Action>, rng: &mut SmallRng) -> i64 {
+    let mut state = state.clone();
+    let mut turn = 0;
+    while !state.is_done() {
+        if turn == actions.len() {
+            actions.push(random_action(&state, rng));
+        } else if !state.legal_actions().contains(&actions[turn]) {
+            actions[turn] = random_action(&state, rng);
+        }
+        state.advance(actions[turn]);
+        turn += 1;
+    }
+    actions.truncate(turn);
+    state.game_score() as i64
+}
+
+/// ランダムに選んだターンの行動をその時点の合法手からランダムに選び直す
+fn mutate(state: &State, actions: &mut [Action], rng: &mut SmallRng) {
+    let turn = rng.gen_range(0..actions.len());
+    let mut state = state.clone();
+    for &action in &actions[..turn] {
+        state.advance(action);
+    }
+    actions[turn] = random_action(&state, rng);
+}
+
+/// ランダムに選んだ `TOURNAMENT_SIZE` 個体のうち最も良い個体を選ぶ
+fn tournament<'a>(population: &'a [(i64, Vec<Action>)], rng: &mut SmallRng) -> &'a Vec<Action> {
+    let mut best: Option<&(i64, Vec<Action>)> = None;
+    for _ in 0..TOURNAMENT_SIZE {
+        let individual = &population[rng.gen_range(0..population.len())];
+        if best.is_none_or(|best| individual.0 > best.0) {
+            best = Some(individual);
+        }
+    }
+    &best.unwrap().1
+}
+
+/// 一点交叉
+fn crossover(parent1: &[Action], parent2: &[Action], rng: &mut SmallRng) -> Vec<Action> {
+    let point = rng.gen_range(0..=parent1.len());
+    parent1[..point].iter().chain(&parent2[point..]).copied().collect()
+}
+
+fn genetic_search_action(state: &State, population_size: usize, generations: u32, mutation_rate: f64, rng: &mut SmallRng) -> Action {
+    let mut population = (0..population_size).map(|_| {
+        let mut actions = Vec::new();
+        let score = play_actions(state, &mut actions, rng);
+        (score, actions)
+    }).collect::<Vec<_>>();
+    for _ in 0..generations {
+        population = (0..population_size).map(|_| {
+            let parent1 = tournament(&population, rng);
+            let parent2 = tournament(&population, rng);
+            let mut actions = crossover(parent1, parent2, rng);
+            let mut score = play_actions(state, &mut actions, rng);
+            if rng.gen::<f64>() < mutation_rate {
+                mutate(state, &mut actions, rng);
+                score = play_actions(state, &mut actions, rng);
+            }
+            (score, actions)
+        }).collect();
+    }
+    population.iter().max_by_key(|(score, _)| *score).unwrap().1[0]
+}
+
+fn test_ai_score(game_number: i32) {
+    let mut rng_for_action = SmallRng::seed_from_u64(0);
+    let mut rng_for_construct = SmallRng::seed_from_u64(0);
+    let mut greedy_score_mean = 0.0;
+    let mut genetic_score_mean = 0.0;
+    for _ in 0..game_number {
+        let initial_state = State::new(rng_for_construct.next_u64(), PARAMS);
+        let mut state = initial_state.clone();
+        while !state.is_done() {
+            state.advance(greedy_action(&state));
+        }
+        greedy_score_mean += state.game_score() as f64;
+        let mut state = initial_state;
+        while !state.is_done() {
+            state.advance(genetic_search_action(&state, 100, 50, 0.5, &mut rng_for_action));
+        }
+        genetic_score_mean += state.game_score() as f64;
+    }
+    greedy_score_mean /= game_number as f64;
+    genetic_score_mean /= game_number as f64;
+    println!("Greedy:\t{greedy_score_mean}");
+    println!("Genetic search:\t{genetic_score_mean}");
+}
+
+fn main() {
+    test_ai_score(10);
+}
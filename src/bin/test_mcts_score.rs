@@ -0,0 +1,62 @@
+use rand::rngs::SmallRng;
+use rand::{RngCore, SeedableRng};
+use game_search_algorithm::SingleGameState;
+use game_search_algorithm::evaluate::RunningStats;
+use game_search_algorithm::maze::{Action, MazeParams, MazeState};
+use game_search_algorithm::search::budget::SearchBudget;
+use game_search_algorithm::search::greedy::greedy_action;
+use game_search_algorithm::search::mcts::{greedy_rollout_policy, mcts_action, mcts_action_with_policy, mcts_action_with_prior, mcts_action_with_tree, uniform_prior, MctsTree, C};
+
+const H: usize = 30;
+const W: usize = 30;
+const END_TURN: i32 = 100;
+const PARAMS: MazeParams = MazeParams { h: H, w: W, end_turn: END_TURN };
+
+type State = MazeState;
+
+fn test_ai_score(game_number: i32, name: &str, mut ai: impl FnMut(&State, &mut SmallRng) -> Action) {
+    let mut rng_for_action = SmallRng::seed_from_u64(0);
+    let mut rng_for_construct = SmallRng::seed_from_u64(0);
+    let mut stats = RunningStats::new();
+    for _ in 0..game_number {
+        let mut state = State::new(rng_for_construct.next_u64(), PARAMS);
+        while !state.is_done() {
+            state.advance(ai(&state, &mut rng_for_action));
+        }
+        stats.push(state.game_score());
+    }
+    println!("{name}:\t{stats}");
+}
+
+/// 木を使い回したときの得点と、行動を選ぶときの根の訪問回数の平均を表示する
+fn test_tree_reuse(game_number: i32, simulation_count: usize) {
+    let mut rng_for_action = SmallRng::seed_from_u64(0);
+    let mut rng_for_construct = SmallRng::seed_from_u64(0);
+    let mut stats = RunningStats::new();
+    let mut root_visit_count_sum = 0;
+    let mut move_number = 0;
+    for _ in 0..game_number {
+        let mut state = State::new(rng_for_construct.next_u64(), PARAMS);
+        let mut tree = MctsTree::new(&state, C);
+        while !state.is_done() {
+            let action = mcts_action_with_tree(&mut tree, SearchBudget::Nodes(simulation_count), &mut rng_for_action);
+            root_visit_count_sum += tree.root_visit_count() as u64;
+            move_number += 1;
+            tree.advance_root(action);
+            state.advance(action);
+        }
+        stats.push(state.game_score());
+    }
+    let root_visit_count_mean = root_visit_count_sum as f64 / move_number as f64;
+    println!("MCTS tree reuse:\t{stats}");
+    println!("Simulations/move:\t{root_visit_count_mean:.1} (reuse) vs {simulation_count} (fresh)");
+}
+
+fn main() {
+    test_tree_reuse(100, 300);
+    test_ai_score(100, "Greedy", |state, _| greedy_action(state));
+    test_ai_score(100, "MCTS", |state, rng| mcts_action(state, 300, rng));
+    test_ai_score(100, "MCTS PUCT (uniform prior)", |state, rng| mcts_action_with_prior(state, 300, C, &uniform_prior::<State>, rng));
+    test_ai_score(10, "MCTS (10 games)", |state, rng| mcts_action(state, 300, rng));
+    test_ai_score(10, "MCTS greedy rollout (10 games)", |state, rng| mcts_action_with_policy(state, 300, C, greedy_rollout_policy::<State>, rng));
+}
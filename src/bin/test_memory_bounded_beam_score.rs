@@ -0,0 +1,25 @@
+use std::time::Instant;
+use game_search_algorithm::evaluate::evaluate_ai;
+use game_search_algorithm::maze::{Action, MazeParams, MazeState};
+use game_search_algorithm::search::beam::beam_search_action;
+use game_search_algorithm::search::memory_bounded_beam::memory_bounded_beam_search_action;
+
+const H: usize = 30;
+const W: usize = 30;
+const END_TURN: i32 = 100;
+const PARAMS: MazeParams = MazeParams { h: H, w: W, end_turn: END_TURN };
+
+type State = MazeState;
+
+fn test_ai_score(game_number: i32, name: &str, ai: impl FnMut(&State) -> Action) {
+    let start = Instant::now();
+    let stats = evaluate_ai(game_number, PARAMS, ai);
+    println!("{name}:\t{}\t(std {:.2}, {:.1}s)", stats.mean, stats.std_dev, start.elapsed().as_secs_f64());
+}
+
+fn main() {
+    test_ai_score(20, "Beam", |state: &State| beam_search_action(state, 5, END_TURN as usize));
+    for max_total_states in [4, 10, 20, 1000] {
+        test_ai_score(20, &format!("Memory bounded beam ({max_total_states})"), |state: &State| memory_bounded_beam_search_action(state, 5, END_TURN as usize, max_total_states));
+    }
+}
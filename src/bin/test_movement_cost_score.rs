@@ -0,0 +1,49 @@
+use rand::rngs::SmallRng;
+use rand::{Rng, RngCore, SeedableRng};
+use game_search_algorithm::SingleGameState;
+use game_search_algorithm::maze::{Action, MazeParams, MazeState};
+use game_search_algorithm::search::beam::beam_search_action;
+use game_search_algorithm::search::random::random_action;
+
+const H: usize = 30;
+const W: usize = 30;
+const END_TURN: i32 = 100;
+const PARAMS: MazeParams = MazeParams { h: H, w: W, end_turn: END_TURN };
+
+/// 沼 (移動コスト 2) にするマスの割合
+const SWAMP_DENSITY: f64 = 0.3;
+
+type State = MazeState;
+
+/// 得点と、沼に入った回数の割合を表示する
+fn test_ai_score(game_number: i32, name: &str, mut ai: impl FnMut(&State) -> Action) {
+    let mut rng_for_construct = SmallRng::seed_from_u64(0);
+    let mut score_mean = 0.0;
+    let mut swamp_number = 0;
+    let mut move_number = 0;
+    for _ in 0..game_number {
+        let mut state = State::new(rng_for_construct.next_u64(), PARAMS);
+        let movement_costs = (0..H).map(|_| {
+            (0..W).map(|_| if rng_for_construct.gen::<f64>() < SWAMP_DENSITY { 2 } else { 1 }).collect()
+        }).collect();
+        state.set_movement_costs(movement_costs);
+        while !state.is_done() {
+            state.advance(ai(&state));
+            if state.movement_cost_at(state.character()) == 2 {
+                swamp_number += 1;
+            }
+            move_number += 1;
+        }
+        score_mean += state.game_score() as f64;
+    }
+    score_mean /= game_number as f64;
+    let swamp_rate = swamp_number as f64 / move_number as f64;
+    println!("{name}:\t{score_mean}\t(swamp rate {swamp_rate:.3})");
+}
+
+/// 沼の割合が同じ盤面で、ランダムとビームサーチが沼に入る割合を比べる
+fn main() {
+    let mut rng_for_action = SmallRng::seed_from_u64(0);
+    test_ai_score(20, "Random", |state| random_action(state, &mut rng_for_action));
+    test_ai_score(20, "Beam", |state| beam_search_action(state, 5, END_TURN as usize));
+}
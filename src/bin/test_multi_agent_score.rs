@@ -0,0 +1,44 @@
+use rand::rngs::SmallRng;
+use rand::{RngCore, SeedableRng};
+use game_search_algorithm::SingleGameState;
+use game_search_algorithm::maze::{Action, MazeParams};
+use game_search_algorithm::multi_agent_maze::MultiAgentMazeState;
+use game_search_algorithm::search::beam::beam_search_action;
+use game_search_algorithm::search::greedy::greedy_action;
+
+const H: usize = 30;
+const W: usize = 30;
+const END_TURN: i32 = 100;
+const PARAMS: MazeParams = MazeParams { h: H, w: W, end_turn: END_TURN };
+
+type State = MultiAgentMazeState;
+
+/// 2 人を相方のいない一人ゲームとしてそれぞれ独立に解く
+fn independent_greedy_action(state: &State) -> (Action, Action) {
+    (greedy_action(&state.single_agent_view(0)), greedy_action(&state.single_agent_view(1)))
+}
+
+fn independent_beam_search_action(state: &State, beam_width: usize, beam_depth: usize) -> (Action, Action) {
+    (beam_search_action(&state.single_agent_view(0), beam_width, beam_depth), beam_search_action(&state.single_agent_view(1), beam_width, beam_depth))
+}
+
+fn test_ai_score(game_number: i32, name: &str, ai: impl Fn(&State) -> (Action, Action)) {
+    let mut rng_for_construct = SmallRng::seed_from_u64(0);
+    let mut score_mean = 0.0;
+    for _ in 0..game_number {
+        let mut state = State::new(rng_for_construct.next_u64(), PARAMS);
+        while !state.is_done() {
+            state.advance(ai(&state));
+        }
+        score_mean += state.game_score as f64;
+    }
+    score_mean /= game_number as f64;
+    println!("{name}:\t{score_mean}");
+}
+
+fn main() {
+    test_ai_score(20, "Greedy", greedy_action);
+    test_ai_score(20, "Independent greedy", independent_greedy_action);
+    test_ai_score(20, "Beam", |state| beam_search_action(state, 5, END_TURN as usize));
+    test_ai_score(20, "Independent beam", |state| independent_beam_search_action(state, 5, END_TURN as usize));
+}
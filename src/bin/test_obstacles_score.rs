@@ -0,0 +1,38 @@
+use rand::rngs::SmallRng;
+use rand::{RngCore, SeedableRng};
+use game_search_algorithm::SingleGameState;
+use game_search_algorithm::maze::MazeParams;
+use game_search_algorithm::obstacles_maze::ObstaclesMazeState;
+use game_search_algorithm::search::chokudai::chokudai_search_action;
+
+const H: usize = 30;
+const W: usize = 30;
+const END_TURN: i32 = 100;
+const PARAMS: MazeParams = MazeParams { h: H, w: W, end_turn: END_TURN };
+const OBSTACLE_NUMBER: usize = 100;
+
+type State = ObstaclesMazeState;
+
+fn test_ai_score(game_number: i32) {
+    let mut rng_for_construct = SmallRng::seed_from_u64(0);
+    let mut score_mean = 0.0;
+    for _ in 0..game_number {
+        let mut state = State::new(rng_for_construct.next_u64(), PARAMS, OBSTACLE_NUMBER);
+        while !state.is_done() {
+            state.advance(chokudai_search_action(&state, 1, END_TURN as usize, 5));
+        }
+        score_mean += state.game_score() as f64;
+    }
+    score_mean /= game_number as f64;
+    println!("Chokudai:\t{score_mean}");
+}
+
+fn main() {
+    let mut state = State::new(0, MazeParams { h: 5, w: 5, end_turn: 3 }, 3);
+    println!("{state:?}");
+    while !state.is_done() {
+        state.advance(chokudai_search_action(&state, 1, 3, 5));
+        println!("{state:?}");
+    }
+    test_ai_score(20);
+}
@@ -0,0 +1,35 @@
+use rand::rngs::SmallRng;
+use rand::{RngCore, SeedableRng};
+use game_search_algorithm::SingleGameState;
+use game_search_algorithm::evaluate::RunningStats;
+use game_search_algorithm::maze::{Action, MazeParams, MazeState, PointDistribution};
+use game_search_algorithm::search::beam::beam_search_action;
+use game_search_algorithm::search::greedy::greedy_action;
+
+const H: usize = 30;
+const W: usize = 30;
+const END_TURN: i32 = 100;
+const PARAMS: MazeParams = MazeParams { h: H, w: W, end_turn: END_TURN };
+const SPARSE: PointDistribution = PointDistribution::Sparse { density: 0.1, max: 9 };
+
+type State = MazeState;
+
+fn test_ai_score(game_number: i32, name: &str, distribution: PointDistribution, ai: impl Fn(&State) -> Action) {
+    let mut rng_for_construct = SmallRng::seed_from_u64(0);
+    let mut score_stats = RunningStats::new();
+    for _ in 0..game_number {
+        let mut state = State::new_with_distribution(rng_for_construct.next_u64(), PARAMS, distribution);
+        while !state.is_done() {
+            state.advance(ai(&state));
+        }
+        score_stats.push(state.game_score());
+    }
+    println!("{name}:\t{score_stats}");
+}
+
+fn main() {
+    test_ai_score(20, "Greedy (uniform)", PointDistribution::Uniform, greedy_action);
+    test_ai_score(20, "Beam (uniform)", PointDistribution::Uniform, |state| beam_search_action(state, 5, END_TURN as usize));
+    test_ai_score(20, "Greedy (sparse)", SPARSE, greedy_action);
+    test_ai_score(20, "Beam (sparse)", SPARSE, |state| beam_search_action(state, 5, END_TURN as usize));
+}
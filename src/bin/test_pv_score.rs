@@ -0,0 +1,56 @@
+use rand::rngs::SmallRng;
+use rand::{RngCore, SeedableRng};
+use game_search_algorithm::SingleGameState;
+use game_search_algorithm::evaluate::RunningStats;
+use game_search_algorithm::maze::{MazeParams, MazeState};
+use game_search_algorithm::search::beam::{beam_search_action_with_prediction, pv_search_action};
+
+const H: usize = 30;
+const W: usize = 30;
+const END_TURN: i32 = 100;
+const PARAMS: MazeParams = MazeParams { h: H, w: W, end_turn: END_TURN };
+
+type State = MazeState;
+
+/// 初手で予測した得点と実際の得点を比べる
+fn test_prediction(game_number: i32) {
+    let mut rng_for_construct = SmallRng::seed_from_u64(0);
+    let mut predicted_stats = RunningStats::new();
+    let mut actual_stats = RunningStats::new();
+    for _ in 0..game_number {
+        let mut state = State::new(rng_for_construct.next_u64(), PARAMS);
+        let mut first_prediction = None;
+        while !state.is_done() {
+            let (action, predicted_score) = beam_search_action_with_prediction(&state, 5, END_TURN as usize);
+            first_prediction.get_or_insert(predicted_score);
+            state.advance(action);
+        }
+        predicted_stats.push(first_prediction.unwrap() as i32);
+        actual_stats.push(state.game_score());
+    }
+    println!("Predicted:\t{predicted_stats}");
+    println!("Actual:\t{actual_stats}");
+}
+
+/// 毎ターン読み直す場合と、読み筋を `commit_number` 手ずつまとめて進める場合の得点を比べる
+fn test_ai_score(game_number: i32, commit_number: usize) {
+    let mut rng_for_construct = SmallRng::seed_from_u64(0);
+    let mut score_stats = RunningStats::new();
+    for _ in 0..game_number {
+        let mut state = State::new(rng_for_construct.next_u64(), PARAMS);
+        while !state.is_done() {
+            for action in pv_search_action(&state, 5, END_TURN as usize).into_iter().take(commit_number) {
+                state.advance(action);
+            }
+        }
+        score_stats.push(state.game_score());
+    }
+    println!("Commit {commit_number}:\t{score_stats}");
+}
+
+fn main() {
+    test_prediction(20);
+    test_ai_score(20, 1);
+    test_ai_score(20, 10);
+    test_ai_score(20, END_TURN as usize);
+}
@@ -0,0 +1,23 @@
+use game_search_algorithm::evaluate::{evaluate_ai, evaluate_ai_with_reseeded_rng, ScoreStats};
+use game_search_algorithm::maze::{MazeParams, MazeState};
+use game_search_algorithm::search::greedy::{greedy_action, random_restart_greedy_action};
+
+const H: usize = 30;
+const W: usize = 30;
+const END_TURN: i32 = 100;
+const PARAMS: MazeParams = MazeParams { h: H, w: W, end_turn: END_TURN };
+
+type State = MazeState;
+
+fn print_stats(name: &str, stats: &ScoreStats) {
+    println!("{name}:\t{}\t(min {}, max {})", stats.mean, stats.min, stats.max);
+}
+
+fn main() {
+    let game_number = 20;
+    print_stats("Greedy", &evaluate_ai(game_number, PARAMS, |state: &State| greedy_action(state)));
+    for noise_scale in [1.0, 3.0, 9.0] {
+        let stats = evaluate_ai_with_reseeded_rng(game_number, PARAMS, |state, rng| random_restart_greedy_action(state, 10, noise_scale, rng));
+        print_stats(&format!("Random restart greedy (noise {noise_scale})"), &stats);
+    }
+}
@@ -0,0 +1,50 @@
+use rand::rngs::SmallRng;
+use rand::{RngCore, SeedableRng};
+use game_search_algorithm::SingleGameState;
+use game_search_algorithm::maze::{MazeParams, MazeState};
+use game_search_algorithm::search::greedy::greedy_action;
+use game_search_algorithm::search::simulated_annealing::simulated_annealing_action;
+use game_search_algorithm::search::tabu::tabu_search_action;
+
+const H: usize = 30;
+const W: usize = 30;
+const END_TURN: i32 = 100;
+const PARAMS: MazeParams = MazeParams { h: H, w: W, end_turn: END_TURN };
+
+type State = MazeState;
+
+fn test_ai_score(game_number: i32) {
+    let mut rng_for_action = SmallRng::seed_from_u64(0);
+    let mut rng_for_construct = SmallRng::seed_from_u64(0);
+    let mut greedy_score_mean = 0.0;
+    let mut annealing_score_mean = 0.0;
+    let mut tabu_score_mean = 0.0;
+    for _ in 0..game_number {
+        let initial_state = State::new(rng_for_construct.next_u64(), PARAMS);
+        let mut state = initial_state.clone();
+        while !state.is_done() {
+            state.advance(greedy_action(&state));
+        }
+        greedy_score_mean += state.game_score() as f64;
+        let mut state = initial_state.clone();
+        while !state.is_done() {
+            state.advance(simulated_annealing_action(&state, 10.0, 0.998, 1000, &mut rng_for_action));
+        }
+        annealing_score_mean += state.game_score() as f64;
+        let mut state = initial_state;
+        while !state.is_done() {
+            state.advance(tabu_search_action(&state, 100, 20, &mut rng_for_action));
+        }
+        tabu_score_mean += state.game_score() as f64;
+    }
+    greedy_score_mean /= game_number as f64;
+    annealing_score_mean /= game_number as f64;
+    tabu_score_mean /= game_number as f64;
+    println!("Greedy:\t{greedy_score_mean}");
+    println!("Simulated annealing:\t{annealing_score_mean}");
+    println!("Tabu search:\t{tabu_score_mean}");
+}
+
+fn main() {
+    test_ai_score(100);
+}
@@ -0,0 +1,9 @@
+use game_search_algorithm::maze::{MazeParams, MazeState};
+use game_search_algorithm::search::greedy::greedy_action;
+use game_search_algorithm::trace::{to_csv, to_json, trace_game};
+
+fn main() {
+    let records = trace_game(MazeState::new(0, MazeParams { h: 3, w: 4, end_turn: 3 }), greedy_action);
+    print!("{}", to_csv(&records));
+    println!("{}", to_json(&records));
+}
@@ -0,0 +1,288 @@
+use std::fmt;
+use rand::rngs::SmallRng;
+use rand::{RngCore, SeedableRng};
+use crate::SingleGameState;
+use crate::maze::{Action, MazeParams, MazeState};
+
+/// 複数ゲームの得点の統計量
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ScoreStats {
+    pub mean: f64,
+    pub std_dev: f64,
+    pub min: i32,
+    pub max: i32,
+    pub median: f64,
+}
+
+impl ScoreStats {
+    /// 得点が 1 つもなければ統計量を決められないので `None` を返す
+    ///
+    /// 平均、標準偏差、最小値、最大値は `RunningStats` で 1 回なめて求め、中央値だけ並べ替えて求める。
+    pub fn from_scores(scores: &[i32]) -> Option<Self> {
+        let mut running_stats = RunningStats::new();
+        for &score in scores {
+            running_stats.push(score);
+        }
+        let (min, max) = (running_stats.min()?, running_stats.max()?);
+        let mut sorted_scores = scores.to_vec();
+        sorted_scores.sort_unstable();
+        let n = sorted_scores.len();
+        let median = if n % 2 == 1 {
+            sorted_scores[n / 2] as f64
+        } else {
+            (sorted_scores[n / 2 - 1] + sorted_scores[n / 2]) as f64 / 2.0
+        };
+        Some(Self {
+            mean: running_stats.mean(),
+            std_dev: running_stats.std_dev(),
+            min,
+            max,
+            median,
+        })
+    }
+}
+
+/// 得点を 1 つずつ受け取り、平均と分散を Welford の方法で逐次更新する (得点を溜めないので中央値は求めない)
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct RunningStats {
+    count: u64,
+    mean: f64,
+    /// 平均からの偏差の 2 乗の和
+    m2: f64,
+    min: Option<i32>,
+    max: Option<i32>,
+}
+
+impl RunningStats {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push(&mut self, score: i32) {
+        self.count += 1;
+        let delta = score as f64 - self.mean;
+        self.mean += delta / self.count as f64;
+        self.m2 += delta * (score as f64 - self.mean);
+        self.min = Some(self.min.map_or(score, |min| min.min(score)));
+        self.max = Some(self.max.map_or(score, |max| max.max(score)));
+    }
+
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    pub fn mean(&self) -> f64 {
+        self.mean
+    }
+
+    /// `ScoreStats` と同じく母集団の標準偏差 (得点が 1 つもなければ 0)
+    pub fn std_dev(&self) -> f64 {
+        if self.count == 0 {
+            return 0.0;
+        }
+        (self.m2 / self.count as f64).sqrt()
+    }
+
+    pub fn min(&self) -> Option<i32> {
+        self.min
+    }
+
+    pub fn max(&self) -> Option<i32> {
+        self.max
+    }
+}
+
+/// `平均\t(std 標準偏差, min 最小値, max 最大値)` の形で表示する
+impl fmt::Display for RunningStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.min, self.max) {
+            (Some(min), Some(max)) => write!(f, "{:.2}\t(std {:.2}, min {min}, max {max})", self.mean, self.std_dev()),
+            _ => write!(f, "no scores"),
+        }
+    }
+}
+
+/// `test_ai_score` と同じ seed の盤面で `game_number` ゲーム遊ばせ、得点の統計量を返す (`game_number` は正であること)
+pub fn evaluate_ai(game_number: i32, params: MazeParams, mut ai: impl FnMut(&MazeState) -> Action) -> ScoreStats {
+    assert!(game_number > 0, "game_number must be positive");
+    let mut rng_for_construct = SmallRng::seed_from_u64(0);
+    let mut scores = Vec::new();
+    for _ in 0..game_number {
+        let mut state = MazeState::new(rng_for_construct.next_u64(), params);
+        while !state.is_done() {
+            state.advance(ai(&state));
+        }
+        scores.push(state.game_score());
+    }
+    ScoreStats::from_scores(&scores).unwrap()
+}
+
+/// `evaluate_ai` と同じ seed の盤面で遊ばせ、AI に渡す乱数をゲームごとにその盤面の seed で初期化し直す
+///
+/// 各ゲームの乱数が前のゲームの手数によらなくなるので、何番目のゲームも単独で再現できる。
+pub fn evaluate_ai_with_reseeded_rng(game_number: i32, params: MazeParams, mut ai: impl FnMut(&MazeState, &mut SmallRng) -> Action) -> ScoreStats {
+    assert!(game_number > 0, "game_number must be positive");
+    let mut rng_for_construct = SmallRng::seed_from_u64(0);
+    let mut scores = Vec::new();
+    for _ in 0..game_number {
+        let seed = rng_for_construct.next_u64();
+        let mut rng_for_action = SmallRng::seed_from_u64(seed);
+        let mut state = MazeState::new(seed, params);
+        while !state.is_done() {
+            state.advance(ai(&state, &mut rng_for_action));
+        }
+        scores.push(state.game_score());
+    }
+    ScoreStats::from_scores(&scores).unwrap()
+}
+
+/// 名前と、盤面から行動を選ぶ AI の組
+pub type NamedAi<'a> = (&'a str, Box<dyn FnMut(&MazeState) -> Action + 'a>);
+
+/// 全ての AI を `evaluate_ai` で同じ seed の盤面で遊ばせ、名前と得点の統計量を渡した順に返す
+pub fn compare_algorithms(game_number: i32, params: MazeParams, algorithms: Vec<NamedAi>) -> Vec<(String, ScoreStats)> {
+    algorithms.into_iter().map(|(name, ai)| (name.to_string(), evaluate_ai(game_number, params, ai))).collect()
+}
+
+/// `compare_algorithms` の結果を、AI ごとに 1 行の表にする
+pub fn format_comparison(results: &[(String, ScoreStats)]) -> String {
+    let name_width = results.iter().map(|(name, _)| name.len()).max().unwrap_or(0).max("algorithm".len());
+    let mut s = format!("{:<name_width$}\t{:>8}\t{:>8}\t{:>5}\t{:>5}\t{:>8}\n", "algorithm", "mean", "std", "min", "max", "median");
+    for (name, stats) in results {
+        s += &format!("{name:<name_width$}\t{:>8.2}\t{:>8.2}\t{:>5}\t{:>5}\t{:>8.1}\n", stats.mean, stats.std_dev, stats.min, stats.max, stats.median);
+    }
+    s
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::maze::{heuristic_game_score, theoretical_max, ScoreType, NORMALIZATION_SCALE};
+    use crate::search::random::random_action;
+    use crate::search::greedy::greedy_action_with_heuristic;
+    use crate::search::beam::{beam_search_action_with_eval, beam_search_action_with_heuristic};
+    use crate::search::chokudai::{chokudai_search_action_with_eval, chokudai_search_action_with_heuristic};
+    use super::*;
+
+    /// 偶数個の得点では中央の 2 つの平均を中央値にする (平均と標準偏差は逐次更新するので丸め誤差を許す)
+    #[test]
+    fn score_stats_from_scores() {
+        let stats = ScoreStats::from_scores(&[9, 2, 4, 4, 5, 4, 7, 5]).unwrap();
+        assert!((stats.mean - 5.0).abs() < 1e-12);
+        assert!((stats.std_dev - 2.0).abs() < 1e-12);
+        assert_eq!((stats.min, stats.max, stats.median), (2, 9, 4.5));
+        assert_eq!(ScoreStats::from_scores(&[3]).unwrap().median, 3.0);
+    }
+
+    /// 得点が 1 つもなければ統計量を返さない
+    #[test]
+    fn score_stats_from_no_scores() {
+        assert_eq!(ScoreStats::from_scores(&[]), None);
+    }
+
+    /// 2, 4, 4, 4, 5, 5, 7, 9 の平均は 5、偏差の 2 乗の和は 9 + 1 + 1 + 1 + 0 + 0 + 4 + 16 = 32 なので、母集団の標準偏差は sqrt(32 / 8) = 2 になる
+    #[test]
+    fn running_stats_known_scores() {
+        let scores = [2, 4, 4, 4, 5, 5, 7, 9];
+        let mut stats = RunningStats::new();
+        assert_eq!((stats.std_dev(), stats.min(), stats.max()), (0.0, None, None));
+        for score in scores {
+            stats.push(score);
+        }
+        assert_eq!(stats.count(), 8);
+        assert!((stats.mean() - 5.0).abs() < 1e-12);
+        assert!((stats.std_dev() - 2.0).abs() < 1e-12);
+        assert_eq!((stats.min(), stats.max()), (Some(2), Some(9)));
+        let score_stats = ScoreStats::from_scores(&scores).unwrap();
+        assert!((stats.std_dev() - score_stats.std_dev).abs() < 1e-12);
+        assert_eq!(stats.to_string(), "5.00\t(std 2.00, min 2, max 9)");
+        assert_eq!(RunningStats::new().to_string(), "no scores");
+    }
+
+    /// ゲームごとに選んだ行動列 (番号が `noisy_game_number` 未満のゲームでは毎ターン余分に乱数を 1 回引く)
+    fn random_trajectories(game_number: i32, noisy_game_number: usize) -> Vec<Vec<Action>> {
+        let mut trajectories: Vec<Vec<Action>> = Vec::new();
+        evaluate_ai_with_reseeded_rng(game_number, MazeParams { h: 3, w: 4, end_turn: 4 }, |state, rng| {
+            if state.turn() == 0 {
+                trajectories.push(Vec::new());
+            }
+            if trajectories.len() <= noisy_game_number {
+                random_action(state, rng);
+            }
+            let action = random_action(state, rng);
+            trajectories.last_mut().unwrap().push(action);
+            action
+        });
+        trajectories
+    }
+
+    /// 乱数をゲームごとに初期化し直すと、前のゲームで乱数を何回引いたかによらず、同じ番号のゲームは同じ行動列になる
+    #[test]
+    fn reseeded_trajectories_are_independent() {
+        let trajectories = random_trajectories(20, 0);
+        for (game_index, trajectory) in trajectories.iter().enumerate() {
+            assert_eq!(random_trajectories(game_index as i32 + 1, game_index)[game_index], *trajectory);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "game_number must be positive")]
+    fn evaluate_ai_rejects_zero_games() {
+        evaluate_ai(0, MazeParams { h: 3, w: 4, end_turn: 4 }, |state| state.legal_actions()[0]);
+    }
+
+    /// 評価値が全て 0 なら生成順に展開するので、最後のビームの先頭は必ず最初の合法手から始まる
+    #[test]
+    fn zero_eval_expands_in_generation_order() {
+        let params = MazeParams { h: 30, w: 30, end_turn: 100 };
+        let zero = |_: &MazeState| 0 as ScoreType;
+        let mut rng_for_construct = SmallRng::seed_from_u64(0);
+        for _ in 0..10 {
+            let state = MazeState::new(rng_for_construct.next_u64(), params);
+            assert_eq!(beam_search_action_with_eval(&state, 5, params.end_turn as usize, &zero), state.legal_actions()[0]);
+            assert_eq!(chokudai_search_action_with_eval(&state, 1, params.end_turn as usize, 5, &zero), state.legal_actions()[0]);
+        }
+    }
+
+    /// 盤面の大きさやゲームの長さによらず、正規化した評価値は得点を最初の盤面の `theoretical_max` で割った値で、0 以上 `NORMALIZATION_SCALE` 以下に収まる
+    #[test]
+    fn normalized_eval_is_fraction_of_theoretical_max() {
+        let mut rng_for_action = SmallRng::seed_from_u64(0);
+        let mut rng_for_construct = SmallRng::seed_from_u64(0);
+        for params in [MazeParams { h: 30, w: 30, end_turn: 100 }, MazeParams { h: 5, w: 5, end_turn: 10 }, MazeParams { h: 10, w: 40, end_turn: 300 }] {
+            for _ in 0..5 {
+                let mut state = MazeState::new(rng_for_construct.next_u64(), params);
+                let max_score = theoretical_max(&state);
+                state.set_normalized(true);
+                loop {
+                    state.evaluate_score();
+                    assert_eq!(state.evaluated_score(), state.game_score() as ScoreType * NORMALIZATION_SCALE / max_score);
+                    assert!((0..=NORMALIZATION_SCALE).contains(&state.evaluated_score()));
+                    if state.is_done() { break; }
+                    state.advance(random_action(&state, &mut rng_for_action));
+                }
+            }
+        }
+        // 一直線に 1, 2, 3 点が並ぶ盤面では、最大値の 6 点を取りきると `NORMALIZATION_SCALE` になる
+        let mut state = MazeState::from_board_string("3 0 0 @123").unwrap();
+        state.set_normalized(true);
+        for _ in 0..3 {
+            state.advance(state.legal_actions()[0]);
+        }
+        state.evaluate_score();
+        assert_eq!(state.evaluated_score(), NORMALIZATION_SCALE);
+    }
+
+    /// `heuristic_game_score` で評価する探索は、ヒューリスティックを渡さずに `evaluate_score` で評価する探索と同じ行動を選ぶ
+    #[test]
+    fn heuristic_game_score_is_default() {
+        let params = MazeParams { h: 30, w: 30, end_turn: 100 };
+        let beam_depth = params.end_turn as usize;
+        let mut rng_for_construct = SmallRng::seed_from_u64(0);
+        for _ in 0..10 {
+            let state = MazeState::new(rng_for_construct.next_u64(), params);
+            assert_eq!(greedy_action_with_heuristic(&state, Some(heuristic_game_score)), greedy_action_with_heuristic(&state, None));
+            assert_eq!(beam_search_action_with_heuristic(&state, 5, beam_depth, Some(heuristic_game_score)), beam_search_action_with_heuristic(&state, 5, beam_depth, None));
+            assert_eq!(chokudai_search_action_with_heuristic(&state, 1, beam_depth, 5, Some(heuristic_game_score)), chokudai_search_action_with_heuristic(&state, 1, beam_depth, 5, None));
+        }
+    }
+}
@@ -0,0 +1,38 @@
+pub mod maze;
+pub mod search;
+pub mod alternate_maze;
+pub mod simultaneous_maze;
+pub mod auto_move_maze;
+pub mod multi_agent_maze;
+pub mod obstacles_maze;
+pub mod trace;
+pub mod evaluate;
+pub mod tournament;
+pub mod time_keeper;
+pub mod args;
+
+/// 一人ゲームの状態
+pub trait SingleGameState: Clone {
+    type Action: Copy;
+    type ScoreType: Copy + Ord;
+
+    fn is_done(&self) -> bool;
+
+    fn advance(&mut self, action: Self::Action);
+
+    fn legal_actions(&self) -> Vec<Self::Action>;
+
+    /// 合法手が 1 つもない状態か (この状態は現在の得点のまま終わったものとして扱う)
+    fn is_dead_end(&self) -> bool {
+        self.legal_actions().is_empty()
+    }
+
+    fn evaluate_score(&mut self);
+
+    fn evaluated_score(&self) -> Self::ScoreType;
+
+    /// 探索の根からこの状態に至るまでに最初に選んだ行動
+    fn first_action(&self) -> Option<Self::Action>;
+
+    fn set_first_action(&mut self, action: Self::Action);
+}
@@ -0,0 +1,1350 @@
+use std::cell::RefCell;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::rc::Rc;
+use std::fmt::{Debug, Display, Formatter};
+use std::hash::Hash;
+use rand::rngs::SmallRng;
+use rand::{Rng, RngCore, SeedableRng};
+use crate::SingleGameState;
+
+/// キャラクターの移動方向 (先頭の 4 つが上下左右で、残りの 4 つが斜め)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Action {
+    Right,
+    Left,
+    Down,
+    Up,
+    DownRight,
+    UpRight,
+    DownLeft,
+    UpLeft,
+}
+
+impl Action {
+    pub const ALL: [Action; 8] = [Action::Right, Action::Left, Action::Down, Action::Up, Action::DownRight, Action::UpRight, Action::DownLeft, Action::UpLeft];
+
+    /// `(dx, dy)`
+    pub fn delta(&self) -> (i32, i32) {
+        match self {
+            Action::Right => (1, 0),
+            Action::Left => (-1, 0),
+            Action::Down => (0, 1),
+            Action::Up => (0, -1),
+            Action::DownRight => (1, 1),
+            Action::UpRight => (1, -1),
+            Action::DownLeft => (-1, 1),
+            Action::UpLeft => (-1, -1),
+        }
+    }
+}
+
+impl Display for Action {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            Action::Right => "→",
+            Action::Left => "←",
+            Action::Down => "↓",
+            Action::Up => "↑",
+            Action::DownRight => "↘",
+            Action::UpRight => "↗",
+            Action::DownLeft => "↙",
+            Action::UpLeft => "↖",
+        };
+        write!(f, "{s}")
+    }
+}
+
+pub type ScoreType = i64;
+
+/// `evaluate_score_with` や探索の `*_with_heuristic` に渡す、盤面から評価値を計算する関数
+pub type HeuristicFn<S = MazeState> = fn(&S) -> <S as SingleGameState>::ScoreType;
+
+/// `distance_weight` を使うときに `game_score` に掛ける倍率
+const DISTANCE_SCALE: ScoreType = 1000;
+
+/// `set_normalized` で正規化した評価値の 1 に当たる値
+pub const NORMALIZATION_SCALE: ScoreType = 1_000_000;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Coord {
+    pub x: usize,
+    pub y: usize,
+}
+
+impl Coord {
+    /// 上下左右にだけ動くときの距離
+    pub fn manhattan_distance(&self, other: &Coord) -> usize {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    }
+
+    /// 斜めにも動けるときの距離
+    pub fn chebyshev_distance(&self, other: &Coord) -> usize {
+        self.x.abs_diff(other.x).max(self.y.abs_diff(other.y))
+    }
+}
+
+/// 盤面の大きさとゲームの長さ
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MazeParams {
+    pub h: usize,
+    pub w: usize,
+    pub end_turn: i32,
+}
+
+/// 盤面を作るときの各マスの得点の分布 (どの分布でも得点は 0..=9 に収める)
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PointDistribution {
+    /// 0..=9 から一様に選ぶ
+    #[default]
+    Uniform,
+    /// 確率 `density` で 1..=`max` から一様に選び、それ以外は 0 にする (`max` は 1..=9)
+    Sparse { density: f64, max: i32 },
+    /// 平均 `mean`、標準偏差 `std` の正規分布から選んで四捨五入し、0..=9 に切り詰める
+    Gaussian { mean: f64, std: f64 },
+}
+
+impl PointDistribution {
+    fn sample(&self, rng: &mut impl RngCore) -> i32 {
+        match *self {
+            PointDistribution::Uniform => (rng.next_u32() % 10) as i32,
+            PointDistribution::Sparse { density, max } => {
+                if rng.gen::<f64>() < density { rng.gen_range(1..=max) } else { 0 }
+            }
+            PointDistribution::Gaussian { mean, std } => {
+                // Box-Muller 法で標準正規分布に従う値を作る
+                let u1 = 1.0 - rng.gen::<f64>();
+                let u2 = rng.gen::<f64>();
+                let z = (-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos();
+                ((mean + std * z).round() as i32).clamp(0, 9)
+            }
+        }
+    }
+}
+
+/// 盤面の各マスの得点と、キャラクターの位置と、ターン (`0..=end_turn`) に割り当てた乱数
+///
+/// 移動コストがあると同じ盤面に違うターンで行き着くことがあるので、`board_key` と同じくターンもハッシュに含める。
+pub struct ZobristTable {
+    points: Vec<Vec<[u64; 10]>>,
+    character: Vec<Vec<u64>>,
+    turn: Vec<u64>,
+}
+
+impl ZobristTable {
+    pub fn new(height: usize, width: usize, end_turn: i32, seed: u64) -> Self {
+        let mut rng = SmallRng::seed_from_u64(seed);
+        let points = (0..height).map(|_| {
+            (0..width).map(|_| std::array::from_fn(|_| rng.next_u64())).collect()
+        }).collect();
+        let character = (0..height).map(|_| {
+            (0..width).map(|_| rng.next_u64()).collect()
+        }).collect();
+        let turn = (0..=end_turn).map(|_| rng.next_u64()).collect();
+        Self { points, character, turn }
+    }
+}
+
+thread_local! {
+    /// 盤面の大きさごとに一度だけ作る `zobrist_hash` 用のテーブル
+    static ZOBRIST_TABLES: RefCell<HashMap<(usize, usize, i32), Rc<ZobristTable>>> = RefCell::new(HashMap::new());
+}
+
+/// 置換表のキーにする盤面の中身 (得点の配置、キャラクターの位置、ターン)
+///
+/// `MazeState` の `Eq` はビームで並べるために評価値だけを比べるので、盤面の同一性はこちらで比べる。
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BoardKey {
+    points: Vec<Vec<i32>>,
+    character: Coord,
+    turn: i32,
+}
+
+/// `advance_with_undo` で変わった部分を元に戻すための記録
+#[derive(Debug, Clone)]
+pub struct UndoRecord {
+    character: Coord,
+    point: i32,
+    turn: i32,
+    /// 行動する前から移動先に来たことがあったか
+    revisited: bool,
+    /// 行動した後に `evaluate_score` や `set_first_action` を呼んでも戻せるように、行動する前の値を覚えておく
+    evaluated_score: ScoreType,
+    first_action: Option<Action>,
+}
+
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MazeState {
+    height: usize,
+    width: usize,
+    end_turn: i32,
+    points: Vec<Vec<i32>>,
+    walls: Vec<Vec<bool>>,
+    /// 各マスに入るのにかかるターン数 (既定は全て 1)
+    movement_costs: Vec<Vec<u8>>,
+    turn: i32,
+    character: Coord,
+    game_score: i32,
+    evaluated_score: ScoreType,
+    first_action: Option<Action>,
+    distance_weight: f64,
+    /// 来たことのあるマスを 1 ビットずつ記録する (`y * width + x` 番目のビット)
+    visited: Vec<u64>,
+    /// 来たことのあるマスにもう一度入った回数
+    revisit_count: u32,
+    revisit_penalty: ScoreType,
+    /// `set_normalized` で正規化するときの、このゲームで取れる得点の最大値
+    normalization_max: Option<ScoreType>,
+    allow_diagonal: bool,
+}
+
+impl MazeState {
+    pub fn new(seed: u64, params: MazeParams) -> Self {
+        Self::new_with_seeds(seed, seed, params)
+    }
+
+    /// キャラクターの初期位置と得点の配置を別々のシードで決める
+    pub fn new_with_seeds(char_seed: u64, point_seed: u64, params: MazeParams) -> Self {
+        Self::generate(char_seed, point_seed, params, 0.0, PointDistribution::Uniform)
+    }
+
+    /// 斜め 4 方向にも移動できる盤面を作る
+    pub fn new_with_diagonals(seed: u64, params: MazeParams) -> Self {
+        let mut state = Self::new(seed, params);
+        state.set_allow_diagonal(true);
+        state
+    }
+
+    /// 各マスを確率 `wall_density` で壁にした盤面を作る (キャラクターのいるマスは壁にしない)
+    pub fn new_with_walls(seed: u64, params: MazeParams, wall_density: f64) -> Self {
+        Self::generate(seed, seed, params, wall_density, PointDistribution::Uniform)
+    }
+
+    /// 各マスの得点を `distribution` に従って選んだ盤面を作る
+    pub fn new_with_distribution(seed: u64, params: MazeParams, distribution: PointDistribution) -> Self {
+        if let PointDistribution::Sparse { density, max } = distribution {
+            assert!((0.0..=1.0).contains(&density), "density must be in 0.0..=1.0");
+            assert!((1..=9).contains(&max), "max must be in 1..=9");
+        }
+        Self::generate(seed, seed, params, 0.0, distribution)
+    }
+
+    /// 乱数を使わずに、与えた得点の配置とキャラクターの位置から盤面を作る (ターンと得点は 0)
+    ///
+    /// 盤面が空でない長方形であること、キャラクターが盤面内にいてそのマスが 0 点であること、得点が 0..=9 であることを確かめる。
+    pub fn from_points(points: Vec<Vec<i32>>, character: Coord, end_turn: i32) -> Self {
+        let height = points.len();
+        assert!(height > 0, "points must have at least one row");
+        let width = points[0].len();
+        assert!(width > 0 && points.iter().all(|row| row.len() == width), "points must be a non-empty rectangle");
+        assert!(character.y < height && character.x < width, "character is out of the board");
+        assert!(points.iter().flatten().all(|point| (0..=9).contains(point)), "points must be in 0..=9");
+        assert_eq!(points[character.y][character.x], 0, "the character's cell must have no point");
+        Self::from_parts(MazeParams { h: height, w: width, end_turn }, points, 0, character)
+    }
+
+    /// 呼び出し側が渡した乱数生成器だけを使って盤面を作る (キャラクターの位置を決めてから同じ乱数列で得点を配置する)
+    pub fn with_rng(rng: &mut impl RngCore, params: MazeParams) -> Self {
+        let character = Self::random_character(rng, &params);
+        Self::generate_with_character(rng, character, params, 0.0, PointDistribution::Uniform)
+    }
+
+    fn generate(char_seed: u64, point_seed: u64, params: MazeParams, wall_density: f64, distribution: PointDistribution) -> Self {
+        let rng_from_seed = |seed: u64| if seed < u64::MAX {
+            SmallRng::seed_from_u64(seed)
+        } else {
+            SmallRng::from_entropy()
+        };
+        let character = Self::random_character(&mut rng_from_seed(char_seed), &params);
+        Self::generate_with_character(&mut rng_from_seed(point_seed), character, params, wall_density, distribution)
+    }
+
+    fn random_character(rng: &mut impl RngCore, params: &MazeParams) -> Coord {
+        let y = rng.next_u32() as usize % params.h;
+        let x = rng.next_u32() as usize % params.w;
+        Coord { x, y }
+    }
+
+    fn generate_with_character(rng_for_points: &mut impl RngCore, character: Coord, params: MazeParams, wall_density: f64, distribution: PointDistribution) -> Self {
+        let MazeParams { h: height, w: width, end_turn } = params;
+        let mut points = vec![vec![0; width]; height];
+        for (y, row) in points.iter_mut().enumerate() {
+            for (x, point) in row.iter_mut().enumerate() {
+                if y == character.y && x == character.x { continue; }
+                *point = distribution.sample(rng_for_points);
+            }
+        }
+        let mut walls = vec![vec![false; width]; height];
+        if wall_density > 0.0 {
+            for (y, row) in walls.iter_mut().enumerate() {
+                for (x, wall) in row.iter_mut().enumerate() {
+                    if y == character.y && x == character.x { continue; }
+                    if rng_for_points.gen::<f64>() < wall_density {
+                        *wall = true;
+                        points[y][x] = 0;
+                    }
+                }
+            }
+        }
+        let mut state = Self {
+            height,
+            width,
+            end_turn,
+            points,
+            walls,
+            movement_costs: vec![vec![1; width]; height],
+            turn: 0,
+            character,
+            game_score: 0,
+            evaluated_score: 0,
+            first_action: None,
+            distance_weight: 0.0,
+            visited: Vec::new(),
+            revisit_count: 0,
+            revisit_penalty: 0,
+            normalization_max: None,
+            allow_diagonal: false,
+        };
+        state.reset_visited();
+        state
+    }
+
+    pub fn game_score(&self) -> i32 {
+        self.game_score
+    }
+
+    pub fn turn(&self) -> i32 {
+        self.turn
+    }
+
+    pub fn end_turn(&self) -> i32 {
+        self.end_turn
+    }
+
+    pub fn point_at(&self, c: &Coord) -> i32 {
+        self.points[c.y][c.x]
+    }
+
+    pub fn character(&self) -> &Coord {
+        &self.character
+    }
+
+    /// ニューラルネットワークなどに渡すための、長さ `2 * H * W + 1` の特徴量ベクトルを返す
+    ///
+    /// - `[0, H * W)`: `y * W + x` 番目に `(y, x)` の得点を 9 で割ったもの (0.0〜1.0)
+    /// - `[H * W, 2 * H * W)`: `H * W + y * W + x` 番目はキャラクターが `(y, x)` にいれば 1.0、いなければ 0.0
+    /// - `2 * H * W`: `turn / END_TURN` (`END_TURN` が 0 なら最初から終わっているので 1.0)
+    pub fn to_feature_vector(&self) -> Vec<f32> {
+        let size = self.height * self.width;
+        let mut features = Vec::with_capacity(2 * size + 1);
+        features.extend(self.points.iter().flatten().map(|&point| point as f32 / 9.0));
+        features.extend((0..size).map(|_| 0.0));
+        features[size + self.character.y * self.width + self.character.x] = 1.0;
+        features.push(if self.end_turn == 0 { 1.0 } else { self.turn as f32 / self.end_turn as f32 });
+        features
+    }
+
+    /// 1 ターンに高々 1 マスしか取れないので、残りの得点の大きい方から残りターン数分を足したものは今後得られる得点の上界になる
+    ///
+    /// 移動コストが 0 のマスがあると 1 ターンに何マスも取れるので上界にならない。
+    pub fn remaining_score_upper_bound(&self) -> i32 {
+        let mut points = self.points.iter().flatten().copied().filter(|&point| point > 0).collect::<Vec<_>>();
+        points.sort_unstable_by(|a, b| b.cmp(a));
+        points.iter().take((self.end_turn - self.turn) as usize).sum()
+    }
+
+    /// `advance` と同じく行動し、`undo` に渡すと行動前の盤面に戻せる記録を返す
+    pub fn advance_with_undo(&mut self, action: Action) -> UndoRecord {
+        let character = self.character.clone();
+        let (dx, dy) = action.delta();
+        let ty = (character.y as i32 + dy) as usize;
+        let tx = (character.x as i32 + dx) as usize;
+        let point = self.points[ty][tx];
+        let turn = self.turn;
+        let revisit_count = self.revisit_count;
+        let (evaluated_score, first_action) = (self.evaluated_score, self.first_action);
+        self.advance(action);
+        UndoRecord { character, point, turn, revisited: self.revisit_count > revisit_count, evaluated_score, first_action }
+    }
+
+    pub fn undo(&mut self, record: &UndoRecord) {
+        self.points[self.character.y][self.character.x] = record.point;
+        self.game_score -= record.point;
+        if record.revisited {
+            self.revisit_count -= 1;
+        } else {
+            let index = self.character.y * self.width + self.character.x;
+            self.visited[index / 64] &= !(1 << (index % 64));
+        }
+        self.character = record.character.clone();
+        self.turn = record.turn;
+        self.evaluated_score = record.evaluated_score;
+        self.first_action = record.first_action;
+    }
+
+    /// 途中の盤面からそのまま一人ゲームを始める
+    pub(crate) fn from_parts(params: MazeParams, points: Vec<Vec<i32>>, turn: i32, character: Coord) -> Self {
+        let mut state = Self {
+            height: params.h,
+            width: params.w,
+            end_turn: params.end_turn,
+            points,
+            walls: vec![vec![false; params.w]; params.h],
+            movement_costs: vec![vec![1; params.w]; params.h],
+            turn,
+            character,
+            game_score: 0,
+            evaluated_score: 0,
+            first_action: None,
+            distance_weight: 0.0,
+            visited: Vec::new(),
+            revisit_count: 0,
+            revisit_penalty: 0,
+            normalization_max: None,
+            allow_diagonal: false,
+        };
+        state.reset_visited();
+        state
+    }
+
+    /// 来たことのあるマスを今いるマスだけにする
+    fn reset_visited(&mut self) {
+        self.visited = vec![0; (self.height * self.width).div_ceil(64)];
+        self.revisit_count = 0;
+        self.visit();
+    }
+
+    /// 今いるマスを来たことのあるマスとして記録し、前から来たことがあったかを返す
+    fn visit(&mut self) -> bool {
+        let index = self.character.y * self.width + self.character.x;
+        let bit = 1 << (index % 64);
+        let revisited = self.visited[index / 64] & bit != 0;
+        self.visited[index / 64] |= bit;
+        revisited
+    }
+
+    /// 0 より大きくすると、`evaluate_score` で来たことのあるマスにもう一度入った回数 1 回につき `revisit_penalty` を引く (`game_score` は変えない)
+    pub fn set_revisit_penalty(&mut self, revisit_penalty: ScoreType) {
+        self.revisit_penalty = revisit_penalty;
+    }
+
+    /// 来たことのあるマスにもう一度入った回数
+    pub fn revisit_count(&self) -> u32 {
+        self.revisit_count
+    }
+
+    /// 各マスに入るのにかかるターン数を盤面と同じ大きさの配列で与える (2 なら沼、0 なら道)
+    ///
+    /// 残りのターン数より移動コストが大きいマスには入れない。移動コストが 0 のマスだけを行き来する方策ではゲームが終わらない。
+    /// `to_board_string` には移動コストを書き出さない。
+    pub fn set_movement_costs(&mut self, movement_costs: Vec<Vec<u8>>) {
+        assert!(movement_costs.len() == self.height && movement_costs.iter().all(|row| row.len() == self.width), "movement_costs must match the board size");
+        self.movement_costs = movement_costs;
+    }
+
+    pub fn movement_cost_at(&self, c: &Coord) -> u8 {
+        self.movement_costs[c.y][c.x]
+    }
+
+    /// `Action::ALL[i]` が合法手なら `i` 番目のビットを立てる (斜めに動けない盤面では下位 4 ビットだけを使う)
+    ///
+    /// `legal_actions` と違ってヒープを使わないので、探索の内側のループでは `actions_from_mask` と組み合わせて使う。
+    pub fn legal_actions_mask(&self) -> u8 {
+        let mut mask = 0;
+        for (i, &action) in Action::ALL[..self.direction_number()].iter().enumerate() {
+            if self.can_move(action) {
+                mask |= 1 << i;
+            }
+        }
+        mask
+    }
+
+    /// 移動コストが 0 の (ターンを進めずに入れる) マスがあるか
+    pub(crate) fn has_road(&self) -> bool {
+        self.movement_costs.iter().flatten().any(|&cost| cost == 0)
+    }
+
+    /// 0 より大きくすると、`evaluate_score` で最も近い得点のあるマスまでのマンハッタン距離に反比例する `distance_weight / distance` 点を加える
+    pub fn set_distance_weight(&mut self, distance_weight: f64) {
+        self.distance_weight = distance_weight;
+    }
+
+    /// `true` にすると、`evaluate_score` で `game_score` の代わりに、今の得点と `theoretical_max` の和 (このゲームで取れる得点の最大値) に対する
+    /// 得点の割合を `NORMALIZATION_SCALE` 倍した値 (0 以上 `NORMALIZATION_SCALE` 以下) を使う
+    ///
+    /// 最大値は呼んだ時点の盤面で決め、その後に進めた盤面でも同じ値で割る。
+    pub fn set_normalized(&mut self, normalized: bool) {
+        self.normalization_max = normalized.then(|| self.game_score as ScoreType + theoretical_max(self));
+    }
+
+    /// `set_normalized` のときの `game_score` に代わる値。得点を取れるマスが一つもなければ 0
+    fn normalized_score(&self, normalization_max: ScoreType) -> ScoreType {
+        if normalization_max == 0 {
+            return 0;
+        }
+        self.game_score as ScoreType * NORMALIZATION_SCALE / normalization_max
+    }
+
+    /// `true` にすると、上下左右に加えて斜め 4 方向にも移動できる
+    pub fn set_allow_diagonal(&mut self, allow_diagonal: bool) {
+        self.allow_diagonal = allow_diagonal;
+    }
+
+    /// 評価値を `heuristic_reachable_points` にする
+    ///
+    /// 取ったマスの得点は届くマスの合計から `game_score` に移るだけなので、得点を取っても評価値は増えない。
+    /// 評価値が下がるのは、得点のあるマスが届かなくなったときだけになる。
+    pub fn heuristic_evaluate_score(&mut self) {
+        self.evaluate_score_with(heuristic_reachable_points);
+    }
+
+    /// 評価値を `heuristic` で計算する
+    pub fn evaluate_score_with(&mut self, heuristic: HeuristicFn) {
+        self.evaluated_score = heuristic(self);
+    }
+
+    /// `evaluate_score` の評価値に、`prev` からこの状態への移動によるポテンシャル (最も近い得点のあるマスまでの距離の符号を反転したもの) の増分を加える
+    ///
+    /// 増分は始点と終点のポテンシャルだけで決まるので、行動列全体で足し合わせても最善の行動列は変わらない。
+    pub fn set_evaluated_score_with_potential(&mut self, prev: &MazeState) {
+        self.evaluate_score();
+        self.evaluated_score += self.potential() - prev.potential();
+    }
+
+    /// 得点のあるマスが残っていなければ 0
+    fn potential(&self) -> ScoreType {
+        -(self.nearest_point_distance().unwrap_or(0) as ScoreType)
+    }
+
+    pub(crate) fn direction_number(&self) -> usize {
+        if self.allow_diagonal { 8 } else { 4 }
+    }
+
+    /// `action` の移動先が盤面内で壁でなく、その移動コストが残りのターン数以下か
+    fn can_move(&self, action: Action) -> bool {
+        let (dx, dy) = action.delta();
+        let ty = self.character.y as i32 + dy;
+        let tx = self.character.x as i32 + dx;
+        if ty < 0 || ty >= self.height as i32 || tx < 0 || tx >= self.width as i32 {
+            return false;
+        }
+        let (ty, tx) = (ty as usize, tx as usize);
+        !self.walls[ty][tx] && self.turn + self.movement_costs[ty][tx] as i32 <= self.end_turn
+    }
+
+    fn nearest_point_distance(&self) -> Option<usize> {
+        let mut nearest = None;
+        for (y, row) in self.points.iter().enumerate() {
+            for (x, &point) in row.iter().enumerate() {
+                if point == 0 { continue; }
+                let distance = self.character.manhattan_distance(&Coord { x, y });
+                if nearest.is_none_or(|nearest| distance < nearest) {
+                    nearest = Some(distance);
+                }
+            }
+        }
+        nearest
+    }
+
+    /// `table` はこの盤面と同じ大きさで、終了ターンがこの盤面以上であること
+    pub fn hash(&self, table: &ZobristTable) -> u64 {
+        let mut hash = table.character[self.character.y][self.character.x] ^ table.turn[self.turn as usize];
+        for (y, row) in self.points.iter().enumerate() {
+            for (x, &point) in row.iter().enumerate() {
+                hash ^= table.points[y][x][point as usize];
+            }
+        }
+        hash
+    }
+
+    /// 中身が同じ盤面どうしでだけ等しくなるキー
+    pub fn board_key(&self) -> BoardKey {
+        BoardKey { points: self.points.clone(), character: self.character.clone(), turn: self.turn }
+    }
+
+    /// 盤面の大きさと終了ターンごとに共有するテーブルで計算した `hash`
+    pub fn zobrist_hash(&self) -> u64 {
+        let table = ZOBRIST_TABLES.with(|tables| {
+            tables.borrow_mut()
+                .entry((self.height, self.width, self.end_turn))
+                .or_insert_with(|| Rc::new(ZobristTable::new(self.height, self.width, self.end_turn, 0)))
+                .clone()
+        });
+        self.hash(&table)
+    }
+
+    /// 終了ターン、ターン、得点と盤面を `"{end_turn} {turn} {game_score} {行}/{行}/..."` の形の 1 行にする
+    ///
+    /// 盤面は得点を数字 (0 点は `.`)、壁を `#`、キャラクターを `@` で表す。探索の設定 (`distance_weight` や斜め移動) は含まない。
+    pub fn to_board_string(&self) -> String {
+        let rows = (0..self.height).map(|y| {
+            (0..self.width).map(|x| {
+                if self.character.y == y && self.character.x == x {
+                    '@'
+                } else if self.walls[y][x] {
+                    '#'
+                } else if self.points[y][x] > 0 {
+                    char::from(b'0' + self.points[y][x] as u8)
+                } else {
+                    '.'
+                }
+            }).collect::<String>()
+        }).collect::<Vec<_>>().join("/");
+        format!("{} {} {} {}", self.end_turn, self.turn, self.game_score, rows)
+    }
+
+    /// `to_board_string` の逆変換 (ターン数が `0..=end_turn` に収まらない文字列はエラーになる)
+    pub fn from_board_string(s: &str) -> Result<MazeState, ParseError> {
+        let mut fields = s.split_whitespace();
+        let mut next_number = |name: &'static str| {
+            let field = fields.next().ok_or(ParseError::MissingField(name))?;
+            field.parse::<i32>().map_err(|_| ParseError::InvalidNumber(name, field.to_string()))
+        };
+        let end_turn = next_number("end_turn")?;
+        let turn = next_number("turn")?;
+        let game_score = next_number("game_score")?;
+        if !(0..=end_turn).contains(&turn) {
+            return Err(ParseError::TurnOutOfRange(turn, end_turn));
+        }
+        let board = fields.next().ok_or(ParseError::MissingField("board"))?;
+        if let Some(field) = fields.next() {
+            return Err(ParseError::TrailingField(field.to_string()));
+        }
+        let mut points = Vec::new();
+        let mut walls = Vec::new();
+        let mut characters = Vec::new();
+        for (y, row) in board.split('/').enumerate() {
+            let mut point_row = Vec::new();
+            let mut wall_row = Vec::new();
+            for (x, c) in row.chars().enumerate() {
+                match c {
+                    '@' => characters.push(Coord { x, y }),
+                    '#' | '.' | '0'..='9' => {}
+                    _ => return Err(ParseError::InvalidCell(c)),
+                }
+                point_row.push(c.to_digit(10).map_or(0, |point| point as i32));
+                wall_row.push(c == '#');
+            }
+            points.push(point_row);
+            walls.push(wall_row);
+        }
+        let width = points[0].len();
+        if width == 0 || points.iter().any(|row| row.len() != width) {
+            return Err(ParseError::RaggedRows);
+        }
+        if characters.len() != 1 {
+            return Err(ParseError::CharacterCount(characters.len()));
+        }
+        let mut state = Self {
+            height: points.len(),
+            width,
+            end_turn,
+            points,
+            movement_costs: vec![vec![1; width]; walls.len()],
+            walls,
+            turn,
+            character: characters.pop().unwrap(),
+            game_score,
+            evaluated_score: 0,
+            first_action: None,
+            distance_weight: 0.0,
+            visited: Vec::new(),
+            revisit_count: 0,
+            revisit_penalty: 0,
+            normalization_max: None,
+            allow_diagonal: false,
+        };
+        state.reset_visited();
+        Ok(state)
+    }
+
+    /// マスに表示する文字と色 (ANSI エスケープシーケンス、CSS の色名)。色を付けないマスは `None`
+    fn cell_style(&self, y: usize, x: usize) -> (char, Option<(&'static str, &'static str)>) {
+        if self.character.y == y && self.character.x == x {
+            ('@', Some(("\x1b[92m", "limegreen")))
+        } else if self.walls[y][x] {
+            ('#', None)
+        } else {
+            match self.points[y][x] {
+                point @ 1..=3 => (char::from(b'0' + point as u8), Some(("\x1b[33m", "gold"))),
+                point @ 4..=6 => (char::from(b'0' + point as u8), Some(("\x1b[38;5;208m", "darkorange"))),
+                point @ 7..=9 => (char::from(b'0' + point as u8), Some(("\x1b[31m", "red"))),
+                _ => ('.', Some(("\x1b[90m", "dimgray"))),
+            }
+        }
+    }
+
+    /// ANSI エスケープシーケンスで色を付けた盤面 (環境変数 `NO_COLOR` が設定されていれば色を付けない)
+    pub fn display_ansi(&self) -> String {
+        let no_color = no_color();
+        let mut s = format!("turn:\t{}\nscore:\t{}\n", self.turn, self.game_score);
+        for y in 0..self.height {
+            for x in 0..self.width {
+                match self.cell_style(y, x) {
+                    (c, Some((ansi, _))) if !no_color => {
+                        s.push_str(ansi);
+                        s.push(c);
+                        s.push_str("\x1b[0m");
+                    }
+                    (c, _) => s.push(c),
+                }
+            }
+            s.push('\n');
+        }
+        s
+    }
+
+    /// Web ページに埋め込むための `<pre>` 要素 (環境変数 `NO_COLOR` が設定されていれば色を付けない)
+    pub fn display_html(&self) -> String {
+        let no_color = no_color();
+        let mut s = format!("<pre class=\"maze\">turn:\t{}\nscore:\t{}\n", self.turn, self.game_score);
+        for y in 0..self.height {
+            for x in 0..self.width {
+                match self.cell_style(y, x) {
+                    (c, Some((_, color))) if !no_color => {
+                        s.push_str(&format!("<span style=\"color: {color}\">{c}</span>"));
+                    }
+                    (c, _) => s.push(c),
+                }
+            }
+            s.push('\n');
+        }
+        s.push_str("</pre>");
+        s
+    }
+}
+
+impl SingleGameState for MazeState {
+    type Action = Action;
+    type ScoreType = ScoreType;
+
+    /// 終了ターンに達するか、動ける方向がなくなれば終わり
+    fn is_done(&self) -> bool {
+        self.turn >= self.end_turn || self.is_dead_end()
+    }
+
+    fn is_dead_end(&self) -> bool {
+        self.legal_actions_mask() == 0
+    }
+
+    fn advance(&mut self, action: Action) {
+        let (dx, dy) = action.delta();
+        self.character.x = (self.character.x as i32 + dx) as usize;
+        self.character.y = (self.character.y as i32 + dy) as usize;
+        debug_assert!(!self.walls[self.character.y][self.character.x], "moved into a wall");
+        if self.points[self.character.y][self.character.x] > 0 {
+            self.game_score += self.points[self.character.y][self.character.x];
+            self.points[self.character.y][self.character.x] = 0;
+        }
+        self.turn += self.movement_costs[self.character.y][self.character.x] as i32;
+        if self.visit() {
+            self.revisit_count += 1;
+        }
+    }
+
+    fn evaluate_score(&mut self) {
+        self.evaluated_score = self.normalization_max.map_or(self.game_score as ScoreType, |normalization_max| self.normalized_score(normalization_max));
+        self.evaluated_score -= self.revisit_penalty * self.revisit_count as ScoreType;
+        if self.distance_weight > 0.0 {
+            self.evaluated_score *= DISTANCE_SCALE;
+            if let Some(distance) = self.nearest_point_distance() {
+                self.evaluated_score += (self.distance_weight * DISTANCE_SCALE as f64 / distance as f64) as ScoreType;
+            }
+        }
+    }
+
+    fn evaluated_score(&self) -> ScoreType {
+        self.evaluated_score
+    }
+
+    fn legal_actions(&self) -> Vec<Action> {
+        actions_from_mask(self.legal_actions_mask()).collect()
+    }
+
+    fn first_action(&self) -> Option<Action> {
+        self.first_action
+    }
+
+    fn set_first_action(&mut self, action: Action) {
+        self.first_action = Some(action);
+    }
+}
+
+impl Eq for MazeState {}
+
+impl PartialEq<Self> for MazeState {
+    fn eq(&self, other: &Self) -> bool {
+        self.evaluated_score.eq(&other.evaluated_score)
+    }
+}
+
+impl PartialOrd<Self> for MazeState {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for MazeState {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.evaluated_score.cmp(&other.evaluated_score)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    MissingField(&'static str),
+    InvalidNumber(&'static str, String),
+    TrailingField(String),
+    InvalidCell(char),
+    RaggedRows,
+    CharacterCount(usize),
+    TurnOutOfRange(i32, i32),
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseError::MissingField(name) => write!(f, "missing {name}"),
+            ParseError::InvalidNumber(name, value) => write!(f, "invalid {name}: {value}"),
+            ParseError::TrailingField(field) => write!(f, "unexpected field: {field}"),
+            ParseError::InvalidCell(c) => write!(f, "invalid cell: {c}"),
+            ParseError::RaggedRows => write!(f, "rows must be non-empty and of equal length"),
+            ParseError::CharacterCount(count) => write!(f, "expected exactly one character, found {count}"),
+            ParseError::TurnOutOfRange(turn, end_turn) => write!(f, "turn {turn} must be within 0..={end_turn}"),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// https://no-color.org/ に従い、`NO_COLOR` が空でない値で設定されていれば色付けしない
+fn no_color() -> bool {
+    std::env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty())
+}
+
+/// 盤面だけを表示する (ターン数と得点も見るときは `Debug` を使う)
+impl Display for MazeState {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let s = (0..self.height).map(|h| {
+            (0..self.width).map(|w| {
+                if self.character.y == h && self.character.x == w {
+                    "@"
+                } else if self.walls[h][w] {
+                    "#"
+                } else if self.points[h][w] > 0 {
+                    ["0", "1", "2", "3", "4", "5", "6", "7", "8", "9"][self.points[h][w] as usize]
+                } else {
+                    "."
+                }
+            }).collect::<Vec<_>>().join("")
+        }).collect::<Vec<_>>().join("\n");
+        write!(f, "{s}")
+    }
+}
+
+impl Debug for MazeState {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "turn:\t{}\nscore:\t{}\n{}", self.turn, self.game_score, self)
+    }
+}
+
+/// `legal_actions_mask` で立っているビットの行動を `Action::ALL` の順に返す
+pub fn actions_from_mask(mask: u8) -> impl Iterator<Item = Action> {
+    Action::ALL.into_iter().enumerate().filter(move |&(i, _)| mask & (1 << i) != 0).map(|(_, action)| action)
+}
+
+/// これまでの得点だけを評価値にする (`evaluate_score` の既定と同じ)
+pub fn heuristic_game_score(state: &MazeState) -> ScoreType {
+    state.game_score as ScoreType
+}
+
+/// これまでの得点に、次の 1 手で移動できるマスの得点の合計を加える
+pub fn heuristic_lookahead_sum(state: &MazeState) -> ScoreType {
+    let neighbor_point_sum: i32 = actions_from_mask(state.legal_actions_mask())
+        .map(|action| {
+            let (dx, dy) = action.delta();
+            state.points[(state.character.y as i32 + dy) as usize][(state.character.x as i32 + dx) as usize]
+        })
+        .sum();
+    (state.game_score + neighbor_point_sum) as ScoreType
+}
+
+/// これまでの得点に、残りのターン数で届く距離 (斜めに動けるならチェビシェフ距離、そうでなければマンハッタン距離。壁は無視する) にあるマスの得点の合計を加える
+pub fn heuristic_reachable_points(state: &MazeState) -> ScoreType {
+    let remaining_turn = (state.end_turn - state.turn) as usize;
+    let mut reachable_point_sum = 0;
+    for (y, row) in state.points.iter().enumerate() {
+        for (x, &point) in row.iter().enumerate() {
+            if point == 0 { continue; }
+            let coord = Coord { x, y };
+            let distance = if state.allow_diagonal {
+                state.character.chebyshev_distance(&coord)
+            } else {
+                state.character.manhattan_distance(&coord)
+            };
+            if distance <= remaining_turn {
+                reachable_point_sum += point;
+            }
+        }
+    }
+    (state.game_score + reachable_point_sum) as ScoreType
+}
+
+/// 残りのターンで得られる得点の最大値 (残っている得点の大きい方から残りターン数分の和)
+pub fn theoretical_max(state: &MazeState) -> ScoreType {
+    state.remaining_score_upper_bound() as ScoreType
+}
+
+#[cfg(feature = "serde")]
+pub fn save(state: &MazeState, path: &std::path::Path) -> std::io::Result<()> {
+    let file = std::io::BufWriter::new(std::fs::File::create(path)?);
+    serde_json::to_writer(file, state)?;
+    Ok(())
+}
+
+#[cfg(feature = "serde")]
+pub fn load(path: &std::path::Path) -> std::io::Result<MazeState> {
+    let file = std::io::BufReader::new(std::fs::File::open(path)?);
+    Ok(serde_json::from_reader(file)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+    use std::hash::{BuildHasher, RandomState};
+    use rand::rngs::SmallRng;
+    use rand::{Rng, RngCore, SeedableRng};
+    use crate::SingleGameState;
+    use crate::search::beam::beam_search_action;
+    use crate::search::random::random_action;
+    use super::*;
+
+    /// どのシードでも、生成した全てのマスの得点が 0..=9 に収まり、キャラクターのいるマスは 0 点になる
+    #[test]
+    fn generated_points_are_in_range() {
+        let params = MazeParams { h: 7, w: 9, end_turn: 10 };
+        for seed in (0..1000).chain([u64::MAX - 1]) {
+            let state = MazeState::new(seed, params);
+            assert!(state.points.iter().flatten().all(|point| (0..=9).contains(point)), "seed {seed}");
+            assert_eq!(state.point_at(state.character()), 0, "seed {seed}");
+        }
+    }
+
+    /// 得点のあるマスが 1 つだけの盤面では、距離の重みを付けるとそのマスに近いキャラクターほど評価値が高い
+    #[test]
+    fn distance_weight_prefers_closer_character() {
+        let evaluated_score = |board: &str| {
+            let mut state = MazeState::from_board_string(board).unwrap();
+            state.set_distance_weight(2.0);
+            state.evaluate_score();
+            state.evaluated_score()
+        };
+        let near = evaluated_score("5 0 0 ...@9");
+        let far = evaluated_score("5 0 0 @...9");
+        assert!(near > far, "{near} <= {far}");
+        assert_eq!((near, far), (2 * DISTANCE_SCALE, 2 * DISTANCE_SCALE / 4));
+    }
+
+    /// 手で作った盤面で数手進めると、ターン・キャラクターの位置・得点・各マスの得点が期待どおりになる
+    #[test]
+    fn accessors_follow_advance() {
+        let mut state = MazeState::from_points(vec![vec![0, 3, 0], vec![0, 5, 7]], Coord { x: 0, y: 0 }, 4);
+        assert_eq!((state.turn(), state.character(), state.game_score()), (0, &Coord { x: 0, y: 0 }, 0));
+        state.advance(Action::Right);
+        assert_eq!((state.turn(), state.character(), state.game_score()), (1, &Coord { x: 1, y: 0 }, 3));
+        let points = [[0, 0, 0], [0, 5, 7]];
+        for (y, row) in points.iter().enumerate() {
+            for (x, &point) in row.iter().enumerate() {
+                assert_eq!(state.point_at(&Coord { x, y }), point);
+            }
+        }
+        state.advance(Action::Down);
+        state.advance(Action::Right);
+        assert_eq!((state.turn(), state.character(), state.game_score()), (3, &Coord { x: 2, y: 1 }, 15));
+        assert_eq!(state.end_turn(), 4);
+        state.advance(Action::Left);
+        assert_eq!((state.turn(), state.character(), state.game_score()), (4, &Coord { x: 1, y: 1 }, 15));
+    }
+
+    /// 50x50 で 200 ターンの盤面でも、合法手で進む限りキャラクターは盤面の中にいて、ゲームは 200 ターンで終わる
+    #[test]
+    fn large_board_stays_in_bounds() {
+        let params = MazeParams { h: 50, w: 50, end_turn: 200 };
+        let mut rng = SmallRng::seed_from_u64(0);
+        for seed in 0..5 {
+            let mut state = MazeState::new(seed, params);
+            let mut move_number = 0;
+            while !state.is_done() {
+                let legal_actions = state.legal_actions();
+                assert!(!legal_actions.is_empty());
+                state.advance(legal_actions[rng.gen_range(0..legal_actions.len())]);
+                assert!(state.character().x < params.w && state.character().y < params.h);
+                move_number += 1;
+            }
+            assert_eq!((move_number, state.turn()), (200, 200));
+        }
+    }
+
+    /// 得点が同じなら、得点のあるマスに近づいた状態のほうが遠ざかった状態より整形後の評価値が高い
+    #[test]
+    fn potential_shaping_prefers_approach() {
+        let prev = MazeState::from_points(vec![vec![0, 0, 0, 0, 9]], Coord { x: 2, y: 0 }, 5);
+        let shaped_score = |action: Action| {
+            let mut state = prev.clone();
+            state.advance(action);
+            state.set_evaluated_score_with_potential(&prev);
+            state.evaluated_score()
+        };
+        assert!(shaped_score(Action::Right) > shaped_score(Action::Left));
+    }
+
+    /// 中身が同じ盤面は評価値によらず同じキー・同じハッシュ値に、得点が 1 マス違う盤面は違うキー・違うハッシュ値になる
+    #[test]
+    fn board_key_follows_contents() {
+        let hasher = RandomState::new();
+        let points = vec![vec![0, 3, 5], vec![1, 0, 2]];
+        let state = MazeState::from_points(points.clone(), Coord { x: 0, y: 0 }, 4);
+        let mut same_state = MazeState::from_points(points.clone(), Coord { x: 0, y: 0 }, 4);
+        same_state.evaluated_score = 100;
+        let mut other_points = points;
+        other_points[1][2] = 3;
+        let other_state = MazeState::from_points(other_points, Coord { x: 0, y: 0 }, 4);
+        assert_eq!(state.board_key(), same_state.board_key());
+        assert_eq!(hasher.hash_one(state.board_key()), hasher.hash_one(same_state.board_key()));
+        assert_ne!(state.board_key(), other_state.board_key());
+        assert_ne!(hasher.hash_one(state.board_key()), hasher.hash_one(other_state.board_key()));
+    }
+
+    /// 得点の配置とキャラクターの位置が同じでもターンが違えば、`board_key` と同じく Zobrist ハッシュも違う値になる
+    #[test]
+    fn zobrist_hash_includes_turn() {
+        let state = MazeState::from_board_string("4 1 0 @12").unwrap();
+        let later_state = MazeState::from_board_string("4 2 0 @12").unwrap();
+        assert_ne!(state.board_key(), later_state.board_key());
+        assert_ne!(state.zobrist_hash(), later_state.zobrist_hash());
+        let table = ZobristTable::new(1, 3, 4, 0);
+        assert_ne!(state.hash(&table), later_state.hash(&table));
+        assert_eq!(state.hash(&table), MazeState::from_board_string("4 1 0 @12").unwrap().hash(&table));
+    }
+
+    /// 残り 2 ターンなら、マンハッタン距離 2 以内の 3 点と 5 点は数え、距離 3 の 2 点は数えない
+    #[test]
+    fn heuristic_counts_reachable_points() {
+        assert_eq!(Coord { x: 0, y: 0 }.manhattan_distance(&Coord { x: 2, y: 1 }), 3);
+        assert_eq!(Coord { x: 0, y: 0 }.chebyshev_distance(&Coord { x: 2, y: 1 }), 2);
+        let mut state = MazeState::from_points(vec![vec![0, 3, 5], vec![0, 0, 2]], Coord { x: 0, y: 0 }, 2);
+        state.heuristic_evaluate_score();
+        assert_eq!(state.evaluated_score(), 8);
+    }
+
+    /// 斜めにも動ける 3x3 の盤面で、四隅では盤面の外に出る行動を除いた 3 方向だけ、辺の中央では 5 方向、中央では 8 方向に動ける
+    #[test]
+    fn diagonal_legal_actions() {
+        let legal_actions = |x: usize, y: usize| {
+            let mut state = MazeState::from_points(vec![vec![0; 3]; 3], Coord { x, y }, 1);
+            state.set_allow_diagonal(true);
+            state.legal_actions()
+        };
+        let corners = [
+            (0, 0, [Action::Right, Action::Down, Action::DownRight]),
+            (2, 0, [Action::Left, Action::Down, Action::DownLeft]),
+            (0, 2, [Action::Right, Action::Up, Action::UpRight]),
+            (2, 2, [Action::Left, Action::Up, Action::UpLeft]),
+        ];
+        for (x, y, expected) in corners {
+            let actions = legal_actions(x, y);
+            assert_eq!(actions.len(), 3, "corner ({x}, {y})");
+            assert!(expected.iter().all(|action| actions.contains(action)), "corner ({x}, {y}): {actions:?}");
+        }
+        assert_eq!(legal_actions(1, 0).len(), 5);
+        assert_eq!(legal_actions(1, 1).len(), 8);
+    }
+
+    /// 左の 5 点のマスが沼 (移動コスト 2) なら、移動コストがなければ 9 点取れる左ではなく 6 点取れる右に進み、
+    /// 道 (移動コスト 0) を通れば 1 ターンで 3 マス先の得点を取れる
+    #[test]
+    fn movement_costs() {
+        let play = |mut state: MazeState| {
+            while !state.is_done() {
+                state.advance(beam_search_action(&state, 5, 2));
+            }
+            state.game_score()
+        };
+        let points = vec![vec![4, 5, 0, 5, 1]];
+        assert_eq!(play(MazeState::from_points(points.clone(), Coord { x: 2, y: 0 }, 2)), 9);
+        let mut swamp_state = MazeState::from_points(points, Coord { x: 2, y: 0 }, 2);
+        swamp_state.set_movement_costs(vec![vec![1, 2, 1, 1, 1]]);
+        assert_eq!(beam_search_action(&swamp_state, 5, 2), Action::Right);
+        assert_eq!(play(swamp_state), 6);
+        let mut road_state = MazeState::from_points(vec![vec![0, 0, 0, 5]], Coord { x: 0, y: 0 }, 1);
+        road_state.set_movement_costs(vec![vec![1, 0, 0, 1]]);
+        assert_eq!(play(road_state), 5);
+    }
+
+    /// 3 割のマスを沼にした盤面では、沼に入ると残りのターンが 1 つ余分に減るので、ビームサーチはランダムより沼に入る割合が低い
+    #[test]
+    fn beam_avoids_swamps_more_than_random() {
+        let params = MazeParams { h: 10, w: 10, end_turn: 30 };
+        let swamp_rate = |ai: &mut dyn FnMut(&MazeState) -> Action| {
+            let mut rng_for_construct = SmallRng::seed_from_u64(0);
+            let (mut swamp_number, mut move_number) = (0, 0);
+            for _ in 0..10 {
+                let mut state = MazeState::new(rng_for_construct.next_u64(), params);
+                let movement_costs = (0..params.h).map(|_| {
+                    (0..params.w).map(|_| if rng_for_construct.gen::<f64>() < 0.3 { 2 } else { 1 }).collect()
+                }).collect();
+                state.set_movement_costs(movement_costs);
+                while !state.is_done() {
+                    state.advance(ai(&state));
+                    if state.movement_cost_at(state.character()) == 2 {
+                        swamp_number += 1;
+                    }
+                    move_number += 1;
+                }
+            }
+            swamp_number as f64 / move_number as f64
+        };
+        let mut rng_for_action = SmallRng::seed_from_u64(0);
+        let random_swamp_rate = swamp_rate(&mut |state| random_action(state, &mut rng_for_action));
+        let beam_swamp_rate = swamp_rate(&mut |state| beam_search_action(state, 5, params.end_turn as usize));
+        assert!(beam_swamp_rate < random_swamp_rate, "{beam_swamp_rate} >= {random_swamp_rate}");
+    }
+
+    /// 得点が同じ 0 点でも、来たマスに戻った状態のほうが戻らなかった状態より評価値が低く、`undo` で戻した回数も元に戻る
+    #[test]
+    fn revisit_penalty() {
+        let mut initial_state = MazeState::from_points(vec![vec![0, 0, 0, 0]], Coord { x: 1, y: 0 }, 2);
+        initial_state.set_revisit_penalty(1);
+        let play = |actions: [Action; 2]| {
+            let mut state = initial_state.clone();
+            for action in actions {
+                state.advance(action);
+            }
+            state.evaluate_score();
+            state
+        };
+        let (forward, back) = (play([Action::Right, Action::Right]), play([Action::Left, Action::Right]));
+        assert_eq!((forward.game_score(), back.game_score()), (0, 0));
+        assert_eq!((forward.revisit_count(), back.revisit_count()), (0, 1));
+        assert!(forward.evaluated_score() > back.evaluated_score());
+        let mut state = back.clone();
+        let record = state.advance_with_undo(Action::Left);
+        assert_eq!(state.revisit_count(), 2);
+        state.undo(&record);
+        assert_eq!(state.revisit_count(), 1);
+    }
+
+    /// `advance_with_undo` の後に評価値や最初の行動を書き換えても、`undo` で全てのフィールドが行動する前と一致する
+    ///
+    /// `==` は評価値しか比べないので、フィールドごとに比べる。
+    #[test]
+    fn undo_restores_every_field() {
+        let assert_same = |state: &MazeState, expected: &MazeState| {
+            assert_eq!((state.height, state.width, state.end_turn), (expected.height, expected.width, expected.end_turn));
+            assert_eq!(state.points, expected.points);
+            assert_eq!(state.walls, expected.walls);
+            assert_eq!(state.movement_costs, expected.movement_costs);
+            assert_eq!((state.turn, &state.character, state.game_score), (expected.turn, &expected.character, expected.game_score));
+            assert_eq!((state.evaluated_score, state.first_action), (expected.evaluated_score, expected.first_action));
+            assert_eq!((&state.visited, state.revisit_count), (&expected.visited, expected.revisit_count));
+        };
+        let mut rng = SmallRng::seed_from_u64(0);
+        for seed in 0..20 {
+            let mut state = MazeState::new(seed, MazeParams { h: 4, w: 5, end_turn: 12 });
+            state.set_revisit_penalty(1);
+            while !state.is_done() {
+                let before = state.clone();
+                let legal_actions = state.legal_actions();
+                let action = legal_actions[rng.gen_range(0..legal_actions.len())];
+                let record = state.advance_with_undo(action);
+                state.evaluate_score();
+                state.set_first_action(action);
+                state.undo(&record);
+                assert_same(&state, &before);
+                state.advance(action);
+                state.evaluate_score();
+            }
+        }
+    }
+
+    /// 上下左右だけの盤面でも斜めにも動ける盤面でも、盤面内の全ての位置 (四隅と辺を含む) でビットマスクと `legal_actions` が一致する
+    #[test]
+    fn legal_actions_mask_matches_legal_actions() {
+        let (height, width) = (3, 4);
+        for allow_diagonal in [false, true] {
+            for y in 0..height {
+                for x in 0..width {
+                    let mut state = MazeState::from_points(vec![vec![0; width]; height], Coord { x, y }, 1);
+                    state.set_allow_diagonal(allow_diagonal);
+                    let mask = state.legal_actions_mask();
+                    assert_eq!(actions_from_mask(mask).collect::<Vec<_>>(), state.legal_actions());
+                    if !allow_diagonal {
+                        assert_eq!(mask >> 4, 0);
+                    }
+                }
+            }
+        }
+    }
+
+    /// 右と下が x と y の正の向きで、斜めの行動はその 2 方向を合わせた向きに 1 マスずつ動く
+    #[test]
+    fn action_delta_directions() {
+        assert_eq!(Action::Right.delta(), (1, 0));
+        assert_eq!(Action::Left.delta(), (-1, 0));
+        assert_eq!(Action::Down.delta(), (0, 1));
+        assert_eq!(Action::Up.delta(), (0, -1));
+        assert_eq!(Action::DownRight.delta(), (1, 1));
+        assert_eq!(Action::UpRight.delta(), (1, -1));
+        assert_eq!(Action::DownLeft.delta(), (-1, 1));
+        assert_eq!(Action::UpLeft.delta(), (-1, -1));
+        let deltas = Action::ALL.iter().map(Action::delta).collect::<HashSet<_>>();
+        assert_eq!(deltas.len(), Action::ALL.len());
+    }
+
+    /// 特徴量ベクトルの長さと、得点・キャラクターの位置・ターンがそれぞれ説明どおりの場所に入っている
+    #[test]
+    fn feature_vector_layout() {
+        let (height, width) = (2, 3);
+        let mut state = MazeState::from_points(vec![vec![0, 9, 3], vec![0, 0, 0]], Coord { x: 0, y: 0 }, 4);
+        let features = state.to_feature_vector();
+        assert_eq!(features.len(), 2 * height * width + 1);
+        assert_eq!(features[1], 1.0);
+        assert_eq!(features[2], 3.0 / 9.0);
+        assert_eq!(features[height * width..2 * height * width], [1.0, 0.0, 0.0, 0.0, 0.0, 0.0]);
+        assert_eq!(features[2 * height * width], 0.0);
+        state.advance(Action::Right);
+        let features = state.to_feature_vector();
+        assert_eq!(features[1], 0.0);
+        assert_eq!(features[height * width + 1], 1.0);
+        assert_eq!(features.iter().skip(height * width).take(height * width).sum::<f32>(), 1.0);
+        assert_eq!(features[2 * height * width], 0.25);
+    }
+
+    /// 終了ターンが 0 の盤面でも、ターンの特徴量は NaN にならず 1.0 になる
+    #[test]
+    fn feature_vector_with_zero_end_turn() {
+        let state = MazeState::from_points(vec![vec![0, 9, 3]], Coord { x: 0, y: 0 }, 0);
+        assert!(state.is_done());
+        let features = state.to_feature_vector();
+        assert_eq!(features[2 * 3], 1.0);
+        assert!(features.iter().all(|feature| feature.is_finite()));
+    }
+
+    /// 同じシードで作った乱数生成器からは同じ盤面ができ、同じ乱数生成器から続けて作った盤面は別の盤面になる
+    #[test]
+    fn with_rng_is_deterministic() {
+        let params = MazeParams { h: 5, w: 6, end_turn: 8 };
+        let mut rng = SmallRng::seed_from_u64(42);
+        let mut same_rng = SmallRng::seed_from_u64(42);
+        let state = MazeState::with_rng(&mut rng, params);
+        let same_state = MazeState::with_rng(&mut same_rng, params);
+        assert_eq!(state.character(), same_state.character());
+        assert_eq!(state.to_string(), same_state.to_string());
+        let next_state = MazeState::with_rng(&mut rng, params);
+        assert_ne!(state.to_string(), next_state.to_string());
+    }
+
+    /// ランダムな大きさ、壁、途中のターンの盤面で `from_board_string(to_board_string(s))` が元の盤面に戻る
+    #[test]
+    fn board_string_round_trip() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        for _ in 0..200 {
+            let h = 1 + rng.next_u32() as usize % 10;
+            let w = 1 + rng.next_u32() as usize % 10;
+            let end_turn = 1 + (rng.next_u32() % 20) as i32;
+            let wall_density = (rng.next_u32() % 4) as f64 / 10.0;
+            let mut state = MazeState::new_with_walls(rng.next_u64(), MazeParams { h, w, end_turn }, wall_density);
+            let steps = rng.next_u32() % (end_turn as u32 + 1);
+            for _ in 0..steps {
+                if state.legal_actions().is_empty() { break; }
+                state.advance(random_action(&state, &mut rng));
+            }
+            let board_string = state.to_board_string();
+            let restored = MazeState::from_board_string(&board_string).unwrap();
+            assert_eq!(restored.to_board_string(), board_string);
+            assert_eq!(format!("{restored:?}"), format!("{state:?}"));
+            assert_eq!(restored.zobrist_hash(), state.zobrist_hash());
+            assert_eq!(restored.is_done(), state.is_done());
+            assert_eq!(restored.legal_actions(), state.legal_actions());
+        }
+    }
+
+    /// 形の崩れた文字列や範囲外のターン数はエラーになる
+    #[test]
+    fn board_string_rejects_invalid_input() {
+        let cases = [
+            ("4 0 0 91#6/27a3/@489", ParseError::InvalidCell('a')),
+            ("4 0 0 913/2763/@489", ParseError::RaggedRows),
+            ("4 0 0 9136/2763/0489", ParseError::CharacterCount(0)),
+            ("4 x 0 9136/2763/@489", ParseError::InvalidNumber("turn", "x".to_string())),
+            ("4 0 0", ParseError::MissingField("board")),
+            ("4 0 0 @1 2", ParseError::TrailingField("2".to_string())),
+            ("4 -1 0 @1", ParseError::TurnOutOfRange(-1, 4)),
+            ("4 5 0 @1", ParseError::TurnOutOfRange(5, 4)),
+            ("-1 0 0 @1", ParseError::TurnOutOfRange(0, -1)),
+        ];
+        for (s, expected) in cases {
+            assert_eq!(MazeState::from_board_string(s).unwrap_err(), expected, "{s}");
+        }
+        assert!(MazeState::from_board_string("4 4 0 @1").unwrap().is_done());
+    }
+
+    /// `Display` は `Debug` からターン数と得点の 2 行を除いた盤面だけになる
+    #[test]
+    fn display_has_no_header() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        for _ in 0..100 {
+            let h = 1 + rng.next_u32() as usize % 10;
+            let w = 1 + rng.next_u32() as usize % 10;
+            let state = MazeState::new(rng.next_u64(), MazeParams { h, w, end_turn: 4 });
+            let display = format!("{state}");
+            let debug = format!("{state:?}");
+            assert_eq!(display.lines().count(), h);
+            assert_eq!(debug.lines().count(), display.lines().count() + 2);
+            assert!(debug.ends_with(&format!("{display}\n")));
+        }
+    }
+
+    /// 大きな盤面で、`Sparse { density: 0.1, .. }` の得点のあるマスはおよそ 1 割になり、正規分布の平均はおよそ `mean` になる
+    #[test]
+    fn point_distributions_match_parameters() {
+        let params = MazeParams { h: 200, w: 200, end_turn: 1 };
+        let points = |distribution| {
+            let state = MazeState::new_with_distribution(0, params, distribution);
+            state.points.into_iter().flatten().collect::<Vec<_>>()
+        };
+        let sparse_points = points(PointDistribution::Sparse { density: 0.1, max: 9 });
+        assert!(sparse_points.iter().all(|point| (0..=9).contains(point)));
+        let nonzero_ratio = sparse_points.iter().filter(|&&point| point > 0).count() as f64 / sparse_points.len() as f64;
+        assert!((0.09..=0.11).contains(&nonzero_ratio), "{nonzero_ratio}");
+        let gaussian_points = points(PointDistribution::Gaussian { mean: 4.5, std: 1.5 });
+        assert!(gaussian_points.iter().all(|point| (0..=9).contains(point)));
+        let mean = gaussian_points.iter().sum::<i32>() as f64 / gaussian_points.len() as f64;
+        assert!((mean - 4.5).abs() < 0.1, "{mean}");
+    }
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod serde_tests {
+    use crate::SingleGameState;
+    use super::*;
+
+    /// 一時ファイルに保存して読み戻すと、盤面・合法手・得点が元の状態と一致する
+    #[test]
+    fn save_load_round_trip() {
+        let mut state = MazeState::new(0, MazeParams { h: 5, w: 6, end_turn: 10 });
+        state.advance(state.legal_actions()[0]);
+        let path = std::env::temp_dir().join(format!("maze_state_{}.json", std::process::id()));
+        save(&state, &path).unwrap();
+        let loaded = load(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(loaded.to_board_string(), state.to_board_string());
+        assert_eq!(loaded.legal_actions(), state.legal_actions());
+        assert_eq!(loaded.game_score(), state.game_score());
+    }
+}
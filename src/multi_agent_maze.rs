@@ -0,0 +1,161 @@
+use std::cmp::Ordering;
+use std::fmt::{Debug, Formatter};
+use rand::rngs::SmallRng;
+use rand::{RngCore, SeedableRng};
+use crate::SingleGameState;
+use crate::maze::{Action, Coord, MazeParams, MazeState, ScoreType};
+
+/// 2 人のキャラクターが毎ターン同時に動き、得点を共有する迷路
+#[derive(Clone)]
+pub struct MultiAgentMazeState {
+    height: usize,
+    width: usize,
+    end_turn: i32,
+    points: Vec<Vec<i32>>,
+    turn: i32,
+    characters: [Coord; 2],
+    pub game_score: i32,
+    evaluated_score: ScoreType,
+    first_action: Option<(Action, Action)>,
+}
+
+impl MultiAgentMazeState {
+    pub fn new(seed: u64, params: MazeParams) -> Self {
+        let MazeParams { h: height, w: width, end_turn } = params;
+        let mut rng_for_construct = if seed < u64::MAX {
+            SmallRng::seed_from_u64(seed)
+        } else {
+            SmallRng::from_entropy()
+        };
+        let characters: [Coord; 2] = std::array::from_fn(|_| {
+            let y = rng_for_construct.next_u32() as usize % height;
+            let x = rng_for_construct.next_u32() as usize % width;
+            Coord { x, y }
+        });
+        let mut points = vec![vec![0; width]; height];
+        for (y, row) in points.iter_mut().enumerate() {
+            for (x, point) in row.iter_mut().enumerate() {
+                if characters.iter().any(|character| character.y == y && character.x == x) { continue; }
+                *point = (rng_for_construct.next_u32() % 10) as i32;
+            }
+        }
+        Self {
+            height,
+            width,
+            end_turn,
+            points,
+            turn: 0,
+            characters,
+            game_score: 0,
+            evaluated_score: 0,
+            first_action: None,
+        }
+    }
+
+    fn character_legal_actions(&self, character_id: usize) -> Vec<Action> {
+        let character = &self.characters[character_id];
+        let mut actions = Vec::new();
+        for &action in &Action::ALL[..4] {
+            let (dx, dy) = action.delta();
+            let ty = character.y as i32 + dy;
+            let tx = character.x as i32 + dx;
+            if ty >= 0 && ty < self.height as i32 && tx >= 0 && tx < self.width as i32 {
+                actions.push(action);
+            }
+        }
+        actions
+    }
+
+    /// 相方を無視して `character_id` のキャラクターだけが動く一人ゲームとして見た盤面
+    pub fn single_agent_view(&self, character_id: usize) -> MazeState {
+        let params = MazeParams { h: self.height, w: self.width, end_turn: self.end_turn };
+        MazeState::from_parts(params, self.points.clone(), self.turn, self.characters[character_id].clone())
+    }
+}
+
+impl SingleGameState for MultiAgentMazeState {
+    type Action = (Action, Action);
+    type ScoreType = ScoreType;
+
+    fn is_done(&self) -> bool {
+        self.turn == self.end_turn
+    }
+
+    /// 2 人が同じマスに入ったときは得点を 1 回だけ数える
+    fn advance(&mut self, action: (Action, Action)) {
+        for (character, action) in self.characters.iter_mut().zip([action.0, action.1]) {
+            let (dx, dy) = action.delta();
+            character.x = (character.x as i32 + dx) as usize;
+            character.y = (character.y as i32 + dy) as usize;
+            let point = &mut self.points[character.y][character.x];
+            self.game_score += *point;
+            *point = 0;
+        }
+        self.turn += 1;
+    }
+
+    fn legal_actions(&self) -> Vec<(Action, Action)> {
+        let mut actions = Vec::new();
+        for &action0 in &self.character_legal_actions(0) {
+            for &action1 in &self.character_legal_actions(1) {
+                actions.push((action0, action1));
+            }
+        }
+        actions
+    }
+
+    fn evaluate_score(&mut self) {
+        self.evaluated_score = self.game_score as ScoreType
+    }
+
+    fn evaluated_score(&self) -> ScoreType {
+        self.evaluated_score
+    }
+
+    fn first_action(&self) -> Option<(Action, Action)> {
+        self.first_action
+    }
+
+    fn set_first_action(&mut self, action: (Action, Action)) {
+        self.first_action = Some(action);
+    }
+}
+
+impl Eq for MultiAgentMazeState {}
+
+impl PartialEq<Self> for MultiAgentMazeState {
+    fn eq(&self, other: &Self) -> bool {
+        self.evaluated_score.eq(&other.evaluated_score)
+    }
+}
+
+impl PartialOrd<Self> for MultiAgentMazeState {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for MultiAgentMazeState {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.evaluated_score.cmp(&other.evaluated_score)
+    }
+}
+
+impl Debug for MultiAgentMazeState {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let s = (0..self.height).map(|h| {
+            (0..self.width).map(|w| {
+                if self.characters[0].y == h && self.characters[0].x == w {
+                    "A"
+                } else if self.characters[1].y == h && self.characters[1].x == w {
+                    "B"
+                } else if self.points[h][w] > 0 {
+                    ["0", "1", "2", "3", "4", "5", "6", "7", "8", "9"][self.points[h][w] as usize]
+                } else {
+                    "."
+                }
+            }).collect::<Vec<_>>().join("")
+        }).collect::<Vec<_>>().join("\n");
+        writeln!(f, "turn:\t{}\nscore:\t{}\n{}", self.turn, self.game_score, s)
+    }
+}
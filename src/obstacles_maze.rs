@@ -0,0 +1,270 @@
+use std::cmp::Ordering;
+use std::fmt::{Debug, Formatter};
+use std::rc::Rc;
+use rand::rngs::SmallRng;
+use rand::{RngCore, SeedableRng};
+use crate::SingleGameState;
+use crate::maze::{Action, Coord, MazeParams, ScoreType};
+
+/// 障害物が行き来する区間の長さの上限
+const MAX_PATROL_LENGTH: usize = 5;
+
+/// 毎ターン決まった経路を巡回する障害物がいる迷路
+///
+/// キャラクターが動いた後に障害物が 1 マス進み、障害物と同じマスになったらその時点でゲームが終わる。
+/// 合法手からは障害物が次に来るマスへの移動を除くので、合法手だけを選んでいれば衝突しない。
+#[derive(Clone)]
+pub struct ObstaclesMazeState {
+    height: usize,
+    width: usize,
+    end_turn: i32,
+    points: Vec<Vec<i32>>,
+    turn: i32,
+    character: Coord,
+    /// 障害物ごとの巡回経路 (全ての状態で共有する)
+    paths: Rc<Vec<Vec<Coord>>>,
+    /// 障害物ごとの経路上の現在の位置
+    phases: Vec<usize>,
+    collided: bool,
+    game_score: i32,
+    evaluated_score: ScoreType,
+    first_action: Option<Action>,
+}
+
+impl ObstaclesMazeState {
+    /// 縦か横の一直線の区間を往復する障害物を `obstacle_number` 個置く (キャラクターの初期位置は通らない)
+    pub fn new(seed: u64, params: MazeParams, obstacle_number: usize) -> Self {
+        let MazeParams { h: height, w: width, end_turn } = params;
+        let mut rng_for_construct = if seed < u64::MAX {
+            SmallRng::seed_from_u64(seed)
+        } else {
+            SmallRng::from_entropy()
+        };
+        let y = rng_for_construct.next_u32() as usize % height;
+        let x = rng_for_construct.next_u32() as usize % width;
+        let character = Coord { x, y };
+        let mut points = vec![vec![0; width]; height];
+        for (y, row) in points.iter_mut().enumerate() {
+            for (x, point) in row.iter_mut().enumerate() {
+                if y == character.y && x == character.x { continue; }
+                *point = (rng_for_construct.next_u32() % 10) as i32;
+            }
+        }
+        let mut paths = Vec::new();
+        let mut phases = Vec::new();
+        while paths.len() < obstacle_number {
+            let path = Self::random_patrol(height, width, &mut rng_for_construct);
+            if path.contains(&character) { continue; }
+            phases.push(rng_for_construct.next_u32() as usize % path.len());
+            paths.push(path);
+        }
+        Self {
+            height,
+            width,
+            end_turn,
+            points,
+            turn: 0,
+            character,
+            paths: Rc::new(paths),
+            phases,
+            collided: false,
+            game_score: 0,
+            evaluated_score: 0,
+            first_action: None,
+        }
+    }
+
+    /// 縦か横の区間 `[start, start + length)` を端で折り返して往復する経路
+    fn random_patrol(height: usize, width: usize, rng: &mut SmallRng) -> Vec<Coord> {
+        let vertical = rng.next_u32().is_multiple_of(2);
+        let line_length = if vertical { height } else { width };
+        let length = 2 + rng.next_u32() as usize % (MAX_PATROL_LENGTH - 1);
+        let length = length.min(line_length);
+        let start = rng.next_u32() as usize % (line_length - length + 1);
+        let fixed = rng.next_u32() as usize % if vertical { width } else { height };
+        let coord = |i: usize| if vertical { Coord { x: fixed, y: i } } else { Coord { x: i, y: fixed } };
+        let forward = start..start + length;
+        let backward = (start + 1..start + length - 1).rev();
+        forward.chain(backward).map(coord).collect()
+    }
+
+    pub fn game_score(&self) -> i32 {
+        self.game_score
+    }
+
+    pub fn character(&self) -> &Coord {
+        &self.character
+    }
+
+    /// 障害物とぶつかって終わったか
+    pub fn collided(&self) -> bool {
+        self.collided
+    }
+
+    /// 障害物の現在の位置
+    pub fn obstacles(&self) -> impl Iterator<Item = &Coord> + '_ {
+        self.paths.iter().zip(&self.phases).map(|(path, &phase)| &path[phase])
+    }
+
+    /// 障害物が次のターンにいる位置
+    fn next_obstacles(&self) -> impl Iterator<Item = &Coord> + '_ {
+        self.paths.iter().zip(&self.phases).map(|(path, &phase)| &path[(phase + 1) % path.len()])
+    }
+}
+
+impl SingleGameState for ObstaclesMazeState {
+    type Action = Action;
+    type ScoreType = ScoreType;
+
+    fn is_done(&self) -> bool {
+        self.turn == self.end_turn || self.collided || self.is_dead_end()
+    }
+
+    /// キャラクターを動かしてから障害物を進める
+    fn advance(&mut self, action: Action) {
+        let (dx, dy) = action.delta();
+        self.character.x = (self.character.x as i32 + dx) as usize;
+        self.character.y = (self.character.y as i32 + dy) as usize;
+        if self.points[self.character.y][self.character.x] > 0 {
+            self.game_score += self.points[self.character.y][self.character.x];
+            self.points[self.character.y][self.character.x] = 0;
+        }
+        for (path, phase) in self.paths.iter().zip(self.phases.iter_mut()) {
+            *phase = (*phase + 1) % path.len();
+        }
+        if self.obstacles().any(|obstacle| *obstacle == self.character) {
+            self.collided = true;
+        }
+        self.turn += 1;
+    }
+
+    /// 盤面の外と、障害物が次のターンにいるマスへは動けない
+    fn legal_actions(&self) -> Vec<Action> {
+        let mut actions = Vec::new();
+        for &action in &Action::ALL[..4] {
+            let (dx, dy) = action.delta();
+            let ty = self.character.y as i32 + dy;
+            let tx = self.character.x as i32 + dx;
+            if ty < 0 || ty >= self.height as i32 || tx < 0 || tx >= self.width as i32 { continue; }
+            let target = Coord { x: tx as usize, y: ty as usize };
+            if self.next_obstacles().any(|obstacle| *obstacle == target) { continue; }
+            actions.push(action);
+        }
+        actions
+    }
+
+    fn evaluate_score(&mut self) {
+        self.evaluated_score = self.game_score as ScoreType;
+    }
+
+    fn evaluated_score(&self) -> ScoreType {
+        self.evaluated_score
+    }
+
+    fn first_action(&self) -> Option<Action> {
+        self.first_action
+    }
+
+    fn set_first_action(&mut self, action: Action) {
+        self.first_action = Some(action);
+    }
+}
+
+impl Eq for ObstaclesMazeState {}
+
+impl PartialEq<Self> for ObstaclesMazeState {
+    fn eq(&self, other: &Self) -> bool {
+        self.evaluated_score.eq(&other.evaluated_score)
+    }
+}
+
+impl PartialOrd<Self> for ObstaclesMazeState {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for ObstaclesMazeState {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.evaluated_score.cmp(&other.evaluated_score)
+    }
+}
+
+impl Debug for ObstaclesMazeState {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let s = (0..self.height).map(|h| {
+            (0..self.width).map(|w| {
+                let coord = Coord { x: w, y: h };
+                if self.character == coord {
+                    if self.collided { "*" } else { "@" }
+                } else if self.obstacles().any(|obstacle| *obstacle == coord) {
+                    "X"
+                } else if self.points[h][w] > 0 {
+                    ["0", "1", "2", "3", "4", "5", "6", "7", "8", "9"][self.points[h][w] as usize]
+                } else {
+                    "."
+                }
+            }).collect::<Vec<_>>().join("")
+        }).collect::<Vec<_>>().join("\n");
+        writeln!(f, "turn:\t{}\nscore:\t{}\n{}", self.turn, self.game_score, s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::search::beam::pv_search_action;
+    use crate::search::chokudai::chokudai_search_action;
+    use crate::search::random::random_action;
+    use super::*;
+
+    const PARAMS: MazeParams = MazeParams { h: 30, w: 30, end_turn: 100 };
+    const OBSTACLE_NUMBER: usize = 100;
+
+    /// Chokudai サーチで遊び切っても、読み筋を最後までたどっても、一度も障害物にぶつからない
+    #[test]
+    fn searches_avoid_obstacles() {
+        let mut rng_for_construct = SmallRng::seed_from_u64(0);
+        for _ in 0..3 {
+            let seed = rng_for_construct.next_u64();
+            let mut state = ObstaclesMazeState::new(seed, PARAMS, OBSTACLE_NUMBER);
+            while !state.is_done() {
+                state.advance(chokudai_search_action(&state, 1, PARAMS.end_turn as usize, 5));
+                assert!(!state.collided());
+            }
+            let mut state = ObstaclesMazeState::new(seed, PARAMS, OBSTACLE_NUMBER);
+            for action in pv_search_action(&state, 5, PARAMS.end_turn as usize) {
+                assert!(state.legal_actions().contains(&action));
+                state.advance(action);
+                assert!(!state.collided());
+            }
+        }
+    }
+
+    /// 合法手から除かれた盤面内への移動 (障害物が次に来るマス) を選ぶと、ぶつかってゲームが終わる
+    #[test]
+    fn collision_ends_game() {
+        let mut rng_for_construct = SmallRng::seed_from_u64(0);
+        let mut rng_for_action = SmallRng::seed_from_u64(0);
+        let mut collided_number = 0;
+        for _ in 0..100 {
+            let mut state = ObstaclesMazeState::new(rng_for_construct.next_u64(), PARAMS, OBSTACLE_NUMBER);
+            while !state.is_done() {
+                let legal_actions = state.legal_actions();
+                let blocked_action = Action::ALL[..4].iter().copied().find(|&action| {
+                    let (dx, dy) = action.delta();
+                    let ty = state.character().y as i32 + dy;
+                    let tx = state.character().x as i32 + dx;
+                    ty >= 0 && ty < PARAMS.h as i32 && tx >= 0 && tx < PARAMS.w as i32 && !legal_actions.contains(&action)
+                });
+                if let Some(action) = blocked_action {
+                    state.advance(action);
+                    assert!(state.collided() && state.is_done());
+                    collided_number += 1;
+                    break;
+                }
+                state.advance(random_action(&state, &mut rng_for_action));
+            }
+        }
+        assert!(collided_number > 0);
+    }
+}
@@ -0,0 +1,280 @@
+use std::time::Instant;
+use crate::maze::{Action, ScoreType};
+use crate::alternate_maze::AlternateMazeState;
+
+const INF: ScoreType = 1_000_000_000;
+/// キラームーブを覚えておく根からの手数の上限
+const MAX_DEPTH: usize = 64;
+
+/// 深さごとに、直前に枝刈りを起こした手を 2 つまで覚えておく表
+type KillerTable = [[Option<Action>; MAX_DEPTH]; 2];
+
+/// `deadline` を過ぎたら探索を打ち切って 0 を返す
+fn alpha_beta_score<const H: usize, const W: usize, const END_TURN: i32>(state: &AlternateMazeState<H, W, END_TURN>, mut alpha: ScoreType, beta: ScoreType, depth: usize, deadline: Option<Instant>, node_count: &mut u64) -> ScoreType {
+    if deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+        return 0;
+    }
+    *node_count += 1;
+    if state.is_done() || depth == 0 {
+        return state.teban_evaluation();
+    }
+    let legal_actions = state.legal_actions();
+    if legal_actions.is_empty() {
+        return state.teban_evaluation();
+    }
+    for action in legal_actions {
+        let mut next_state = state.clone();
+        next_state.advance(action);
+        let score = -alpha_beta_score(&next_state, -beta, -alpha, depth - 1, deadline, node_count);
+        if score > alpha {
+            alpha = score;
+        }
+        if alpha >= beta {
+            return alpha;
+        }
+    }
+    alpha
+}
+
+/// 最善手と、`deadline` までに探索を終えられたかどうかを返す (`depth` が 0 なら探索せずに最初の合法手を返す)
+fn alpha_beta_root<const H: usize, const W: usize, const END_TURN: i32>(state: &AlternateMazeState<H, W, END_TURN>, depth: usize, deadline: Option<Instant>, node_count: &mut u64) -> (Action, bool) {
+    if depth == 0 {
+        return (state.legal_actions()[0], true);
+    }
+    let mut best_action = None;
+    let mut alpha = -INF;
+    let beta = INF;
+    for action in state.legal_actions() {
+        let mut next_state = state.clone();
+        next_state.advance(action);
+        let score = -alpha_beta_score(&next_state, -beta, -alpha, depth - 1, deadline, node_count);
+        if deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+            return (best_action.unwrap_or(action), false);
+        }
+        if best_action.is_none() || score > alpha {
+            best_action = Some(action);
+            alpha = score;
+        }
+    }
+    (best_action.unwrap(), true)
+}
+
+/// 探索した節点の数も合わせて返す
+pub fn alpha_beta_action_with_count<const H: usize, const W: usize, const END_TURN: i32>(state: &AlternateMazeState<H, W, END_TURN>, depth: usize) -> (Action, u64) {
+    let mut node_count = 0;
+    let (action, _) = alpha_beta_root(state, depth, None, &mut node_count);
+    (action, node_count)
+}
+
+pub fn alpha_beta_action<const H: usize, const W: usize, const END_TURN: i32>(state: &AlternateMazeState<H, W, END_TURN>, depth: usize) -> Action {
+    alpha_beta_action_with_count(state, depth).0
+}
+
+/// `current_player` から見た評価値を手ごとに符号反転して探索する negamax 形式のアルファ・ベータ法で、窓 `(alpha, beta)` における評価値を返す
+///
+/// `alpha_beta_action` もこの形で探索しているので、`negamax_action` は同じ手を返す。
+pub fn negamax_score<const H: usize, const W: usize, const END_TURN: i32>(state: &AlternateMazeState<H, W, END_TURN>, alpha: ScoreType, beta: ScoreType, depth: usize) -> ScoreType {
+    let mut node_count = 0;
+    alpha_beta_score(state, alpha, beta, depth, None, &mut node_count)
+}
+
+pub fn negamax_action<const H: usize, const W: usize, const END_TURN: i32>(state: &AlternateMazeState<H, W, END_TURN>, depth: usize) -> Action {
+    alpha_beta_action(state, depth)
+}
+
+/// 最善手と、`deadline` までに深さ `depth` の探索を終えられたかどうかを返す
+///
+/// 打ち切られた場合はそれまでに調べた手の中での最善手を返す。
+pub fn alpha_beta_action_with_deadline<const H: usize, const W: usize, const END_TURN: i32>(state: &AlternateMazeState<H, W, END_TURN>, depth: usize, deadline: Instant) -> (Action, bool) {
+    let mut node_count = 0;
+    alpha_beta_root(state, depth, Some(deadline), &mut node_count)
+}
+
+/// 1 手先読みしたときの得点の増え方 (`greedy_action` と同じ基準) が大きい順に並べる (同じなら元の順)
+pub fn order_actions<const H: usize, const W: usize, const END_TURN: i32>(state: &AlternateMazeState<H, W, END_TURN>, actions: Vec<Action>) -> Vec<Action> {
+    let mut scored_actions = actions.into_iter().map(|action| {
+        let mut next_state = state.clone();
+        next_state.advance(action);
+        (-next_state.teban_score(), action)
+    }).collect::<Vec<_>>();
+    scored_actions.sort_by_key(|&(score, _)| std::cmp::Reverse(score));
+    scored_actions.into_iter().map(|(_, action)| action).collect()
+}
+
+/// `order_actions` で並べたうえで、深さ `ply` のキラームーブを先頭に移す
+fn order_actions_with_killers<const H: usize, const W: usize, const END_TURN: i32>(state: &AlternateMazeState<H, W, END_TURN>, ply: usize, killers: &KillerTable) -> Vec<Action> {
+    let mut actions = order_actions(state, state.legal_actions());
+    if ply < MAX_DEPTH {
+        for killer in [killers[1][ply], killers[0][ply]].into_iter().flatten() {
+            if let Some(index) = actions.iter().position(|&action| action == killer) {
+                let action = actions.remove(index);
+                actions.insert(0, action);
+            }
+        }
+    }
+    actions
+}
+
+fn record_killer(killers: &mut KillerTable, ply: usize, action: Action) {
+    if ply < MAX_DEPTH && killers[0][ply] != Some(action) {
+        killers[1][ply] = killers[0][ply];
+        killers[0][ply] = Some(action);
+    }
+}
+
+fn ordered_alpha_beta_score<const H: usize, const W: usize, const END_TURN: i32>(state: &AlternateMazeState<H, W, END_TURN>, mut alpha: ScoreType, beta: ScoreType, depth: usize, ply: usize, killers: &mut KillerTable, node_count: &mut u64) -> ScoreType {
+    *node_count += 1;
+    if state.is_done() || depth == 0 {
+        return state.teban_evaluation();
+    }
+    let legal_actions = order_actions_with_killers(state, ply, killers);
+    if legal_actions.is_empty() {
+        return state.teban_evaluation();
+    }
+    for action in legal_actions {
+        let mut next_state = state.clone();
+        next_state.advance(action);
+        let score = -ordered_alpha_beta_score(&next_state, -beta, -alpha, depth - 1, ply + 1, killers, node_count);
+        if score > alpha {
+            alpha = score;
+        }
+        if alpha >= beta {
+            record_killer(killers, ply, action);
+            return alpha;
+        }
+    }
+    alpha
+}
+
+/// 手の並べ替えとキラームーブで枝刈りを増やしたアルファ・ベータ法で、探索した節点の数も合わせて返す
+///
+/// `depth` が 0 なら探索せずに最初の合法手を返す。
+pub fn ordered_alpha_beta_action_with_count<const H: usize, const W: usize, const END_TURN: i32>(state: &AlternateMazeState<H, W, END_TURN>, depth: usize) -> (Action, u64) {
+    if depth == 0 {
+        return (state.legal_actions()[0], 0);
+    }
+    let mut node_count = 0;
+    let mut killers = [[None; MAX_DEPTH]; 2];
+    let mut best_action = None;
+    let mut alpha = -INF;
+    let beta = INF;
+    for action in order_actions(state, state.legal_actions()) {
+        let mut next_state = state.clone();
+        next_state.advance(action);
+        let score = -ordered_alpha_beta_score(&next_state, -beta, -alpha, depth - 1, 1, &mut killers, &mut node_count);
+        if best_action.is_none() || score > alpha {
+            best_action = Some(action);
+            alpha = score;
+        }
+    }
+    (best_action.unwrap(), node_count)
+}
+
+pub fn ordered_alpha_beta_action<const H: usize, const W: usize, const END_TURN: i32>(state: &AlternateMazeState<H, W, END_TURN>, depth: usize) -> Action {
+    ordered_alpha_beta_action_with_count(state, depth).0
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::rngs::SmallRng;
+    use rand::{RngCore, SeedableRng};
+    use crate::search::mini_max::mini_max_action_with_count;
+    use super::*;
+
+    type State = AlternateMazeState<5, 5, 4>;
+
+    /// 同じ順に合法手を調べて同点なら先の手を残すので、アルファ・ベータ法はミニマックス法と同じ手を選び、探索する節点は多くならない
+    ///
+    /// 初期局面では枝刈りが起きて、節点は厳密に少ない。
+    #[test]
+    fn matches_mini_max_with_fewer_nodes() {
+        let depth = 4;
+        let mut rng_for_action = SmallRng::seed_from_u64(0);
+        let mut rng_for_construct = SmallRng::seed_from_u64(0);
+        for _ in 0..10 {
+            let mut state = State::new(rng_for_construct.next_u64());
+            let (_, alpha_beta_count) = alpha_beta_action_with_count(&state, depth);
+            let (_, mini_max_count) = mini_max_action_with_count(&state, depth);
+            assert!(alpha_beta_count < mini_max_count);
+            while !state.is_done() {
+                let (alpha_beta_action, alpha_beta_count) = alpha_beta_action_with_count(&state, depth);
+                let (mini_max_action, mini_max_count) = mini_max_action_with_count(&state, depth);
+                assert_eq!(alpha_beta_action, mini_max_action);
+                assert!(alpha_beta_count <= mini_max_count);
+                let legal_actions = state.legal_actions();
+                state.advance(legal_actions[rng_for_action.next_u32() as usize % legal_actions.len()]);
+            }
+        }
+    }
+
+    /// 先手から見た評価値を、先手の番では最大化し後手の番では最小化する (符号反転を使わない素朴なミニマックス法)
+    fn explicit_mini_max_score(state: &State, depth: usize) -> ScoreType {
+        let legal_actions = state.legal_actions();
+        if state.is_done() || depth == 0 || legal_actions.is_empty() {
+            let sign = if state.current_player() == 0 { 1 } else { -1 };
+            return sign * state.teban_evaluation();
+        }
+        let scores = legal_actions.into_iter().map(|action| {
+            let mut next_state = state.clone();
+            next_state.advance(action);
+            explicit_mini_max_score(&next_state, depth - 1)
+        });
+        if state.current_player() == 0 {
+            scores.max().unwrap()
+        } else {
+            scores.min().unwrap()
+        }
+    }
+
+    /// 窓を全開にした negamax の評価値は、手番の符号を付けた素朴なミニマックス法の評価値と一致する
+    #[test]
+    fn negamax_matches_explicit_mini_max() {
+        let depth = 4;
+        let mut rng_for_action = SmallRng::seed_from_u64(0);
+        let mut rng_for_construct = SmallRng::seed_from_u64(0);
+        for _ in 0..5 {
+            let mut state = State::new(rng_for_construct.next_u64());
+            while !state.is_done() {
+                let sign = if state.current_player() == 0 { 1 } else { -1 };
+                assert_eq!(negamax_score(&state, -ScoreType::MAX, ScoreType::MAX, depth), sign * explicit_mini_max_score(&state, depth));
+                let legal_actions = state.legal_actions();
+                state.advance(legal_actions[rng_for_action.next_u32() as usize % legal_actions.len()]);
+            }
+        }
+    }
+
+    /// 手の並べ替えとキラームーブを使っても選ぶ手の評価値 (根の評価値) は変わらず、探索する節点は多くならない
+    #[test]
+    fn ordering_keeps_root_value_with_no_more_nodes() {
+        let depth = 4;
+        let mut rng_for_action = SmallRng::seed_from_u64(0);
+        let mut rng_for_construct = SmallRng::seed_from_u64(0);
+        for _ in 0..10 {
+            let mut state = State::new(rng_for_construct.next_u64());
+            while !state.is_done() {
+                let (unordered_action, unordered_count) = alpha_beta_action_with_count(&state, depth);
+                let (ordered_action, ordered_count) = ordered_alpha_beta_action_with_count(&state, depth);
+                let root_value = |action: Action| {
+                    let mut next_state = state.clone();
+                    next_state.advance(action);
+                    -negamax_score(&next_state, -INF, INF, depth - 1)
+                };
+                assert_eq!(root_value(ordered_action), root_value(unordered_action));
+                assert!(ordered_count <= unordered_count, "{ordered_count} > {unordered_count}");
+                let legal_actions = state.legal_actions();
+                state.advance(legal_actions[rng_for_action.next_u32() as usize % legal_actions.len()]);
+            }
+        }
+    }
+
+    /// 深さ 0 では探索せずに最初の合法手を返す
+    #[test]
+    fn depth_zero_returns_first_legal_action() {
+        let state = State::new(0);
+        let first_action = state.legal_actions()[0];
+        assert_eq!(mini_max_action_with_count(&state, 0), (first_action, 0));
+        assert_eq!(alpha_beta_action_with_count(&state, 0), (first_action, 0));
+        assert_eq!(ordered_alpha_beta_action_with_count(&state, 0), (first_action, 0));
+        assert_eq!(alpha_beta_action_with_deadline(&state, 0, Instant::now()), (first_action, true));
+    }
+}
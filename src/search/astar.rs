@@ -0,0 +1,158 @@
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashSet};
+use std::time::Duration;
+use crate::SingleGameState;
+use crate::maze::{Action, MazeState};
+use crate::search::config::SearchConfig;
+use crate::time_keeper::TimeKeeper;
+
+/// `g + h` の大きい順に取り出すための組
+struct Node {
+    f: i32,
+    state: MazeState,
+}
+
+impl Eq for Node {}
+
+impl PartialEq<Self> for Node {
+    fn eq(&self, other: &Self) -> bool {
+        self.f.eq(&other.f)
+    }
+}
+
+impl PartialOrd<Self> for Node {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Node {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.f.cmp(&other.f)
+    }
+}
+
+impl Node {
+    fn new(state: MazeState) -> Self {
+        let h = if state.is_done() { 0 } else { state.remaining_score_upper_bound() };
+        let f = state.game_score() + h;
+        Self { f, state }
+    }
+}
+
+/// 終了状態では行動を選べないので呼べない
+pub fn astar_action(state: &MazeState) -> Action {
+    astar_action_with_count(state).0.expect("astar cannot choose an action from a finished state")
+}
+
+/// `g = game_score`、`h = remaining_score_upper_bound` の A* で、最初に取り出した終了状態 (最適解) に至る最初の行動と展開したノードの数を返す
+///
+/// 終了状態の `h` は 0 とする。`state` 自体が終了状態なら行動は `None` になる。
+/// 移動コストが 0 のマスがあると `h` が上界にならないので、そのような盤面では呼べない。
+pub fn astar_action_with_count(state: &MazeState) -> (Option<Action>, u64) {
+    astar(state, usize::MAX, Duration::MAX)
+}
+
+/// `config` のノード数の上限か制限時間に達したら、その時点で `g + h` が最も大きい状態に至る最初の行動を返す (ビーム幅と深さは使わない)
+///
+/// 打ち切った場合は最適とは限らない。
+pub fn astar_action_with_config(state: &MazeState, config: &SearchConfig) -> Action {
+    astar(state, config.node_limit, config.time_limit).0.expect("astar cannot choose an action from a finished state")
+}
+
+fn astar(state: &MazeState, node_limit: usize, time_limit: Duration) -> (Option<Action>, u64) {
+    assert!(!state.has_road(), "astar cannot bound the score with zero-cost cells");
+    let time_keeper = TimeKeeper::new(time_limit);
+    let mut heap = BinaryHeap::new();
+    let mut closed = HashSet::new();
+    let mut node_count = 0;
+    let mut is_root = true;
+    heap.push(Node::new(state.clone()));
+    while let Some(Node { state: now_state, .. }) = heap.pop() {
+        if now_state.is_done() {
+            return (now_state.first_action(), node_count);
+        }
+        if !closed.insert((now_state.zobrist_hash(), now_state.turn())) { continue; }
+        node_count += 1;
+        for action in now_state.legal_actions() {
+            let mut next_state = now_state.clone();
+            next_state.advance(action);
+            if is_root {
+                next_state.set_first_action(action);
+            }
+            heap.push(Node::new(next_state));
+        }
+        is_root = false;
+        if node_count >= node_limit as u64 || time_keeper.is_time_over() {
+            return (heap.peek().and_then(|node| node.state.first_action()), node_count);
+        }
+    }
+    (None, node_count)
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::rngs::SmallRng;
+    use rand::{RngCore, SeedableRng};
+    use crate::maze::MazeParams;
+    use crate::search::brute_force::brute_force_best_score;
+    use super::*;
+
+    /// 3x4 の盤面で、A* だけで最後まで進めると全探索の最大値と同じ得点になる
+    #[test]
+    fn matches_brute_force_on_3x4() {
+        let params = MazeParams { h: 3, w: 4, end_turn: 4 };
+        let mut rng_for_construct = SmallRng::seed_from_u64(0);
+        for _ in 0..50 {
+            let mut state = MazeState::new(rng_for_construct.next_u64(), params);
+            let best_score = brute_force_best_score(&state);
+            while !state.is_done() {
+                state.advance(astar_action(&state));
+            }
+            assert_eq!(state.game_score(), best_score);
+        }
+    }
+
+    /// 左は移動コスト 2 のマスで 2 点取って行き止まり、右は 1 点ずつ 3 ターン取れるので、行き止まりの得点を上限で水増ししなければ右を選ぶ
+    #[test]
+    fn dead_end_is_scored_without_heuristic() {
+        let mut state = MazeState::from_board_string("3 0 0 2@1119").unwrap();
+        state.set_movement_costs(vec![vec![2, 2, 1, 1, 1, 1]]);
+        assert_eq!(brute_force_best_score(&state), 3);
+        assert_eq!(astar_action_with_count(&state).0, Some(Action::Right));
+    }
+
+    /// ノード数の上限に達して打ち切っても、根を展開した後なので合法手を返す
+    #[test]
+    fn config_node_limit_returns_legal_action() {
+        let config = SearchConfig::new().node_limit(1).build();
+        let mut rng_for_construct = SmallRng::seed_from_u64(0);
+        for _ in 0..20 {
+            let state = MazeState::new(rng_for_construct.next_u64(), MazeParams { h: 30, w: 30, end_turn: 100 });
+            assert!(state.legal_actions().contains(&astar_action_with_config(&state, &config)));
+        }
+    }
+
+    /// 渡した状態に最初の行動が残っていても根として扱い、子に最初の行動を付け直す
+    #[test]
+    fn root_with_stale_first_action() {
+        let mut state = MazeState::from_board_string("3 2 0 1@5").unwrap();
+        state.set_first_action(Action::Left);
+        assert_eq!(astar_action(&state), Action::Right);
+    }
+
+    #[test]
+    #[should_panic(expected = "astar cannot bound the score with zero-cost cells")]
+    fn rejects_roads() {
+        let mut state = MazeState::from_board_string("3 0 0 @1119").unwrap();
+        state.set_movement_costs(vec![vec![1, 0, 0, 1, 1]]);
+        astar_action(&state);
+    }
+
+    /// 終了状態からは行動を選べない
+    #[test]
+    fn finished_state_has_no_action() {
+        let state = MazeState::from_board_string("3 3 0 @1").unwrap();
+        assert_eq!(astar_action_with_count(&state).0, None);
+    }
+}
@@ -0,0 +1,634 @@
+use std::cmp::{Ordering, Reverse};
+use std::collections::{BinaryHeap, HashMap, HashSet};
+use std::time::Duration;
+use crate::SingleGameState;
+use crate::maze::{Coord, HeuristicFn};
+use crate::search::config::SearchConfig;
+use crate::search::stats::SearchStats;
+use crate::time_keeper::TimeKeeper;
+
+pub fn beam_search_action<S: SingleGameState + Ord>(state: &S, beam_width: usize, beam_depth: usize) -> S::Action {
+    beam_search_action_with_count(state, beam_width, beam_depth, None).0
+}
+
+/// 各深さで既に積んだことのある盤面 (`hash` が等しい盤面) を重複して積まないビームサーチ
+pub fn beam_search_action_with_dedup<S: SingleGameState + Ord>(state: &S, beam_width: usize, beam_depth: usize, hash: &dyn Fn(&S) -> u64) -> S::Action {
+    beam_search_action_with_count(state, beam_width, beam_depth, Some(hash)).0
+}
+
+/// 選んだ行動と、展開したノードの数を返す
+pub fn beam_search_action_with_count<S: SingleGameState + Ord>(state: &S, beam_width: usize, beam_depth: usize, hash: Option<&dyn Fn(&S) -> u64>) -> (S::Action, u64) {
+    let (action, _, stats) = beam_search(state, beam_width, beam_depth, hash);
+    (action, stats.nodes_expanded)
+}
+
+/// 選んだ行動と、探索で見つけた最も良い盤面の評価値 (この行動の先で得られると予測した評価値) を返す
+pub fn beam_search_action_with_prediction<S: SingleGameState + Ord>(state: &S, beam_width: usize, beam_depth: usize) -> (S::Action, S::ScoreType) {
+    let (action, predicted_score, _) = beam_search(state, beam_width, beam_depth, None);
+    (action, predicted_score)
+}
+
+/// 選んだ行動と、探索にかけた手間を返す
+pub fn beam_search_action_with_stats<S: SingleGameState + Ord>(state: &S, beam_width: usize, beam_depth: usize) -> (S::Action, SearchStats) {
+    let (action, _, stats) = beam_search(state, beam_width, beam_depth, None);
+    (action, stats)
+}
+
+/// `beam_search_auto` で 1 ノードの展開にかかる時間を測るための探索のビーム幅と深さ
+const CALIBRATION_WIDTH: usize = 8;
+const CALIBRATION_DEPTH: usize = 4;
+
+/// `beam_search_auto` で見積もりが外れても制限時間を超えないよう、残り時間のうち探索に使う割合
+const AUTO_TIME_MARGIN: f64 = 0.8;
+
+/// `beam_search_auto` が選ぶビーム幅の上限 (展開にかかる時間が 0 と測られても際限なく広げない)
+const MAX_AUTO_WIDTH: usize = 10_000;
+
+/// 小さいビーム幅で浅く探索して 1 ノードの展開にかかる時間を測り、深さ `beam_depth` まで探索しても `time_threshold` に収まる最大のビーム幅 (1 以上 `MAX_AUTO_WIDTH` 以下) で探索する
+///
+/// 見積もりが外れても、制限時間を過ぎたらそれまでに探索し終えた深さの最善の行動を返す。選んだビーム幅は `SearchStats::beam_width` に入れて返す。
+pub fn beam_search_auto<S: SingleGameState + Ord>(state: &S, beam_depth: usize, time_threshold: Duration) -> (S::Action, SearchStats) {
+    assert!(beam_depth > 0, "beam_depth must be positive");
+    let time_keeper = TimeKeeper::new(time_threshold);
+    let (_, _, calibration_stats) = beam_search(state, CALIBRATION_WIDTH, CALIBRATION_DEPTH.min(beam_depth), None);
+    let expansion_time = calibration_stats.elapsed.as_secs_f64() / calibration_stats.nodes_expanded as f64;
+    let remaining_time = time_threshold.saturating_sub(time_keeper.elapsed());
+    let beam_width = ((remaining_time.as_secs_f64() * AUTO_TIME_MARGIN / (expansion_time * beam_depth as f64)) as usize).clamp(1, MAX_AUTO_WIDTH);
+    let config = SearchConfig::new().beam_width(beam_width).beam_depth(beam_depth).time_limit(remaining_time);
+    let (action, _, mut stats) = beam_search_with_config(state, &config, &|_| beam_width, None, None);
+    stats.elapsed = time_keeper.elapsed();
+    stats.beam_width = beam_width;
+    (action, stats)
+}
+
+/// 選んだ行動、最も良い盤面の評価値、探索にかけた手間を返す
+///
+/// ビーム幅と深さは 1 以上でなければならず、合法手がない状態 (`is_dead_end`) では呼べない。
+fn beam_search<S: SingleGameState>(state: &S, beam_width: usize, beam_depth: usize, hash: Option<&dyn Fn(&S) -> u64>) -> (S::Action, S::ScoreType, SearchStats) {
+    let config = SearchConfig::new().beam_width(beam_width).beam_depth(beam_depth).time_limit(Duration::MAX).build();
+    beam_search_with_config(state, &config, &|_| beam_width, None, hash)
+}
+
+/// 最も良い最終状態に至る行動列 (読み筋) を丸ごと返すビームサーチ
+///
+/// `beam_search_action` と同じく、評価値が等しければ先に生成した盤面を優先する。
+/// ビーム幅と深さは 1 以上でなければならない。合法手がない状態 (`is_dead_end`) では空の行動列を返す。
+pub fn pv_search_action<S: SingleGameState + Ord>(state: &S, beam_width: usize, depth: usize) -> Vec<S::Action> {
+    let config = SearchConfig::new().beam_width(beam_width).beam_depth(depth).build();
+    let (beam_width, depth) = (config.beam_width, config.beam_depth);
+    let mut order = 0;
+    let mut now_beam = BinaryHeap::new();
+    let mut best_actions = Vec::new();
+    now_beam.push((PlanNode { state: state.clone(), actions: Vec::new() }, Reverse(order)));
+    for _ in 0..depth {
+        let mut next_beam = BinaryHeap::new();
+        for _ in 0..beam_width {
+            let Some((now_node, _)) = now_beam.pop() else { break; };
+            for action in now_node.state.legal_actions() {
+                let mut next_state = now_node.state.clone();
+                next_state.advance(action);
+                next_state.evaluate_score();
+                let mut actions = now_node.actions.clone();
+                actions.push(action);
+                order += 1;
+                next_beam.push((PlanNode { state: next_state, actions }, Reverse(order)));
+            }
+        }
+        if next_beam.is_empty() { break; }
+        now_beam = next_beam;
+        let (best_node, _) = now_beam.peek().unwrap();
+        best_actions = best_node.actions.clone();
+        if best_node.state.is_done() { break; }
+    }
+    best_actions
+}
+
+/// ビームに積む状態と、根からその状態に至るまでの行動列
+#[derive(Clone)]
+pub struct PlanNode<S: SingleGameState> {
+    pub state: S,
+    pub actions: Vec<S::Action>,
+}
+
+impl<S: SingleGameState + Ord> Eq for PlanNode<S> {}
+
+impl<S: SingleGameState + Ord> PartialEq<Self> for PlanNode<S> {
+    fn eq(&self, other: &Self) -> bool {
+        self.state.eq(&other.state)
+    }
+}
+
+impl<S: SingleGameState + Ord> PartialOrd<Self> for PlanNode<S> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<S: SingleGameState + Ord> Ord for PlanNode<S> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.state.cmp(&other.state)
+    }
+}
+
+/// `evaluate_score` の代わりに盤面を評価する関数
+pub(crate) type EvalFn<'a, S> = dyn Fn(&S) -> <S as SingleGameState>::ScoreType + 'a;
+
+/// 盤面の評価に `evaluate_score` の代わりに `eval` を使うビームサーチ (評価値が等しければ先に生成した盤面を優先する)
+pub fn beam_search_action_with_eval<S: SingleGameState>(state: &S, beam_width: usize, beam_depth: usize, eval: &dyn Fn(&S) -> S::ScoreType) -> S::Action {
+    let config = SearchConfig::new().beam_width(beam_width).beam_depth(beam_depth).time_limit(Duration::MAX).build();
+    beam_search_with_config(state, &config, &|_| beam_width, Some(eval), None).0
+}
+
+/// 盤面を `heuristic` で評価するビームサーチ (`None` なら `evaluate_score` で評価する。評価値が等しければ先に生成した盤面を優先する)
+pub fn beam_search_action_with_heuristic<S: SingleGameState>(state: &S, beam_width: usize, beam_depth: usize, heuristic: Option<HeuristicFn<S>>) -> S::Action {
+    let config = SearchConfig::new().beam_width(beam_width).beam_depth(beam_depth).time_limit(Duration::MAX).build();
+    beam_search_with_config(state, &config, &|_| beam_width, heuristic.as_ref().map(|heuristic| heuristic as &EvalFn<S>), None).0
+}
+
+/// 外から与えた評価値で並べるための組。評価値が等しければ `order` が小さい (先に積んだ) ほうが大きい
+pub(crate) struct Scored<S: SingleGameState> {
+    score: S::ScoreType,
+    order: u64,
+    pub(crate) state: S,
+}
+
+impl<S: SingleGameState> Scored<S> {
+    pub(crate) fn new(score: S::ScoreType, order: u64, state: S) -> Self {
+        Self { score, order, state }
+    }
+}
+
+impl<S: SingleGameState> PartialEq for Scored<S> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<S: SingleGameState> Eq for Scored<S> {}
+
+impl<S: SingleGameState> PartialOrd for Scored<S> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<S: SingleGameState> Ord for Scored<S> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.score.cmp(&other.score).then_with(|| other.order.cmp(&self.order))
+    }
+}
+
+pub fn beam_search_action_with_time_threshold<S: SingleGameState + Ord>(state: &S, beam_width: usize, time_threshold: Duration) -> S::Action {
+    let config = SearchConfig::new().beam_width(beam_width).beam_depth(usize::MAX).time_limit(time_threshold).build();
+    beam_search_with_config(state, &config, &|_| beam_width, None, None).0
+}
+
+/// `config` のビーム幅と深さで探索し、制限時間かノード数の上限に達したらそれまでの最善の状態の行動を返す
+pub fn beam_search_action_with_config<S: SingleGameState + Ord>(state: &S, config: &SearchConfig) -> S::Action {
+    beam_search_with_config(state, config, &|_| config.beam_width, None, None).0
+}
+
+/// 深さ `t` のビーム幅を `min(max_width, floor(initial_width * growth_factor^t))` にするビームサーチ
+pub fn adaptive_beam_search_action<S: SingleGameState + Ord>(state: &S, initial_width: usize, max_width: usize, growth_factor: f64, time_threshold: Duration) -> S::Action {
+    assert!(max_width > 0, "max_width must be positive");
+    let config = SearchConfig::new().beam_width(max_width).beam_depth(usize::MAX).time_limit(time_threshold).build();
+    beam_search_with_config(state, &config, &|t| ((initial_width as f64 * growth_factor.powi(t as i32)).floor() as usize).clamp(1, max_width), None, None).0
+}
+
+/// `config` の深さ、制限時間、ノード数の上限で探索し、深さ `t` では `beam_width_at(t)` 個の盤面を展開するビームサーチ
+///
+/// 盤面は `eval` があればその評価値、なければ `evaluate_score` の評価値で並べる (等しければ先に生成した盤面を優先する)。
+/// `hash` を渡すと、各深さで既に積んだことのある盤面 (`hash` が等しい盤面) を重複して積まない。
+/// 制限に達したら、最後に探索し終えた深さの最善の盤面に至る行動とその評価値 (まだなければ最初の合法手と今の盤面の評価値) を、探索にかけた手間と合わせて返す。
+fn beam_search_with_config<S: SingleGameState>(state: &S, config: &SearchConfig, beam_width_at: &dyn Fn(usize) -> usize, eval: Option<&EvalFn<'_, S>>, hash: Option<&dyn Fn(&S) -> u64>) -> (S::Action, S::ScoreType, SearchStats) {
+    let time_keeper = TimeKeeper::new(config.time_limit);
+    let mut stats = SearchStats::default();
+    let score = |state: &mut S| match eval {
+        Some(eval) => eval(state),
+        None => {
+            state.evaluate_score();
+            state.evaluated_score()
+        }
+    };
+    let mut order = 0;
+    let mut now_beam = BinaryHeap::new();
+    let mut root_state = state.clone();
+    let root_score = score(&mut root_state);
+    now_beam.push(Scored::new(root_score, order, root_state));
+    let mut best = None;
+    let result = |best: Option<(S::Action, S::ScoreType)>, mut stats: SearchStats| {
+        stats.elapsed = time_keeper.elapsed();
+        let (action, score) = best.unwrap_or_else(|| (*state.legal_actions().first().expect("no legal actions in a dead end"), root_score));
+        (action, score, stats)
+    };
+    for t in 0..config.beam_depth {
+        let mut next_beam = BinaryHeap::new();
+        let mut seen = HashSet::new();
+        for _ in 0..beam_width_at(t) {
+            if time_keeper.is_time_over() || stats.nodes_expanded >= config.node_limit as u64 {
+                return result(best, stats);
+            }
+            let Some(Scored { state: now_state, .. }) = now_beam.pop() else { break; };
+            stats.nodes_expanded += 1;
+            for action in now_state.legal_actions() {
+                let mut next_state = now_state.clone();
+                next_state.advance(action);
+                if t == 0 {
+                    next_state.set_first_action(action);
+                }
+                if let Some(hash) = hash {
+                    if !seen.insert(hash(&next_state)) { continue; }
+                }
+                order += 1;
+                next_beam.push(Scored::new(score(&mut next_state), order, next_state));
+            }
+        }
+        if next_beam.is_empty() { break; }
+        now_beam = next_beam;
+        stats.max_depth_reached = t + 1;
+        let best_scored = now_beam.peek().unwrap();
+        best = best_scored.state.first_action().map(|action| (action, best_scored.score));
+        if best_scored.state.is_done() { break; }
+    }
+    result(best, stats)
+}
+
+/// `beam_width` 個を選ぶときに、既に選んだ状態と `key` (キャラクターの位置など) が同じ状態の評価値を 1 つにつき `penalty` 下げるビームサーチ
+pub fn diverse_beam_search_action<S: SingleGameState<ScoreType = i64>>(state: &S, beam_width: usize, beam_depth: usize, penalty: i64, key: &dyn Fn(&S) -> u64) -> S::Action {
+    assert!(beam_depth > 0, "beam_depth must be positive");
+    let (best_state, _) = diverse_beam_search(state, beam_depth, &|states| select_diverse(states, beam_width, penalty, key));
+    best_state.and_then(|best_state| best_state.first_action()).expect("no legal actions in a dead end")
+}
+
+/// 各深さで展開した全ての状態から `select` で次のビームを選ぶビームサーチで、最も良い状態 (根が行き止まりなら `None`) と最後の深さで残したビームを返す
+fn diverse_beam_search<S: SingleGameState<ScoreType = i64>>(state: &S, beam_depth: usize, select: &dyn Fn(Vec<S>) -> Vec<S>) -> (Option<S>, Vec<S>) {
+    let mut now_beam = vec![state.clone()];
+    let mut best_state: Option<S> = None;
+    for t in 0..beam_depth {
+        let mut next_states = Vec::new();
+        for now_state in &now_beam {
+            for action in now_state.legal_actions() {
+                let mut next_state = now_state.clone();
+                next_state.advance(action);
+                next_state.evaluate_score();
+                if t == 0 {
+                    next_state.set_first_action(action);
+                }
+                next_states.push(next_state);
+            }
+        }
+        if next_states.is_empty() { break; }
+        let now_best_state = next_states.iter().max_by_key(|next_state| next_state.evaluated_score()).unwrap().clone();
+        let is_done = now_best_state.is_done();
+        best_state = Some(now_best_state);
+        if is_done { break; }
+        now_beam = select(next_states);
+    }
+    (best_state, now_beam)
+}
+
+fn select_diverse<S: SingleGameState<ScoreType = i64>>(states: Vec<S>, beam_width: usize, penalty: i64, key: &dyn Fn(&S) -> u64) -> Vec<S> {
+    let keys = states.iter().map(key).collect::<Vec<_>>();
+    let mut heap = states.iter().enumerate().map(|(i, state)| (state.evaluated_score(), 0, Reverse(i))).collect::<BinaryHeap<_>>();
+    let mut states = states.into_iter().map(Some).collect::<Vec<_>>();
+    let mut selected_count = HashMap::new();
+    let mut selected = Vec::new();
+    while selected.len() < beam_width {
+        let Some((score, count, Reverse(i))) = heap.pop() else { break; };
+        let now_count = selected_count.get(&keys[i]).copied().unwrap_or(0);
+        if now_count == count {
+            selected_count.insert(keys[i], now_count + 1);
+            selected.push(states[i].take().unwrap());
+        } else {
+            heap.push((score - penalty * (now_count - count), now_count, Reverse(i)));
+        }
+    }
+    selected
+}
+
+/// `spatially_diverse_beam_search_action` で近いとみなすチェビシェフ距離
+const DIVERSITY_RADIUS: usize = 2;
+
+/// `beam_width` 個を選ぶときに、既に選んだ状態と `position` (キャラクターの位置など) のチェビシェフ距離が 2 以内の状態の評価値を、
+/// そのような状態 1 つにつき `diversity_weight` 下げるビームサーチ
+pub fn spatially_diverse_beam_search_action<S: SingleGameState<ScoreType = i64>>(state: &S, beam_width: usize, diversity_weight: f64, beam_depth: usize, position: &dyn Fn(&S) -> Coord) -> S::Action {
+    assert!(beam_depth > 0, "beam_depth must be positive");
+    let (best_state, _) = diverse_beam_search(state, beam_depth, &|states| select_spatially_diverse(states, beam_width, diversity_weight, position));
+    best_state.and_then(|best_state| best_state.first_action()).expect("no legal actions in a dead end")
+}
+
+/// 減点後の評価値が最も高い状態を 1 つずつ選び、選ぶたびに近くにある残りの状態の減点を増やす (同点なら先に生成した状態を選ぶ)
+fn select_spatially_diverse<S: SingleGameState<ScoreType = i64>>(states: Vec<S>, beam_width: usize, diversity_weight: f64, position: &dyn Fn(&S) -> Coord) -> Vec<S> {
+    let positions = states.iter().map(position).collect::<Vec<_>>();
+    let mut overlap_counts = vec![0; states.len()];
+    let mut states = states.into_iter().map(Some).collect::<Vec<_>>();
+    let mut selected = Vec::new();
+    while selected.len() < beam_width {
+        let mut best = None;
+        for (i, state) in states.iter().enumerate() {
+            let Some(state) = state else { continue; };
+            let score = state.evaluated_score() as f64 - diversity_weight * overlap_counts[i] as f64;
+            if best.is_none_or(|(_, best_score)| score > best_score) {
+                best = Some((i, score));
+            }
+        }
+        let Some((selected_index, _)) = best else { break; };
+        for (i, state) in states.iter().enumerate() {
+            if state.is_some() && positions[i].chebyshev_distance(&positions[selected_index]) <= DIVERSITY_RADIUS {
+                overlap_counts[i] += 1;
+            }
+        }
+        selected.push(states[selected_index].take().unwrap());
+    }
+    selected
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Instant;
+    use rand::rngs::SmallRng;
+    use rand::{RngCore, SeedableRng};
+    use crate::maze::{Action, MazeParams, MazeState};
+    use crate::search::brute_force::brute_force_best_score;
+    use crate::search::greedy::greedy_action;
+    use super::*;
+
+    /// 3x4 の盤面で、全ての行動列を残せるビーム幅なら全探索の最大値に届き、狭いビーム幅でも最大値を超えない
+    #[test]
+    fn matches_brute_force_on_3x4() {
+        let params = MazeParams { h: 3, w: 4, end_turn: 5 };
+        let mut rng_for_construct = SmallRng::seed_from_u64(0);
+        for _ in 0..50 {
+            let initial_state = MazeState::new(rng_for_construct.next_u64(), params);
+            let best_score = brute_force_best_score(&initial_state);
+            let play = |beam_width: usize| {
+                let mut state = initial_state.clone();
+                while !state.is_done() {
+                    state.advance(beam_search_action(&state, beam_width, params.end_turn as usize));
+                }
+                state.game_score()
+            };
+            assert_eq!(play(4usize.pow(params.end_turn as u32)), best_score);
+            assert!(play(2) <= best_score);
+        }
+    }
+
+    /// 時間制限つきのビームサーチは合法手を返し、制限時間から 2ms 以内に戻る
+    ///
+    /// 並行して走る他のテストに CPU を取られて止まった分を除くため、同じ盤面で 3 回測った最短の時間で確かめる。
+    #[test]
+    fn time_threshold_returns_legal_action_in_time() {
+        let time_threshold = Duration::from_millis(10);
+        let mut rng_for_construct = SmallRng::seed_from_u64(0);
+        for _ in 0..10 {
+            let state = MazeState::new(rng_for_construct.next_u64(), MazeParams { h: 30, w: 30, end_turn: 100 });
+            let elapsed = (0..3).map(|_| {
+                let start = Instant::now();
+                let action = beam_search_action_with_time_threshold(&state, 5, time_threshold);
+                assert!(state.legal_actions().contains(&action));
+                start.elapsed()
+            }).min().unwrap();
+            assert!(elapsed <= time_threshold + Duration::from_millis(2), "took {elapsed:?}");
+        }
+    }
+
+    /// ビーム幅 1 で根を展開したところで打ち切ると、深さ 1 の最善の盤面に至る行動 (評価値が等しければ先に生成したもの) として貪欲法と同じ手を返す
+    #[test]
+    fn config_node_limit_one_matches_greedy() {
+        let config = SearchConfig::new().beam_width(1).beam_depth(10).node_limit(1).time_limit(Duration::MAX).build();
+        let mut rng_for_construct = SmallRng::seed_from_u64(0);
+        for _ in 0..20 {
+            let state = MazeState::new(rng_for_construct.next_u64(), MazeParams { h: 5, w: 5, end_turn: 10 });
+            assert_eq!(beam_search_action_with_config(&state, &config), greedy_action(&state));
+            assert_eq!(beam_search_action_with_eval(&state, 1, 1, &|state: &MazeState| state.game_score() as i64), greedy_action(&state));
+        }
+    }
+
+    /// 時間に余裕があってもビーム幅は `MAX_AUTO_WIDTH` までに抑え、制限時間が 0 でも合法手を返す
+    #[test]
+    fn auto_clamps_width_and_keeps_deadline() {
+        let state = MazeState::new(0, MazeParams { h: 5, w: 5, end_turn: 10 });
+        let (action, stats) = beam_search_auto(&state, 1, Duration::from_secs(60));
+        assert_eq!(stats.beam_width, MAX_AUTO_WIDTH);
+        assert!(state.legal_actions().contains(&action));
+        let (action, stats) = beam_search_auto(&state, 10, Duration::ZERO);
+        assert_eq!(stats.beam_width, 1);
+        assert!(state.legal_actions().contains(&action));
+    }
+
+    /// 評価のたびに `delay` だけ待つ、遅い評価関数を模した迷路
+    #[derive(Clone, PartialEq, Eq, PartialOrd, Ord)]
+    struct SlowMazeState {
+        state: MazeState,
+        delay: Duration,
+    }
+
+    impl SingleGameState for SlowMazeState {
+        type Action = <MazeState as SingleGameState>::Action;
+        type ScoreType = <MazeState as SingleGameState>::ScoreType;
+
+        fn is_done(&self) -> bool {
+            self.state.is_done()
+        }
+
+        fn advance(&mut self, action: Self::Action) {
+            self.state.advance(action);
+        }
+
+        fn legal_actions(&self) -> Vec<Self::Action> {
+            self.state.legal_actions()
+        }
+
+        fn evaluate_score(&mut self) {
+            let start = Instant::now();
+            while start.elapsed() < self.delay {}
+            self.state.evaluate_score();
+        }
+
+        fn evaluated_score(&self) -> Self::ScoreType {
+            self.state.evaluated_score()
+        }
+
+        fn first_action(&self) -> Option<Self::Action> {
+            self.state.first_action()
+        }
+
+        fn set_first_action(&mut self, action: Self::Action) {
+            self.state.set_first_action(action);
+        }
+    }
+
+    /// 評価が遅いほど小さいビーム幅を選ぶ
+    ///
+    /// 見積もりの最中に CPU を取られると速い評価関数でも遅く見えるので、速い方は 3 回選んだ最大のビーム幅で比べる。
+    #[test]
+    fn auto_picks_smaller_width_for_slow_evaluator() {
+        let params = MazeParams { h: 30, w: 30, end_turn: 100 };
+        let time_threshold = Duration::from_millis(100);
+        let mut rng_for_construct = SmallRng::seed_from_u64(0);
+        for _ in 0..3 {
+            let state = MazeState::new(rng_for_construct.next_u64(), params);
+            let fast_width = (0..3).map(|_| beam_search_auto(&state, params.end_turn as usize, time_threshold).1.beam_width).max().unwrap();
+            let slow_state = SlowMazeState { state, delay: Duration::from_micros(50) };
+            let (_, slow_stats) = beam_search_auto(&slow_state, params.end_turn as usize, time_threshold);
+            assert!(fast_width > slow_stats.beam_width, "{fast_width} <= {}", slow_stats.beam_width);
+        }
+    }
+
+    /// 上の枝には 9 点が並び、下の枝には 1 点しかない分かれ道で、2 手進めたときに残るビーム (幅 2) の枝を比べる
+    ///
+    /// 罰則 0 (通常のビームサーチと同じ選び方) では上の枝の 9 点の 2 状態だけが残るが、同じ枝の状態に罰則を課すと下の枝も残る。
+    #[test]
+    fn diverse_beam_keeps_both_branches() {
+        let state = MazeState::from_board_string("4 0 0 99999/.9999/@####/.1111").unwrap();
+        let branch = |state: &MazeState| u64::from(state.character().y > 2);
+        let branches = |penalty: i64| {
+            let (_, beam) = diverse_beam_search(&state, 2, &|states| select_diverse(states, 2, penalty, &branch));
+            let mut branches = beam.iter().map(branch).collect::<Vec<_>>();
+            branches.sort_unstable();
+            branches
+        };
+        assert_eq!(branches(0), vec![0, 0]);
+        assert_eq!(branches(100), vec![0, 1]);
+    }
+
+    #[test]
+    #[should_panic(expected = "beam_depth must be positive")]
+    fn diverse_rejects_zero_depth() {
+        diverse_beam_search_action(&MazeState::new(0, MazeParams { h: 3, w: 4, end_turn: 5 }), 2, 0, 10, &|state: &MazeState| state.zobrist_hash());
+    }
+
+    #[test]
+    #[should_panic(expected = "no legal actions in a dead end")]
+    fn diverse_rejects_dead_end() {
+        let state = MazeState::from_board_string("5 0 0 #@#").unwrap();
+        diverse_beam_search_action(&state, 2, 2, 10, &|state: &MazeState| state.zobrist_hash());
+    }
+
+    /// 上の枝には 9 点が並び、下の枝には 1 点ずつしかない分かれ道で、2 手進めたときに残るビーム (幅 2) の枝を比べる
+    ///
+    /// 重み 0 では上の枝の互いに近い 9 点の 2 状態だけが残るが、近い状態を減点すると 9 点からチェビシェフ距離 3 以上離れた下の枝の状態も残る。
+    #[test]
+    fn spatially_diverse_beam_keeps_both_branches() {
+        let state = MazeState::from_board_string("4 0 0 99999/.9999/@####/1####/1####").unwrap();
+        let position = |state: &MazeState| state.character().clone();
+        let branch = |state: &MazeState| usize::from(state.character().y > 2);
+        let branches = |diversity_weight: f64| {
+            let (_, beam) = diverse_beam_search(&state, 2, &|states| select_spatially_diverse(states, 2, diversity_weight, &position));
+            let mut branches = beam.iter().map(branch).collect::<Vec<_>>();
+            branches.sort_unstable();
+            branches
+        };
+        assert_eq!(branches(0.0), vec![0, 0]);
+        assert_eq!(branches(100.0), vec![0, 1]);
+        assert_eq!(spatially_diverse_beam_search_action(&state, 2, 100.0, 2, &position), Action::Up);
+    }
+
+    #[test]
+    #[should_panic(expected = "no legal actions in a dead end")]
+    fn spatially_diverse_rejects_dead_end() {
+        let state = MazeState::from_board_string("5 0 0 #@#").unwrap();
+        let position = |state: &MazeState| state.character().clone();
+        spatially_diverse_beam_search_action(&state, 2, 1.0, 2, &position);
+    }
+
+    /// 何もない 3x3 の盤面の対角の隅にだけ 9 点がある盤面では、右と下の順序を入れ替えた行動列が同じ盤面に行き着く
+    ///
+    /// 同じ盤面を積まないビームサーチは、同じ 9 点に届いたうえで展開するノードが少ない。
+    #[test]
+    fn dedup_expands_fewer_nodes_on_symmetric_paths() {
+        let state = MazeState::from_board_string("4 0 0 @../.../..9").unwrap();
+        let hash = |state: &MazeState| state.zobrist_hash();
+        let (_, plain_score, plain_stats) = beam_search(&state, 50, 4, None);
+        let (_, dedup_score, dedup_stats) = beam_search(&state, 50, 4, Some(&hash));
+        assert_eq!(plain_score, 9);
+        assert!(dedup_score >= plain_score && dedup_stats.nodes_expanded < plain_stats.nodes_expanded, "{} >= {}", dedup_stats.nodes_expanded, plain_stats.nodes_expanded);
+    }
+
+    #[test]
+    #[should_panic(expected = "beam_depth must be positive")]
+    fn auto_rejects_zero_depth() {
+        let state = MazeState::new(0, MazeParams { h: 5, w: 5, end_turn: 10 });
+        beam_search_auto(&state, 0, Duration::from_millis(10));
+    }
+
+    #[test]
+    #[should_panic(expected = "beam_depth must be positive")]
+    fn rejects_zero_depth() {
+        beam_search_action(&MazeState::new(0, MazeParams { h: 3, w: 4, end_turn: 5 }), 5, 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "beam_width must be positive")]
+    fn rejects_zero_width() {
+        beam_search_action(&MazeState::new(0, MazeParams { h: 3, w: 4, end_turn: 5 }), 0, 5);
+    }
+
+    #[test]
+    #[should_panic(expected = "beam_width must be positive")]
+    fn eval_rejects_zero_width() {
+        beam_search_action_with_eval(&MazeState::new(0, MazeParams { h: 3, w: 4, end_turn: 5 }), 0, 5, &|state: &MazeState| state.game_score() as i64);
+    }
+
+    #[test]
+    #[should_panic(expected = "max_width must be positive")]
+    fn adaptive_rejects_zero_max_width() {
+        adaptive_beam_search_action(&MazeState::new(0, MazeParams { h: 3, w: 4, end_turn: 5 }), 1, 0, 1.5, Duration::from_millis(10));
+    }
+
+    /// 読み筋の先頭は `beam_search_action` と一致し、読み筋をそのまま進めると合法手だけで終局する
+    #[test]
+    fn pv_matches_beam() {
+        let params = MazeParams { h: 30, w: 30, end_turn: 100 };
+        let beam_depth = params.end_turn as usize;
+        let mut rng_for_construct = SmallRng::seed_from_u64(0);
+        for _ in 0..20 {
+            let mut state = MazeState::new(rng_for_construct.next_u64(), params);
+            let pv = pv_search_action(&state, 5, beam_depth);
+            assert_eq!(pv[0], beam_search_action(&state, 5, beam_depth));
+            assert_eq!(pv.len(), beam_depth);
+            for action in pv {
+                assert!(state.legal_actions().contains(&action));
+                state.advance(action);
+            }
+            assert!(state.is_done());
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "beam_width must be positive")]
+    fn pv_rejects_zero_width() {
+        pv_search_action(&MazeState::new(0, MazeParams { h: 3, w: 4, end_turn: 5 }), 0, 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "beam_depth must be positive")]
+    fn pv_rejects_zero_depth() {
+        pv_search_action(&MazeState::new(0, MazeParams { h: 3, w: 4, end_turn: 5 }), 2, 0);
+    }
+
+    /// 合法手がない状態では、進める行動がないので空の読み筋を返す
+    #[test]
+    fn pv_of_dead_end_is_empty() {
+        let state = MazeState::from_board_string("5 0 0 #@#").unwrap();
+        assert!(pv_search_action(&state, 2, 2).is_empty());
+    }
+
+    /// 予測した評価値は、貪欲法で 1 手進めた直後の評価値を下回らない
+    #[test]
+    fn prediction_bounds_greedy() {
+        let params = MazeParams { h: 30, w: 30, end_turn: 100 };
+        let mut rng_for_construct = SmallRng::seed_from_u64(0);
+        for _ in 0..3 {
+            let mut state = MazeState::new(rng_for_construct.next_u64(), params);
+            while !state.is_done() {
+                let (action, predicted_score) = beam_search_action_with_prediction(&state, 5, params.end_turn as usize);
+                let mut greedy_state = state.clone();
+                greedy_state.advance(greedy_action(&state));
+                greedy_state.evaluate_score();
+                assert!(predicted_score >= greedy_state.evaluated_score());
+                state.advance(action);
+            }
+        }
+    }
+}
@@ -0,0 +1,78 @@
+use std::collections::BinaryHeap;
+use std::time::Duration;
+use crate::SingleGameState;
+use crate::search::config::SearchConfig;
+use crate::time_keeper::TimeKeeper;
+
+/// 深さごとの層を積んだスタックで深さ優先にビームを伸ばし、層を使い切ったら 1 つ浅い層に戻るビームサーチ
+///
+/// 一番上の層から評価の高い `beam_width` 個を取り出して展開し、その子を新しい層として積む。
+/// 各層には展開していない状態だけが残るので、使うメモリは `beam_width * depth` 程度に収まる。
+pub fn beam_stack_search_action<S: SingleGameState + Ord>(state: &S, beam_width: usize, time_threshold: Duration) -> S::Action {
+    beam_stack_search_action_with_config(state, &SearchConfig::new().beam_width(beam_width).time_limit(time_threshold).build())
+}
+
+/// `config` のビーム幅で展開し、制限時間に達したら打ち切る (深さとノード数の上限は使わない)
+///
+/// 根は制限時間を過ぎていても展開する。合法手がない状態 (`is_dead_end`) では呼べない。
+pub fn beam_stack_search_action_with_config<S: SingleGameState + Ord>(state: &S, config: &SearchConfig) -> S::Action {
+    let beam_width = config.beam_width;
+    let time_keeper = TimeKeeper::new(config.time_limit);
+    let mut stack = vec![BinaryHeap::from([state.clone()])];
+    let mut best_state: Option<S> = None;
+    let mut first_best_state: Option<S> = None;
+    while let Some(layer) = stack.last_mut() {
+        if first_best_state.is_some() && time_keeper.is_time_over() { break; }
+        if layer.is_empty() {
+            stack.pop();
+            continue;
+        }
+        let t = stack.len() - 1;
+        let now_states = (0..beam_width).map_while(|_| stack[t].pop()).collect::<Vec<_>>();
+        let mut next_layer = BinaryHeap::new();
+        for now_state in now_states {
+            for action in now_state.legal_actions() {
+                let mut next_state = now_state.clone();
+                next_state.advance(action);
+                next_state.evaluate_score();
+                if t == 0 {
+                    next_state.set_first_action(action);
+                    if first_best_state.as_ref().is_none_or(|first_best_state| next_state > *first_best_state) {
+                        first_best_state = Some(next_state.clone());
+                    }
+                }
+                if next_state.is_done() {
+                    if best_state.as_ref().is_none_or(|best_state| next_state > *best_state) {
+                        best_state = Some(next_state);
+                    }
+                } else {
+                    next_layer.push(next_state);
+                }
+            }
+        }
+        if !next_layer.is_empty() {
+            stack.push(next_layer);
+        }
+    }
+    best_state.or(first_best_state).and_then(|best_state| best_state.first_action()).expect("no legal actions in a dead end")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::maze::{MazeParams, MazeState};
+    use super::*;
+
+    /// 制限時間が 0 でも根を展開して合法手を返す
+    #[test]
+    fn zero_time_limit_returns_legal_action() {
+        let state = MazeState::new(0, MazeParams { h: 5, w: 5, end_turn: 10 });
+        assert!(state.legal_actions().contains(&beam_stack_search_action(&state, 2, Duration::ZERO)));
+    }
+
+    #[test]
+    #[should_panic(expected = "no legal actions in a dead end")]
+    fn rejects_dead_end() {
+        let state = MazeState::from_board_string("5 0 0 #@#").unwrap();
+        beam_stack_search_action(&state, 2, Duration::from_millis(10));
+    }
+}
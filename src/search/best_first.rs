@@ -0,0 +1,63 @@
+use std::collections::BinaryHeap;
+use std::time::Duration;
+use crate::SingleGameState;
+use crate::search::config::SearchConfig;
+use crate::time_keeper::TimeKeeper;
+
+/// 深さに関係なく、1 つの優先度付きキューで最も評価の高い状態から展開する
+///
+/// 終了状態を取り出すか `node_limit` 個展開したら、その時点で最も評価の高い状態の最初の行動を返す。
+pub fn best_first_search_action<S: SingleGameState + Ord>(state: &S, node_limit: usize) -> S::Action {
+    best_first_search_action_with_config(state, &SearchConfig::new().node_limit(node_limit).time_limit(Duration::MAX).build())
+}
+
+/// `config` のノード数の上限か制限時間に達したら打ち切る (ビーム幅と深さは使わない)
+pub fn best_first_search_action_with_config<S: SingleGameState + Ord>(state: &S, config: &SearchConfig) -> S::Action {
+    let time_keeper = TimeKeeper::new(config.time_limit);
+    let mut heap = BinaryHeap::new();
+    heap.push(state.clone());
+    let mut node_count = 0;
+    let mut is_root = true;
+    while let Some(now_state) = heap.pop() {
+        if now_state.is_done() {
+            if let Some(action) = now_state.first_action() {
+                return action;
+            }
+            break;
+        }
+        for action in now_state.legal_actions() {
+            let mut next_state = now_state.clone();
+            next_state.advance(action);
+            next_state.evaluate_score();
+            if is_root {
+                next_state.set_first_action(action);
+            }
+            heap.push(next_state);
+        }
+        is_root = false;
+        node_count += 1;
+        if node_count >= config.node_limit || time_keeper.is_time_over() { break; }
+    }
+    heap.peek().and_then(|best_state| best_state.first_action()).expect("no legal actions in a dead end")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::maze::{Action, MazeState};
+    use super::*;
+
+    /// 渡した状態に最初の行動が残っていても根として扱い、子に最初の行動を付け直す
+    #[test]
+    fn root_with_stale_first_action() {
+        let mut state = MazeState::from_board_string("3 2 0 1@5").unwrap();
+        state.set_first_action(Action::Left);
+        assert_eq!(best_first_search_action(&state, 100), Action::Right);
+    }
+
+    #[test]
+    #[should_panic(expected = "no legal actions in a dead end")]
+    fn rejects_dead_end() {
+        let state = MazeState::from_board_string("5 0 0 #@#").unwrap();
+        best_first_search_action(&state, 100);
+    }
+}
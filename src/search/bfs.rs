@@ -0,0 +1,104 @@
+use std::time::Duration;
+use crate::SingleGameState;
+use crate::search::config::SearchConfig;
+use crate::time_keeper::TimeKeeper;
+
+/// 幅優先に 1 層ずつ全ての状態を展開し、評価値が最も高い葉に至る最初の行動を返す
+///
+/// 次の層の状態数が `max_states` を超えそうになった時点で次の層を作るのをやめ、今の層で最も評価の高い状態を使う。
+/// 終了状態や合法手がない状態 (`is_dead_end`) では呼べない。
+pub fn bfs_action<S: SingleGameState>(state: &S, max_states: usize) -> S::Action {
+    bfs_action_with_count(state, max_states).0
+}
+
+/// 選んだ行動と、展開したノードの数を返す
+pub fn bfs_action_with_count<S: SingleGameState>(state: &S, max_states: usize) -> (S::Action, u64) {
+    bfs(state, max_states, Duration::MAX)
+}
+
+/// 次の層の状態数の上限に `config.node_limit` を使い、制限時間を過ぎたら次の層を作るのをやめる (ビーム幅と深さは使わない)
+pub fn bfs_action_with_config<S: SingleGameState>(state: &S, config: &SearchConfig) -> S::Action {
+    bfs(state, config.node_limit, config.time_limit).0
+}
+
+fn bfs<S: SingleGameState>(state: &S, max_states: usize, time_limit: Duration) -> (S::Action, u64) {
+    assert!(!state.is_done(), "cannot choose an action from a finished state");
+    let time_keeper = TimeKeeper::new(time_limit);
+    let mut node_count = 0;
+    let mut now_layer = vec![state.clone()];
+    'search: for t in 0.. {
+        if now_layer.iter().all(|now_state| now_state.is_done()) { break; }
+        if t > 0 && time_keeper.is_time_over() { break; }
+        let mut next_layer = Vec::new();
+        for now_state in &now_layer {
+            let legal_actions = if now_state.is_done() { Vec::new() } else { now_state.legal_actions() };
+            if t > 0 && next_layer.len() + legal_actions.len().max(1) > max_states { break 'search; }
+            if now_state.is_done() {
+                next_layer.push(now_state.clone());
+                continue;
+            }
+            node_count += 1;
+            for action in legal_actions {
+                let mut next_state = now_state.clone();
+                next_state.advance(action);
+                next_state.evaluate_score();
+                if t == 0 {
+                    next_state.set_first_action(action);
+                }
+                next_layer.push(next_state);
+            }
+        }
+        now_layer = next_layer;
+    }
+    let mut best_score = None;
+    let mut best_action = None;
+    for now_state in &now_layer {
+        if best_score.is_none_or(|best_score| now_state.evaluated_score() > best_score) {
+            best_score = Some(now_state.evaluated_score());
+            best_action = now_state.first_action();
+        }
+    }
+    (best_action.expect("no legal actions in a dead end"), node_count)
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::rngs::SmallRng;
+    use rand::{RngCore, SeedableRng};
+    use crate::maze::{MazeParams, MazeState};
+    use crate::search::brute_force::brute_force_best_score;
+    use super::*;
+
+    /// 3x4 の盤面で、状態数の上限に届かなければ BFS だけで最後まで進めると全探索の最大値と同じ得点になる
+    #[test]
+    fn matches_brute_force_on_3x4() {
+        let params = MazeParams { h: 3, w: 4, end_turn: 4 };
+        let mut rng_for_construct = SmallRng::seed_from_u64(0);
+        for _ in 0..50 {
+            let mut state = MazeState::new(rng_for_construct.next_u64(), params);
+            let best_score = brute_force_best_score(&state);
+            while !state.is_done() {
+                state.advance(bfs_action(&state, 100_000));
+            }
+            assert_eq!(state.game_score(), best_score);
+        }
+    }
+
+    /// 次の層が上限を超えるときは作り切る前にやめる
+    #[test]
+    fn stops_before_exceeding_max_states() {
+        // 中央から 2 手以内は盤面内なので、どの状態も 4 つの子を持つ
+        let state = MazeState::from_board_string("10 0 0 ...../...../..@../...../.....").unwrap();
+        let (_, node_count) = bfs_action_with_count(&state, 20);
+        // 根と 1 層目の 4 ノードに加え、2 層目は子が 20 個に収まる 5 ノードだけを展開する
+        assert_eq!(node_count, 1 + 4 + 5);
+    }
+
+    /// 終了状態からは行動を選べない
+    #[test]
+    #[should_panic(expected = "cannot choose an action from a finished state")]
+    fn rejects_finished_state() {
+        let state = MazeState::from_board_string("3 3 0 1@5").unwrap();
+        bfs_action(&state, 100);
+    }
+}
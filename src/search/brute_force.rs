@@ -0,0 +1,69 @@
+use crate::SingleGameState;
+use crate::maze::{actions_from_mask, MazeState};
+
+/// `brute_force_best_score` で調べる行動列の数の上限
+const MAX_LEAF_NUMBER: u64 = 10_000_000;
+
+/// 終了までの全ての行動列を深さ優先で調べ、到達できる最大の得点を返す
+///
+/// 行動列の数 (移動できる方向の数の残りターン数乗) が `MAX_LEAF_NUMBER` を超える盤面や、移動コストが 0 のマスがある盤面では呼べない。
+pub fn brute_force_best_score(state: &MazeState) -> i32 {
+    assert!(!state.has_road(), "brute force cannot bound the depth with zero-cost cells");
+    let remaining_turn = (state.end_turn() - state.turn()).max(0) as u32;
+    let leaf_number = (state.direction_number() as u64).checked_pow(remaining_turn);
+    assert!(leaf_number.is_some_and(|leaf_number| leaf_number <= MAX_LEAF_NUMBER), "board is too large for brute force");
+    let mut state = state.clone();
+    best_score(&mut state)
+}
+
+fn best_score(state: &mut MazeState) -> i32 {
+    if state.is_done() {
+        return state.game_score();
+    }
+    let mut max_score = state.game_score();
+    for action in actions_from_mask(state.legal_actions_mask()) {
+        let record = state.advance_with_undo(action);
+        max_score = max_score.max(best_score(state));
+        state.undo(&record);
+    }
+    max_score
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::rngs::SmallRng;
+    use rand::{RngCore, SeedableRng};
+    use crate::maze::{Action, Coord, MazeParams};
+    use crate::search::beam::beam_search_action;
+    use crate::search::greedy::greedy_action;
+    use super::*;
+
+    fn play(mut state: MazeState, ai: impl Fn(&MazeState) -> Action) -> i32 {
+        while !state.is_done() {
+            state.advance(ai(&state));
+        }
+        state.game_score()
+    }
+
+    /// 小さい盤面で、貪欲法とビームサーチの得点は全探索の最大値を超えない
+    #[test]
+    fn never_exceeded_by_other_searches() {
+        let params = MazeParams { h: 4, w: 4, end_turn: 8 };
+        let mut rng_for_construct = SmallRng::seed_from_u64(0);
+        for _ in 0..20 {
+            let state = MazeState::new(rng_for_construct.next_u64(), params);
+            let best_score = brute_force_best_score(&state);
+            assert!(play(state.clone(), greedy_action) <= best_score);
+            assert!(play(state, |state| beam_search_action(state, 5, params.end_turn as usize)) <= best_score);
+        }
+    }
+
+    /// 得点が一直線に並んだ盤面では、貪欲法が全探索の最大値に一致する
+    #[test]
+    fn greedy_solves_straight_line() {
+        let state = MazeState::from_points(vec![vec![0, 1, 2, 3]], Coord { x: 0, y: 0 }, 3);
+        let best_score = brute_force_best_score(&state);
+        assert_eq!(best_score, 6);
+        assert_eq!(play(state, greedy_action), best_score);
+    }
+}
@@ -0,0 +1,50 @@
+use std::time::Duration;
+use crate::time_keeper::TimeKeeper;
+
+/// 探索を打ち切る条件 (`Nodes` は探索ごとに決めた単位で数える。Chokudai サーチなら `beam[t].pop()` の回数、MCTS ならシミュレーションの回数)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SearchBudget {
+    Time(Duration),
+    Nodes(usize),
+    Either(Duration, usize),
+}
+
+impl SearchBudget {
+    /// 時間の計測を始める
+    pub fn start(self) -> BudgetKeeper {
+        match self {
+            SearchBudget::Time(time_threshold) => BudgetKeeper { time_keeper: Some(TimeKeeper::new(time_threshold)), node_limit: None },
+            SearchBudget::Nodes(node_limit) => BudgetKeeper { time_keeper: None, node_limit: Some(node_limit) },
+            SearchBudget::Either(time_threshold, node_limit) => BudgetKeeper { time_keeper: Some(TimeKeeper::new(time_threshold)), node_limit: Some(node_limit) },
+        }
+    }
+}
+
+/// 計測を始めた `SearchBudget`
+#[derive(Debug, Clone, Copy)]
+pub struct BudgetKeeper {
+    time_keeper: Option<TimeKeeper>,
+    node_limit: Option<usize>,
+}
+
+impl BudgetKeeper {
+    /// 制限時間を過ぎたか、数えた量 `nodes` が上限に達したか
+    pub fn is_over(&self, nodes: usize) -> bool {
+        self.time_keeper.is_some_and(|time_keeper| time_keeper.is_time_over()) || self.node_limit.is_some_and(|node_limit| nodes >= node_limit)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn node_limit_counts_nodes() {
+        let keeper = SearchBudget::Nodes(3).start();
+        assert!(!keeper.is_over(2));
+        assert!(keeper.is_over(3));
+        assert!(SearchBudget::Either(Duration::from_secs(60), 3).start().is_over(3));
+        assert!(!SearchBudget::Time(Duration::from_secs(60)).start().is_over(usize::MAX));
+        assert!(SearchBudget::Time(Duration::ZERO).start().is_over(0));
+    }
+}
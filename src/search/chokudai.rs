@@ -0,0 +1,494 @@
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashSet};
+use std::sync::mpsc;
+use std::time::{Duration, Instant};
+use crate::SingleGameState;
+use crate::maze::HeuristicFn;
+use crate::search::beam::{EvalFn, Scored};
+use crate::search::budget::SearchBudget;
+use crate::search::config::SearchConfig;
+use crate::search::stats::SearchStats;
+use crate::time_keeper::TimeKeeper;
+
+/// 深さごとのビームから `beam_width` 個ずつ展開する走査を `beam_number` 回繰り返す
+pub fn chokudai_search_action<S: SingleGameState + Ord>(state: &S, beam_width: i32, beam_depth: usize, beam_number: i32) -> S::Action {
+    first_action(chokudai_search(state, to_width(beam_width), beam_depth, PassLimit::Passes(beam_number as u64), None, false, None).0)
+}
+
+/// `budget` に達するまで走査を繰り返す
+pub fn chokudai_search_action_with_budget<S: SingleGameState + Ord>(state: &S, beam_width: i32, beam_depth: usize, budget: SearchBudget, hash: Option<&dyn Fn(&S) -> u64>) -> S::Action {
+    chokudai_search_action_with_stats(state, beam_width, beam_depth, budget, hash).0
+}
+
+/// 選んだ行動と、探索にかけた手間 (制限内に最後の深さまで走査し終えた回数を含む) を返す
+pub fn chokudai_search_action_with_stats<S: SingleGameState + Ord>(state: &S, beam_width: i32, beam_depth: usize, budget: SearchBudget, hash: Option<&dyn Fn(&S) -> u64>) -> (S::Action, SearchStats) {
+    let (actions, stats) = chokudai_search_plan(state, beam_width, beam_depth, budget, hash, false);
+    (first_action(actions), stats)
+}
+
+/// 走査ごとに各深さを上位 `beam_width` 個に絞り、制限時間が長くてもビームに溜まる状態の数が増え続けないようにする
+pub fn chokudai_search_action_with_window<S: SingleGameState + Ord>(state: &S, beam_width: i32, beam_depth: usize, budget: SearchBudget) -> S::Action {
+    first_action(chokudai_search_plan(state, beam_width, beam_depth, budget, None, true).0)
+}
+
+/// `config` のビーム幅と深さで走査を繰り返し、制限時間かノード数の上限に達するか展開できる状態がなくなったら止める
+///
+/// `hash` を渡すと、各深さで既に積んだことのある盤面を重複して積まないようにする。
+pub fn chokudai_search_action_with_config<S: SingleGameState + Ord>(state: &S, config: &SearchConfig, hash: Option<&dyn Fn(&S) -> u64>) -> S::Action {
+    let budget = SearchBudget::Either(config.time_limit, config.node_limit);
+    first_action(chokudai_search(state, config.beam_width, config.beam_depth, PassLimit::Budget(budget), hash, false, None).0)
+}
+
+/// 展開するノード数で打ち切るので、実行環境によらず同じ行動を返す
+pub fn chokudai_search_action_with_node_limit<S: SingleGameState + Ord>(state: &S, beam_width: i32, beam_depth: usize, node_limit: usize) -> S::Action {
+    chokudai_search_action_with_budget(state, beam_width, beam_depth, SearchBudget::Nodes(node_limit), None)
+}
+
+/// 最も良い最終状態に至る行動列を丸ごと、探索にかけた手間と合わせて返す
+///
+/// `hash` を渡すと、各深さで既に積んだことのある盤面を重複して積まないようにする。
+/// `window` のときは走査を 1 回終えるごとに各深さの上位 `beam_width` 個だけを残し、ビームに溜まる状態の数を抑える。
+/// 合法手がない状態 (`is_dead_end`) では空の行動列を返す。
+pub fn chokudai_search_plan<S: SingleGameState + Ord>(state: &S, beam_width: i32, beam_depth: usize, budget: SearchBudget, hash: Option<&dyn Fn(&S) -> u64>, window: bool) -> (Vec<S::Action>, SearchStats) {
+    chokudai_search(state, to_width(beam_width), beam_depth, PassLimit::Budget(budget), hash, window, None)
+}
+
+/// 公開する関数で受け取ったビーム幅を、内部で使う `usize` にする
+fn to_width(beam_width: i32) -> usize {
+    assert!(beam_width > 0, "beam_width must be positive");
+    beam_width as usize
+}
+
+/// 合法手がない状態では行動を選べない
+fn first_action<A: Copy>(actions: Vec<A>) -> A {
+    *actions.first().expect("no legal actions in a dead end")
+}
+
+/// ビームに積む状態と、根からその状態に至るまでの行動列 (`Scored` と同じく、評価値が等しければ先に積んだほうが大きい)
+struct ScoredPlan<S: SingleGameState> {
+    scored: Scored<S>,
+    actions: Vec<S::Action>,
+}
+
+impl<S: SingleGameState> PartialEq for ScoredPlan<S> {
+    fn eq(&self, other: &Self) -> bool {
+        self.scored.eq(&other.scored)
+    }
+}
+
+impl<S: SingleGameState> Eq for ScoredPlan<S> {}
+
+impl<S: SingleGameState> PartialOrd for ScoredPlan<S> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<S: SingleGameState> Ord for ScoredPlan<S> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.scored.cmp(&other.scored)
+    }
+}
+
+/// `chokudai_search` で走査の繰り返しをやめる条件
+#[derive(Clone, Copy)]
+enum PassLimit {
+    /// 制限に達したらやめる
+    Budget(SearchBudget),
+    /// 走査をこの回数終えたらやめる (0 でも 1 回は走査する)
+    Passes(u64),
+}
+
+/// `limit` に達するか、展開できる状態がなくなるまで走査を繰り返す
+///
+/// 盤面は `eval` があればその評価値、なければ `evaluate_score` の評価値で並べる (等しければ先に生成した盤面を優先する)。
+/// ビーム幅と深さは 1 以上でなければならない。
+fn chokudai_search<S: SingleGameState>(state: &S, beam_width: usize, beam_depth: usize, limit: PassLimit, hash: Option<&dyn Fn(&S) -> u64>, window: bool, eval: Option<&EvalFn<'_, S>>) -> (Vec<S::Action>, SearchStats) {
+    assert!(beam_width > 0, "beam_width must be positive");
+    assert!(beam_depth > 0, "beam_depth must be positive");
+    let start = Instant::now();
+    let (budget_keeper, pass_limit) = match limit {
+        PassLimit::Budget(budget) => (Some(budget.start()), None),
+        PassLimit::Passes(passes) => (None, Some(passes)),
+    };
+    let mut stats = SearchStats::default();
+    let score = |state: &mut S| match eval {
+        Some(eval) => eval(state),
+        None => {
+            state.evaluate_score();
+            state.evaluated_score()
+        }
+    };
+    let mut order = 0;
+    let mut nodes_expanded = 0;
+    let mut beam = (0..=beam_depth).map(|_| BinaryHeap::new()).collect::<Vec<_>>();
+    let mut seen = vec![HashSet::new(); beam_depth + 1];
+    let mut root_state = state.clone();
+    beam[0].push(ScoredPlan { scored: Scored::new(score(&mut root_state), order, root_state), actions: Vec::new() });
+    loop {
+        let last_nodes_expanded = nodes_expanded;
+        for t in 0..beam_depth {
+            for _ in 0..beam_width {
+                if beam[t].is_empty() { break; }
+                if beam[t].peek().unwrap().scored.state.is_done() { break; }
+                let now_node = beam[t].pop().unwrap();
+                nodes_expanded += 1;
+                stats.max_depth_reached = stats.max_depth_reached.max(t + 1);
+                let legal_actions = now_node.scored.state.legal_actions();
+                for action in legal_actions {
+                    let mut next_state = now_node.scored.state.clone();
+                    next_state.advance(action);
+                    let next_score = score(&mut next_state);
+                    if let Some(hash) = hash {
+                        if !seen[t + 1].insert(hash(&next_state)) { continue; }
+                    }
+                    let mut actions = now_node.actions.clone();
+                    actions.push(action);
+                    order += 1;
+                    beam[t + 1].push(ScoredPlan { scored: Scored::new(next_score, order, next_state), actions });
+                    stats.states_pushed += 1;
+                }
+            }
+        }
+        if nodes_expanded == last_nodes_expanded {
+            break;
+        }
+        stats.passes_completed += 1;
+        stats.peak_states = stats.peak_states.max(beam.iter().map(|beam| beam.len()).sum());
+        if window {
+            for beam in beam.iter_mut() {
+                let mut kept = BinaryHeap::new();
+                for _ in 0..beam_width {
+                    let Some(node) = beam.pop() else { break; };
+                    kept.push(node);
+                }
+                *beam = kept;
+            }
+        }
+        if budget_keeper.is_some_and(|budget_keeper| budget_keeper.is_over(nodes_expanded)) || pass_limit.is_some_and(|pass_limit| stats.passes_completed >= pass_limit) {
+            break;
+        }
+    }
+    stats.nodes_expanded = nodes_expanded as u64;
+    stats.elapsed = start.elapsed();
+    for t in (1..=beam_depth).rev() {
+        if let Some(node) = beam[t].peek() {
+            return (node.actions.clone(), stats);
+        }
+    }
+    (Vec::new(), stats)
+}
+
+/// 各深さで取り出した `beam_width` 個の状態の展開を、探索の始めに立てた `thread_number` 個の作業スレッドに分ける
+///
+/// 取り出した状態は順に `thread_number` 個のかたまりに分けて各スレッドに渡す。
+/// `deterministic` のときはかたまりの順に結果を積むので、1 スレッドなら `chokudai_search_action_with_budget` と同じ行動を返す。
+/// そうでないときは終わったスレッドから順に積むので、同点の状態の順序が実行ごとに変わりうる。
+pub fn parallel_chokudai_search_action<S>(state: &S, beam_width: i32, beam_depth: usize, budget: SearchBudget, thread_number: usize, deterministic: bool) -> S::Action
+where
+    S: SingleGameState + Ord + Send + Sync,
+    S::Action: Send,
+{
+    assert!(thread_number > 0, "thread_number must be positive");
+    let expand = |t: usize, now_states: Vec<S>| {
+        let mut next_states = Vec::new();
+        for now_state in now_states {
+            for action in now_state.legal_actions() {
+                let mut next_state = now_state.clone();
+                next_state.advance(action);
+                next_state.evaluate_score();
+                if t == 0 {
+                    next_state.set_first_action(action);
+                }
+                next_states.push(next_state);
+            }
+        }
+        next_states
+    };
+    std::thread::scope(|scope| {
+        let (result_sender, result_receiver) = mpsc::channel();
+        let job_senders = (0..thread_number).map(|_| {
+            let (job_sender, job_receiver) = mpsc::channel::<(usize, usize, Vec<S>)>();
+            let result_sender = result_sender.clone();
+            scope.spawn(move || {
+                for (index, t, now_states) in job_receiver {
+                    result_sender.send((index, expand(t, now_states))).unwrap();
+                }
+            });
+            job_sender
+        }).collect::<Vec<_>>();
+        let budget_keeper = budget.start();
+        let mut order = 0;
+        let mut nodes_expanded = 0;
+        let mut beam = (0..=beam_depth).map(|_| BinaryHeap::new()).collect::<Vec<_>>();
+        let mut root_state = state.clone();
+        root_state.evaluate_score();
+        beam[0].push(Scored::new(root_state.evaluated_score(), order, root_state));
+        loop {
+            let last_nodes_expanded = nodes_expanded;
+            for t in 0..beam_depth {
+                let mut now_states = Vec::new();
+                for _ in 0..beam_width {
+                    if beam[t].is_empty() { break; }
+                    if beam[t].peek().unwrap().state.is_done() { break; }
+                    now_states.push(beam[t].pop().unwrap().state);
+                    nodes_expanded += 1;
+                }
+                if now_states.is_empty() { continue; }
+                let chunk_size = now_states.len().div_ceil(thread_number);
+                let mut job_number = 0;
+                while !now_states.is_empty() {
+                    let rest = now_states.split_off(chunk_size.min(now_states.len()));
+                    job_senders[job_number].send((job_number, t, std::mem::replace(&mut now_states, rest))).unwrap();
+                    job_number += 1;
+                }
+                let mut results = (0..job_number).map(|_| None).collect::<Vec<_>>();
+                for _ in 0..job_number {
+                    let (index, next_states) = result_receiver.recv().unwrap();
+                    if deterministic {
+                        results[index] = Some(next_states);
+                    } else {
+                        for next_state in next_states {
+                            order += 1;
+                            beam[t + 1].push(Scored::new(next_state.evaluated_score(), order, next_state));
+                        }
+                    }
+                }
+                for next_state in results.into_iter().flatten().flatten() {
+                    order += 1;
+                    beam[t + 1].push(Scored::new(next_state.evaluated_score(), order, next_state));
+                }
+            }
+            if nodes_expanded == last_nodes_expanded {
+                break;
+            }
+            if budget_keeper.is_over(nodes_expanded) {
+                break;
+            }
+        }
+        for t in (1..=beam_depth).rev() {
+            if let Some(scored) = beam[t].peek() {
+                return scored.state.first_action().unwrap();
+            }
+        }
+        panic!("no legal actions in a dead end")
+    })
+}
+
+/// 1 回の走査で深さ `t` に割り当てる展開数を時間の経過に合わせて変える Chokudai サーチ
+///
+/// 1 回の走査の展開数は合わせて `beam_depth` 個で、始めは `beam_depth - t` に比例して浅い深さに多く、
+/// 制限時間の経過した割合 `f` に応じて `(1 - f) * (beam_depth - t) + f * (t + 1)` に比例するように深い深さへ寄せていく。
+/// 1 個に満たない割り当ては持ち越して、溜まった分だけ展開する。展開できる状態がない深さには 1 個分までしか溜めない。
+/// 深さは 1 以上でなければならず、合法手がない状態 (`is_dead_end`) では呼べない。
+pub fn chokudai_search_adaptive<S: SingleGameState + Ord>(state: &S, beam_depth: usize, time_threshold: Duration) -> S::Action {
+    assert!(beam_depth > 0, "beam_depth must be positive");
+    let time_keeper = TimeKeeper::new(time_threshold);
+    let mut beam = vec![BinaryHeap::new(); beam_depth + 1];
+    let mut credits = vec![0.0; beam_depth];
+    let weight_sum = (beam_depth * (beam_depth + 1) / 2) as f64;
+    beam[0].push(state.clone());
+    loop {
+        let elapsed_fraction = time_keeper.elapsed_fraction().min(1.0);
+        let mut expanded = false;
+        for t in 0..beam_depth {
+            let weight = (1.0 - elapsed_fraction) * (beam_depth - t) as f64 + elapsed_fraction * (t + 1) as f64;
+            credits[t] += weight / weight_sum * beam_depth as f64;
+            while credits[t] >= 1.0 {
+                if beam[t].peek().is_none_or(|state| state.is_done()) {
+                    credits[t] = 1.0;
+                    break;
+                }
+                credits[t] -= 1.0;
+                let now_state = beam[t].pop().unwrap();
+                expanded = true;
+                for action in now_state.legal_actions() {
+                    let mut next_state = now_state.clone();
+                    next_state.advance(action);
+                    next_state.evaluate_score();
+                    if t == 0 {
+                        next_state.set_first_action(action);
+                    }
+                    beam[t + 1].push(next_state);
+                }
+            }
+        }
+        if time_keeper.is_time_over() { break; }
+        if !expanded && beam[..beam_depth].iter().all(|beam| beam.peek().is_none_or(|state| state.is_done())) { break; }
+    }
+    for t in (1..=beam_depth).rev() {
+        if let Some(state) = beam[t].peek() {
+            return state.first_action().unwrap();
+        }
+    }
+    panic!("no legal actions in a dead end")
+}
+
+/// 盤面を `heuristic` で評価する Chokudai サーチ (`None` なら `evaluate_score` で評価する)
+pub fn chokudai_search_action_with_heuristic<S: SingleGameState + Ord>(state: &S, beam_width: i32, beam_depth: usize, beam_number: i32, heuristic: Option<HeuristicFn<S>>) -> S::Action {
+    match heuristic {
+        Some(heuristic) => chokudai_search_action_with_eval(state, beam_width, beam_depth, beam_number, &heuristic),
+        None => chokudai_search_action(state, beam_width, beam_depth, beam_number),
+    }
+}
+
+/// 盤面の評価に `evaluate_score` の代わりに `eval` を使う Chokudai サーチ (評価値が等しければ先に生成した盤面を優先する)
+pub fn chokudai_search_action_with_eval<S: SingleGameState>(state: &S, beam_width: i32, beam_depth: usize, beam_number: i32, eval: &dyn Fn(&S) -> S::ScoreType) -> S::Action {
+    first_action(chokudai_search(state, to_width(beam_width), beam_depth, PassLimit::Passes(beam_number as u64), None, false, Some(eval)).0)
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::rngs::SmallRng;
+    use rand::{RngCore, SeedableRng};
+    use crate::maze::{MazeParams, MazeState, ZobristTable};
+    use super::*;
+
+    /// ノード数の上限は走査ごとに確かめるので展開数は少しずれるが、展開 1 回あたりにビームに積む状態は Zobrist ハッシュで重複を除くと減る
+    #[test]
+    fn zobrist_dedup_pushes_fewer_states() {
+        let params = MazeParams { h: 10, w: 10, end_turn: 30 };
+        let hash = |state: &MazeState| state.zobrist_hash();
+        let mut rng_for_construct = SmallRng::seed_from_u64(0);
+        for _ in 0..5 {
+            let state = MazeState::new(rng_for_construct.next_u64(), params);
+            let budget = SearchBudget::Nodes(500);
+            let (_, plain) = chokudai_search_action_with_stats(&state, 2, params.end_turn as usize, budget, None);
+            let (_, deduped) = chokudai_search_action_with_stats(&state, 2, params.end_turn as usize, budget, Some(&hash));
+            assert!(deduped.states_pushed * plain.nodes_expanded < plain.states_pushed * deduped.nodes_expanded);
+        }
+    }
+
+    /// 同じノード数の上限で 1 局を通して探索すると、`ZobristTable` のハッシュで重複を除いたほうがビームに積む状態の合計は少ない
+    #[test]
+    fn zobrist_table_dedup_pushes_fewer_states_over_a_game() {
+        let params = MazeParams { h: 10, w: 10, end_turn: 30 };
+        let table = ZobristTable::new(params.h, params.w, params.end_turn, 0);
+        let hash = |state: &MazeState| state.hash(&table);
+        let budget = SearchBudget::Nodes(200);
+        let mut rng_for_construct = SmallRng::seed_from_u64(0);
+        for _ in 0..3 {
+            let mut state = MazeState::new(rng_for_construct.next_u64(), params);
+            let (mut plain_pushed, mut deduped_pushed) = (0, 0);
+            while !state.is_done() {
+                let (action, plain) = chokudai_search_action_with_stats(&state, 1, params.end_turn as usize, budget, None);
+                let (_, deduped) = chokudai_search_action_with_stats(&state, 1, params.end_turn as usize, budget, Some(&hash));
+                plain_pushed += plain.states_pushed;
+                deduped_pushed += deduped.states_pushed;
+                state.advance(action);
+            }
+            assert!(deduped_pushed < plain_pushed, "{deduped_pushed} >= {plain_pushed}");
+        }
+    }
+
+    /// ノード数で打ち切れば結果は決定的なので、1 スレッドで決定的に動かした並列版は毎手直列版と同じ行動を返す
+    ///
+    /// 2 スレッドで順序を決めずに動かしても合法手を返す。
+    #[test]
+    fn parallel_matches_serial_with_one_thread() {
+        let params = MazeParams { h: 10, w: 10, end_turn: 30 };
+        let (beam_width, beam_depth, node_limit) = (2, params.end_turn as usize, 200);
+        let mut rng_for_construct = SmallRng::seed_from_u64(0);
+        for _ in 0..3 {
+            let mut state = MazeState::new(rng_for_construct.next_u64(), params);
+            while !state.is_done() {
+                let serial_action = chokudai_search_action_with_node_limit(&state, beam_width, beam_depth, node_limit);
+                let parallel_action = parallel_chokudai_search_action(&state, beam_width, beam_depth, SearchBudget::Nodes(node_limit), 1, true);
+                assert_eq!(parallel_action, serial_action);
+                let threaded_action = parallel_chokudai_search_action(&state, beam_width, beam_depth, SearchBudget::Nodes(node_limit), 2, false);
+                assert!(state.legal_actions().contains(&threaded_action));
+                state.advance(serial_action);
+            }
+        }
+    }
+
+    /// 制限時間で打ち切っても、最初の走査は最後まで終えるので、手間はどれも 0 にならない
+    #[test]
+    fn stats_count_work_under_time_budget() {
+        let params = MazeParams { h: 30, w: 30, end_turn: 100 };
+        let mut state = MazeState::new(0, params);
+        for _ in 0..10 {
+            let (action, stats) = chokudai_search_action_with_stats(&state, 1, params.end_turn as usize, SearchBudget::Time(Duration::from_millis(5)), None);
+            assert!(stats.nodes_expanded > 0 && stats.max_depth_reached > 0 && stats.passes_completed > 0 && stats.elapsed > Duration::ZERO, "{stats:?}");
+            state.advance(action);
+        }
+    }
+
+    /// 何度も走査すると、`window` ならビームに溜まる状態の数は `(beam_depth + 1) * beam_width * (1 + 行動の数)`
+    /// (残した分と 1 回の走査で積む分) を超えず、`window` でなければその数を超えて溜まる
+    #[test]
+    fn window_bounds_beam() {
+        let params = MazeParams { h: 30, w: 30, end_turn: 100 };
+        let state = MazeState::new(0, params);
+        let (beam_width, beam_depth) = (2, params.end_turn as usize);
+        let bound = (beam_depth + 1) * beam_width as usize * (1 + 4);
+        let budget = SearchBudget::Nodes(20_000);
+        let (_, unbounded) = chokudai_search_plan(&state, beam_width, beam_depth, budget, None, false);
+        let (_, windowed) = chokudai_search_plan(&state, beam_width, beam_depth, budget, None, true);
+        assert!(windowed.peak_states <= bound, "{} > {bound}", windowed.peak_states);
+        assert!(unbounded.peak_states > bound, "{} <= {bound}", unbounded.peak_states);
+    }
+
+    /// 得点をそのまま返す評価関数を渡すと `evaluate_score` で評価したときと同じ行動を返し、走査回数が 0 でも 1 回は走査する
+    #[test]
+    fn eval_matches_default_evaluation() {
+        let params = MazeParams { h: 10, w: 10, end_turn: 30 };
+        let eval = |state: &MazeState| state.game_score() as i64;
+        let mut rng_for_construct = SmallRng::seed_from_u64(0);
+        for _ in 0..5 {
+            let state = MazeState::new(rng_for_construct.next_u64(), params);
+            for beam_number in [0, 1, 3] {
+                assert_eq!(chokudai_search_action_with_eval(&state, 2, params.end_turn as usize, beam_number, &eval), chokudai_search_action(&state, 2, params.end_turn as usize, beam_number));
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "no legal actions in a dead end")]
+    fn eval_rejects_dead_end() {
+        let state = MazeState::from_board_string("5 0 0 #@#").unwrap();
+        chokudai_search_action_with_eval(&state, 2, 5, 1, &|state: &MazeState| state.game_score() as i64);
+    }
+
+    #[test]
+    #[should_panic(expected = "no legal actions in a dead end")]
+    fn adaptive_rejects_dead_end() {
+        let state = MazeState::from_board_string("5 0 0 #@#").unwrap();
+        chokudai_search_adaptive(&state, 5, Duration::from_millis(1));
+    }
+
+    /// 組み立てずに作った設定でも、ビーム幅や深さが 0 なら `SearchConfig::build` と同じ理由で止まる
+    #[test]
+    #[should_panic(expected = "beam_width must be positive")]
+    fn config_rejects_zero_width() {
+        let config = SearchConfig { beam_width: 0, ..SearchConfig::new() };
+        chokudai_search_action_with_config(&MazeState::new(0, MazeParams { h: 3, w: 4, end_turn: 4 }), &config, None);
+    }
+
+    #[test]
+    #[should_panic(expected = "beam_depth must be positive")]
+    fn config_rejects_zero_depth() {
+        let config = SearchConfig { beam_depth: 0, ..SearchConfig::new() };
+        chokudai_search_action_with_config(&MazeState::new(0, MazeParams { h: 3, w: 4, end_turn: 4 }), &config, None);
+    }
+
+    #[test]
+    #[should_panic(expected = "beam_width must be positive")]
+    fn rejects_zero_width() {
+        chokudai_search_action(&MazeState::new(0, MazeParams { h: 3, w: 4, end_turn: 4 }), 0, 4, 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "beam_depth must be positive")]
+    fn adaptive_rejects_zero_depth() {
+        chokudai_search_adaptive(&MazeState::new(0, MazeParams { h: 3, w: 4, end_turn: 4 }), 0, Duration::from_millis(1));
+    }
+
+    /// スレッド数が 0 では展開を分けられない
+    #[test]
+    #[should_panic(expected = "thread_number must be positive")]
+    fn parallel_rejects_zero_threads() {
+        let state = MazeState::new(0, MazeParams { h: 3, w: 4, end_turn: 4 });
+        parallel_chokudai_search_action(&state, 2, 4, SearchBudget::Nodes(10), 0, true);
+    }
+}
@@ -0,0 +1,59 @@
+use std::time::Duration;
+
+/// 探索の設定
+///
+/// `SearchConfig::new().beam_width(5).time_limit(Duration::from_millis(10)).build()` のように、変えたい項目だけを指定する。
+/// 各探索は自分の使う項目だけを読む (深さを決めて読む探索は `beam_depth` を読む深さの上限に使う)。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SearchConfig {
+    pub beam_width: usize,
+    pub beam_depth: usize,
+    pub time_limit: Duration,
+    /// 展開するノードの数の上限
+    pub node_limit: usize,
+}
+
+impl Default for SearchConfig {
+    /// `07` の既定値 (ビーム幅 1、深さ 100、10 ms) で、ノード数は制限しない
+    fn default() -> Self {
+        Self {
+            beam_width: 1,
+            beam_depth: 100,
+            time_limit: Duration::from_millis(10),
+            node_limit: usize::MAX,
+        }
+    }
+}
+
+impl SearchConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn beam_width(mut self, beam_width: usize) -> Self {
+        self.beam_width = beam_width;
+        self
+    }
+
+    pub fn beam_depth(mut self, beam_depth: usize) -> Self {
+        self.beam_depth = beam_depth;
+        self
+    }
+
+    pub fn time_limit(mut self, time_limit: Duration) -> Self {
+        self.time_limit = time_limit;
+        self
+    }
+
+    pub fn node_limit(mut self, node_limit: usize) -> Self {
+        self.node_limit = node_limit;
+        self
+    }
+
+    /// ビーム幅と深さが 1 以上であることを確かめる
+    pub fn build(self) -> Self {
+        assert!(self.beam_width > 0, "beam_width must be positive");
+        assert!(self.beam_depth > 0, "beam_depth must be positive");
+        self
+    }
+}
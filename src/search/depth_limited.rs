@@ -0,0 +1,23 @@
+use crate::SingleGameState;
+use crate::maze::HeuristicFn;
+use crate::search::iterative_deepening::depth_limited_search;
+
+/// 深さ `depth` (1 以上) まで全ての合法手を展開して葉を `heuristic` で評価し、葉の評価値の最大値が最も大きい初手を返す
+///
+/// 深さ `depth` に届く前に終了した状態はそこで葉として評価する。合法手がない状態 (`is_dead_end`) では呼べない。
+pub fn depth_limited_action<S: SingleGameState>(state: &S, depth: usize, heuristic: HeuristicFn<S>) -> S::Action {
+    assert!(depth > 0, "depth must be positive");
+    let mut best_value = None;
+    let mut best_action = None;
+    for action in state.legal_actions() {
+        let mut next_state = state.clone();
+        next_state.advance(action);
+        next_state.evaluate_score();
+        let (value, _) = depth_limited_search(&next_state, depth - 1, heuristic);
+        if best_value.is_none_or(|best_value| value > best_value) {
+            best_value = Some(value);
+            best_action = Some(action);
+        }
+    }
+    best_action.expect("no legal actions in a dead end")
+}
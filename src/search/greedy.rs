@@ -0,0 +1,151 @@
+use rand::rngs::SmallRng;
+use rand::Rng;
+use crate::SingleGameState;
+use crate::maze::HeuristicFn;
+
+/// 合法手がない状態 (`is_dead_end`) では呼べない
+pub fn greedy_action<S: SingleGameState>(state: &S) -> S::Action {
+    greedy_action_with_heuristic(state, None)
+}
+
+/// 盤面を `heuristic` で評価する貪欲法 (`None` なら `evaluate_score` で評価する)
+pub fn greedy_action_with_heuristic<S: SingleGameState>(state: &S, heuristic: Option<HeuristicFn<S>>) -> S::Action {
+    let legal_actions = state.legal_actions();
+    let mut best_score = None;
+    let mut best_action = None;
+    for action in legal_actions {
+        let mut now_state = state.clone();
+        now_state.advance(action);
+        let score = match heuristic {
+            Some(heuristic) => heuristic(&now_state),
+            None => {
+                now_state.evaluate_score();
+                now_state.evaluated_score()
+            }
+        };
+        if best_score.is_none_or(|best_score| score > best_score) {
+            best_score = Some(score);
+            best_action = Some(action);
+        }
+    }
+    best_action.expect("no legal actions in a dead end")
+}
+
+/// 直後の評価値が最大の行動が複数あるときは、その先の 1 手で得られる評価値の最大値が大きいほうを選ぶ
+pub fn greedy_action_with_tiebreak<S: SingleGameState>(state: &S) -> S::Action {
+    let legal_actions = state.legal_actions();
+    let mut best_score = None;
+    let mut best_action = None;
+    for action in legal_actions {
+        let mut now_state = state.clone();
+        now_state.advance(action);
+        now_state.evaluate_score();
+        let follow_up_score = if now_state.is_done() {
+            None
+        } else {
+            now_state.legal_actions().into_iter().map(|next_action| {
+                let mut next_state = now_state.clone();
+                next_state.advance(next_action);
+                next_state.evaluate_score();
+                next_state.evaluated_score()
+            }).max()
+        };
+        let score = (now_state.evaluated_score(), follow_up_score);
+        if best_score.is_none_or(|best_score| score > best_score) {
+            best_score = Some(score);
+            best_action = Some(action);
+        }
+    }
+    best_action.expect("no legal actions in a dead end")
+}
+
+/// 評価値に `noise_scale * U[0,1]` の雑音を加えて比べる貪欲法で終局まで進めることを `restarts` 回やり直し、最終的な評価値が最も高い進め方の最初の行動を選ぶ
+///
+/// 雑音は行動を選ぶときの比較にだけ使い、状態の評価値には残さない。
+pub fn random_restart_greedy_action<S: SingleGameState<ScoreType = i64>>(state: &S, restarts: u32, noise_scale: f64, rng: &mut SmallRng) -> S::Action {
+    assert!(restarts > 0, "restarts must be positive");
+    let mut best_score = None;
+    let mut best_action = None;
+    for _ in 0..restarts {
+        let mut now_state = state.clone();
+        let mut first_action = None;
+        while !now_state.is_done() && !now_state.is_dead_end() {
+            let mut best_noisy_score = None;
+            let mut best_next_state = None;
+            for action in now_state.legal_actions() {
+                let mut next_state = now_state.clone();
+                next_state.advance(action);
+                next_state.evaluate_score();
+                let noisy_score = next_state.evaluated_score() as f64 + noise_scale * rng.gen::<f64>();
+                if best_noisy_score.is_none_or(|best_noisy_score| noisy_score > best_noisy_score) {
+                    best_noisy_score = Some(noisy_score);
+                    best_next_state = Some((action, next_state));
+                }
+            }
+            let (action, next_state) = best_next_state.unwrap();
+            first_action.get_or_insert(action);
+            now_state = next_state;
+        }
+        let Some(first_action) = first_action else { break; };
+        now_state.evaluate_score();
+        if best_score.is_none_or(|best_score| now_state.evaluated_score() > best_score) {
+            best_score = Some(now_state.evaluated_score());
+            best_action = Some(first_action);
+        }
+    }
+    best_action.expect("no legal actions in a dead end")
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::SeedableRng;
+    use crate::SingleGameState;
+    use crate::maze::{heuristic_game_score, heuristic_reachable_points, Action, Coord, MazeState};
+    use super::*;
+
+    /// 1x3 の盤面で、右端にある唯一の得点を取り切る
+    #[test]
+    fn greedy_collects_single_point() {
+        let mut state = MazeState::from_points(vec![vec![0, 0, 5]], Coord { x: 0, y: 0 }, 2);
+        let mut actions = Vec::new();
+        while !state.is_done() {
+            let action = greedy_action(&state);
+            actions.push(action);
+            state.advance(action);
+        }
+        assert_eq!(actions, vec![Action::Right, Action::Right]);
+        assert_eq!(state.game_score(), 5);
+    }
+
+    /// 左右とも隣は 0 点だが、その先は左が 9 点、右が 1 点の盤面で、
+    /// 方向の順に右を選ぶ貪欲法より、1 手先で比べる貪欲法のほうが多く取る
+    #[test]
+    fn greedy_tiebreak_looks_ahead() {
+        let initial_state = MazeState::from_points(vec![vec![0, 9, 0, 0, 0, 1]], Coord { x: 3, y: 0 }, 2);
+        let play = |ai: fn(&MazeState) -> Action| {
+            let mut state = initial_state.clone();
+            while !state.is_done() {
+                state.advance(ai(&state));
+            }
+            state.game_score()
+        };
+        assert_eq!((play(greedy_action), play(greedy_action_with_tiebreak)), (1, 9));
+    }
+
+    /// 左隣は 1 点、右隣は 0 点だがその先に 9 点がある残り 2 ターンの盤面で、
+    /// 得点だけを見ると左を、残りのターンで届く得点も数えると右を選ぶ
+    #[test]
+    fn greedy_with_heuristic() {
+        let state = MazeState::from_points(vec![vec![1, 0, 0, 9]], Coord { x: 1, y: 0 }, 2);
+        assert_eq!(greedy_action_with_heuristic(&state, None), Action::Left);
+        assert_eq!(greedy_action_with_heuristic(&state, Some(heuristic_game_score)), Action::Left);
+        assert_eq!(greedy_action_with_heuristic(&state, Some(heuristic_reachable_points)), Action::Right);
+    }
+
+    #[test]
+    #[should_panic(expected = "restarts must be positive")]
+    fn random_restart_rejects_zero_restarts() {
+        let state = MazeState::from_points(vec![vec![1, 0, 0, 9]], Coord { x: 1, y: 0 }, 2);
+        random_restart_greedy_action(&state, 0, 1.0, &mut SmallRng::seed_from_u64(0));
+    }
+}
@@ -0,0 +1,163 @@
+use rand::rngs::SmallRng;
+use crate::SingleGameState;
+use crate::auto_move_maze::AutoMoveMazeState;
+use crate::search::simulated_annealing::{mutate, play_actions};
+
+/// `hill_climbing_action` で 1 回のやり直しごとに試す変異の回数
+const MUTATION_NUMBER: u32 = 100;
+
+pub fn hill_climb<const H: usize, const W: usize, const END_TURN: i32, const CHARACTER_N: usize>(state: &AutoMoveMazeState<H, W, END_TURN, CHARACTER_N>, number: usize, rng: &mut SmallRng) -> AutoMoveMazeState<H, W, END_TURN, CHARACTER_N> {
+    let mut now_state = state.clone();
+    now_state.init(rng);
+    let mut best_score = now_state.get_score();
+    for _ in 0..number {
+        let mut next_state = now_state.clone();
+        next_state.transition(rng);
+        let next_score = next_state.get_score();
+        if next_score > best_score {
+            best_score = next_score;
+            now_state = next_state;
+        }
+    }
+    now_state
+}
+
+/// `hill_climb_with_strategy` で、キャラクターを 1 人別のマスに移す近傍のどれに移るか
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HillClimbStrategy {
+    /// 決まった順に近傍を調べ、最初に得点が上がった近傍に移る
+    FirstImproving,
+    /// 全ての近傍を調べ、得点が最も上がった近傍に移る
+    BestImproving,
+}
+
+/// ランダムな配置から始め、キャラクターを 1 人別のマスに移す近傍を全て調べても得点が上がらなくなるか `number` 回移るまで山登りする
+///
+/// 最後の配置と、移った回数と、得点を計算した回数を返す。
+pub fn hill_climb_with_strategy<const H: usize, const W: usize, const END_TURN: i32, const CHARACTER_N: usize>(state: &AutoMoveMazeState<H, W, END_TURN, CHARACTER_N>, strategy: HillClimbStrategy, number: u32, rng: &mut SmallRng) -> (AutoMoveMazeState<H, W, END_TURN, CHARACTER_N>, u32, u64) {
+    let mut now_state = state.clone();
+    now_state.init(rng);
+    hill_climb_from(now_state, strategy, number)
+}
+
+/// `hill_climb_with_strategy` を、配置を決めた `state` から乱数を使わずに始める
+pub fn hill_climb_from<const H: usize, const W: usize, const END_TURN: i32, const CHARACTER_N: usize>(state: AutoMoveMazeState<H, W, END_TURN, CHARACTER_N>, strategy: HillClimbStrategy, number: u32) -> (AutoMoveMazeState<H, W, END_TURN, CHARACTER_N>, u32, u64) {
+    let mut now_state = state;
+    let mut now_score = now_state.get_score();
+    let mut evaluation_count = 0;
+    for iteration in 0..number {
+        let mut best_next = None;
+        'neighbors: for character_id in 0..CHARACTER_N {
+            for y in 0..H {
+                for x in 0..W {
+                    let mut next_state = now_state.clone();
+                    next_state.set_character(character_id, y, x);
+                    let next_score = next_state.get_score();
+                    evaluation_count += 1;
+                    if next_score > best_next.as_ref().map_or(now_score, |(best_score, _)| *best_score) {
+                        best_next = Some((next_score, next_state));
+                        if strategy == HillClimbStrategy::FirstImproving {
+                            break 'neighbors;
+                        }
+                    }
+                }
+            }
+        }
+        let Some((next_score, next_state)) = best_next else {
+            return (now_state, iteration, evaluation_count);
+        };
+        now_state = next_state;
+        now_score = next_score;
+    }
+    (now_state, number, evaluation_count)
+}
+
+/// ランダムな行動列から始め、1 ターン分の変異で良くなったときだけ受け入れる山登りを `restarts` 回やり直す
+///
+/// 終了状態や合法手がない状態 (`is_dead_end`) では呼べない。
+pub fn hill_climbing_action<S>(state: &S, restarts: u32, rng: &mut SmallRng) -> S::Action
+where
+    S: SingleGameState<ScoreType = i64>,
+    S::Action: PartialEq,
+{
+    assert!(restarts > 0, "restarts must be positive");
+    assert!(!state.is_done() && !state.is_dead_end(), "cannot choose an action from a finished state");
+    let mut best_actions = Vec::new();
+    let mut best_score = None;
+    for _ in 0..restarts {
+        let mut now_actions = Vec::new();
+        let mut now_score = play_actions(state, &mut now_actions, rng);
+        for _ in 0..MUTATION_NUMBER {
+            let mut next_actions = now_actions.clone();
+            mutate(state, &mut next_actions, rng);
+            let next_score = play_actions(state, &mut next_actions, rng);
+            if next_score > now_score {
+                now_actions = next_actions;
+                now_score = next_score;
+            }
+        }
+        if best_score.is_none_or(|best_score| now_score > best_score) {
+            best_actions = now_actions;
+            best_score = Some(now_score);
+        }
+    }
+    best_actions[0]
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{RngCore, SeedableRng};
+    use crate::maze::{Action, MazeState};
+    use super::*;
+
+    type State = AutoMoveMazeState<5, 5, 5, 3>;
+
+    /// 同じ乱数で回数だけ変えた山登りは前の回数の続きなので、受け入れた移動で得点が下がらなければ回数を増やしても得点は下がらない
+    #[test]
+    fn hill_climb_score_is_non_decreasing() {
+        let mut rng_for_construct = SmallRng::seed_from_u64(0);
+        for _ in 0..10 {
+            let seed = rng_for_construct.next_u64();
+            let state = State::new(seed);
+            let scores = (0..50).map(|number| hill_climb(&state, number, &mut SmallRng::seed_from_u64(seed)).get_score()).collect::<Vec<_>>();
+            assert!(scores.windows(2).all(|pair| pair[0] <= pair[1]), "{scores:?}");
+        }
+    }
+
+    /// 同じ初期配置から山登りすると、最良改善は最初の改善より少ない回数で収束するが、1 回の移動で計算する得点の数は多い
+    #[test]
+    fn best_improving_converges_in_fewer_but_costlier_iterations() {
+        let [first, best] = [HillClimbStrategy::FirstImproving, HillClimbStrategy::BestImproving].map(|strategy| {
+            let mut rng_for_construct = SmallRng::seed_from_u64(0);
+            let mut iteration_sum = 0;
+            let mut evaluation_per_iteration_sum = 0.0;
+            for _ in 0..20 {
+                let seed = rng_for_construct.next_u64();
+                let state = State::new(seed);
+                let (_, iteration, evaluation_count) = hill_climb_with_strategy(&state, strategy, 10000, &mut SmallRng::seed_from_u64(seed));
+                iteration_sum += iteration;
+                // 最後の走査 (どの近傍でも得点が上がらなかった走査) も 1 回と数える
+                evaluation_per_iteration_sum += evaluation_count as f64 / (iteration + 1) as f64;
+            }
+            (iteration_sum, evaluation_per_iteration_sum)
+        });
+        assert!(best.0 < first.0, "{best:?} {first:?}");
+        assert!(best.1 > first.1, "{best:?} {first:?}");
+    }
+
+    /// 残り 1 ターンでも得点の上がる方を選ぶ
+    #[test]
+    fn chooses_last_action() {
+        let state = MazeState::from_board_string("3 2 0 1@5").unwrap();
+        let mut rng = SmallRng::seed_from_u64(0);
+        assert_eq!(hill_climbing_action(&state, 2, &mut rng), Action::Right);
+    }
+
+    /// 終了状態からは行動を選べない
+    #[test]
+    #[should_panic(expected = "cannot choose an action from a finished state")]
+    fn rejects_finished_state() {
+        let state = MazeState::from_board_string("3 3 0 1@5").unwrap();
+        hill_climbing_action(&state, 2, &mut SmallRng::seed_from_u64(0));
+    }
+}
@@ -0,0 +1,229 @@
+use std::time::{Duration, Instant};
+use crate::SingleGameState;
+use crate::alternate_maze::AlternateMazeState;
+use crate::maze::{Action, HeuristicFn, MazeState};
+use crate::search::alpha_beta::alpha_beta_action_with_deadline;
+use crate::search::config::SearchConfig;
+use crate::time_keeper::TimeKeeper;
+
+/// 子ごとに盤面を複製する
+fn for_each_cloned_child<S: SingleGameState>(state: &mut S, visit: &mut dyn FnMut(S::Action, &mut S)) {
+    for action in state.legal_actions() {
+        let mut next_state = state.clone();
+        next_state.advance(action);
+        next_state.evaluate_score();
+        visit(action, &mut next_state);
+    }
+}
+
+/// 盤面を複製せずに、`advance_with_undo` で進めて `visit` に渡し、`undo` で戻す
+fn for_each_child_with_undo(state: &mut MazeState, visit: &mut dyn FnMut(Action, &mut MazeState)) {
+    for action in state.legal_actions() {
+        let record = state.advance_with_undo(action);
+        state.evaluate_score();
+        visit(action, state);
+        state.undo(&record);
+    }
+}
+
+/// 深さ `depth` まで全探索して葉を `heuristic` で評価したときの最善の評価値と、深さ制限で打ち切った葉があったかどうか
+pub(crate) fn depth_limited_search<S: SingleGameState>(state: &S, depth: usize, heuristic: HeuristicFn<S>) -> (S::ScoreType, bool) {
+    depth_limited_search_by(&mut state.clone(), depth, heuristic, &for_each_cloned_child)
+}
+
+/// `depth_limited_search` を、子を `for_each_child` でたどって行う
+///
+/// `for_each_child` は盤面の子を、そこに至る行動と合わせて 1 つずつ `visit` に渡す (渡す前に子の `evaluate_score` を呼んでおく)。
+fn depth_limited_search_by<S, F>(state: &mut S, depth: usize, heuristic: HeuristicFn<S>, for_each_child: &F) -> (S::ScoreType, bool)
+where
+    S: SingleGameState,
+    F: Fn(&mut S, &mut dyn FnMut(S::Action, &mut S)),
+{
+    if state.is_done() {
+        return (heuristic(state), false);
+    }
+    if depth == 0 {
+        return (heuristic(state), true);
+    }
+    let mut best_score = None;
+    let mut is_cut_off = false;
+    for_each_child(state, &mut |_, next_state| {
+        let (score, cut_off) = depth_limited_search_by(next_state, depth - 1, heuristic, for_each_child);
+        is_cut_off |= cut_off;
+        if best_score.is_none_or(|best_score| score > best_score) {
+            best_score = Some(score);
+        }
+    });
+    (best_score.unwrap(), is_cut_off)
+}
+
+pub fn iterative_deepening_action<S: SingleGameState>(state: &S, max_depth: usize) -> S::Action {
+    assert!(max_depth > 0, "max_depth must be positive");
+    iterative_deepening_action_with_config(state, &SearchConfig::new().beam_depth(max_depth).time_limit(Duration::MAX).build())
+}
+
+/// 深さ `config.beam_depth` まで深さを 1 ずつ増やし、制限時間を過ぎたら最後に読み終えた深さの最善手を返す (ビーム幅とノード数の上限は使わない)
+///
+/// 深さ 1 は制限時間を過ぎていても読み終える。
+pub fn iterative_deepening_action_with_config<S: SingleGameState>(state: &S, config: &SearchConfig) -> S::Action {
+    iterative_deepening(&mut state.clone(), config.beam_depth, &TimeKeeper::new(config.time_limit), &for_each_cloned_child)
+}
+
+/// 盤面の複製を根での 1 回だけにした `iterative_deepening_action`
+pub fn iterative_deepening_action_with_undo(state: &MazeState, max_depth: usize) -> Action {
+    iterative_deepening(&mut state.clone(), max_depth, &TimeKeeper::new(Duration::MAX), &for_each_child_with_undo)
+}
+
+/// 深さ `max_depth` (1 以上) まで、深さ制限で打ち切った葉がなくなるか制限時間を過ぎるまで深さを 1 ずつ増やし、最後に読み終えた深さの最善手を返す
+///
+/// 合法手がない状態 (`is_dead_end`) では呼べない。
+fn iterative_deepening<S, F>(state: &mut S, max_depth: usize, time_keeper: &TimeKeeper, for_each_child: &F) -> S::Action
+where
+    S: SingleGameState,
+    F: Fn(&mut S, &mut dyn FnMut(S::Action, &mut S)),
+{
+    assert!(max_depth > 0, "max_depth must be positive");
+    let mut best_action = None;
+    for depth in 1..=max_depth {
+        let mut best_score = None;
+        let mut is_cut_off = false;
+        for_each_child(state, &mut |action, next_state| {
+            let (score, cut_off) = depth_limited_search_by(next_state, depth - 1, S::evaluated_score, for_each_child);
+            is_cut_off |= cut_off;
+            if best_score.is_none_or(|best_score| score > best_score) {
+                best_score = Some(score);
+                best_action = Some(action);
+            }
+        });
+        if !is_cut_off || time_keeper.is_time_over() { break; }
+    }
+    best_action.expect("no legal actions in a dead end")
+}
+
+/// 交互に動く迷路で、制限時間まで深さを 1 ずつ増やしながらアルファ・ベータ法で探索する
+pub fn alpha_beta_iterative_deepening_action<const H: usize, const W: usize, const END_TURN: i32>(state: &AlternateMazeState<H, W, END_TURN>, time_threshold: Duration) -> Action {
+    alpha_beta_iterative_deepening_action_with_depth(state, time_threshold).0
+}
+
+/// 最善手と、最後に探索を終えられた深さを返す
+///
+/// 深さ 1 を読み終えられなくても、そこまでに見つけた最善手を返す。終了状態では呼べない。
+pub fn alpha_beta_iterative_deepening_action_with_depth<const H: usize, const W: usize, const END_TURN: i32>(state: &AlternateMazeState<H, W, END_TURN>, time_threshold: Duration) -> (Action, usize) {
+    assert!(!state.is_done(), "cannot choose an action from a finished state");
+    let deadline = Instant::now() + time_threshold;
+    let mut best_action = None;
+    let mut completed_depth = 0;
+    for depth in 1..=END_TURN as usize {
+        let (action, is_completed) = alpha_beta_action_with_deadline(state, depth, deadline);
+        if !is_completed {
+            if best_action.is_none() {
+                best_action = Some(action);
+            }
+            break;
+        }
+        best_action = Some(action);
+        completed_depth = depth;
+    }
+    (best_action.expect("the first depth always yields an action"), completed_depth)
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::rngs::SmallRng;
+    use rand::{RngCore, SeedableRng};
+    use crate::maze::{heuristic_game_score, MazeParams};
+    use crate::search::brute_force::brute_force_best_score;
+    use crate::search::depth_limited::depth_limited_action;
+    use crate::search::greedy::greedy_action;
+    use super::*;
+
+    #[test]
+    #[should_panic(expected = "max_depth must be positive")]
+    fn rejects_zero_max_depth() {
+        iterative_deepening_action(&MazeState::new(0, MazeParams { h: 3, w: 4, end_turn: 5 }), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "max_depth must be positive")]
+    fn undo_rejects_zero_max_depth() {
+        iterative_deepening_action_with_undo(&MazeState::new(0, MazeParams { h: 3, w: 4, end_turn: 5 }), 0);
+    }
+
+    /// 盤面を複製するか `undo` で戻すかによらず、同じ深さまで読めば同じ手を選ぶ
+    #[test]
+    fn undo_matches_clone() {
+        let params = MazeParams { h: 5, w: 5, end_turn: 10 };
+        let mut rng_for_construct = SmallRng::seed_from_u64(0);
+        for _ in 0..5 {
+            let mut state = MazeState::new(rng_for_construct.next_u64(), params);
+            while !state.is_done() {
+                let action = iterative_deepening_action(&state, 4);
+                assert_eq!(iterative_deepening_action_with_undo(&state, 4), action);
+                state.advance(action);
+            }
+        }
+    }
+
+    /// 深さ 1 で `game_score` を評価すると、貪欲法と同じ行動を選ぶ
+    #[test]
+    fn depth_limited_depth_one_is_greedy() {
+        let params = MazeParams { h: 30, w: 30, end_turn: 100 };
+        let mut rng_for_construct = SmallRng::seed_from_u64(0);
+        for _ in 0..5 {
+            let mut state = MazeState::new(rng_for_construct.next_u64(), params);
+            while !state.is_done() {
+                let action = greedy_action(&state);
+                assert_eq!(depth_limited_action(&state, 1, heuristic_game_score), action);
+                state.advance(action);
+            }
+        }
+    }
+
+    /// 小さい盤面で終了まで読み切ると、全探索の最大値に届く
+    #[test]
+    fn depth_limited_full_depth_is_optimal() {
+        let params = MazeParams { h: 4, w: 4, end_turn: 6 };
+        let mut rng_for_construct = SmallRng::seed_from_u64(0);
+        for _ in 0..10 {
+            let mut state = MazeState::new(rng_for_construct.next_u64(), params);
+            let best_score = brute_force_best_score(&state);
+            while !state.is_done() {
+                state.advance(depth_limited_action(&state, params.end_turn as usize, heuristic_game_score));
+            }
+            assert_eq!(state.game_score(), best_score);
+        }
+    }
+
+    /// 制限時間が 0 でも深さ 1 は読み終えるので、1 手先の評価値が最も高い手 (貪欲法と同じ手) を返す
+    #[test]
+    fn config_zero_time_matches_greedy() {
+        let config = SearchConfig::new().beam_depth(10).time_limit(Duration::ZERO).build();
+        let mut rng_for_construct = SmallRng::seed_from_u64(0);
+        for _ in 0..20 {
+            let state = MazeState::new(rng_for_construct.next_u64(), MazeParams { h: 5, w: 5, end_turn: 10 });
+            assert_eq!(iterative_deepening_action_with_config(&state, &config), greedy_action(&state));
+        }
+    }
+
+    /// 制限時間が長いほど深くまで読み切れ、短い制限時間でも深さ 1 は読み切れる
+    ///
+    /// 並行して走る他のテストに CPU を取られて止まった分を除くため、3 回測った最も深い深さで比べる。
+    #[test]
+    fn longer_time_completes_deeper() {
+        let state = AlternateMazeState::<7, 7, 20>::new(0);
+        let completed_depth = |time_threshold: Duration| {
+            (0..3).map(|_| alpha_beta_iterative_deepening_action_with_depth(&state, time_threshold).1).max().unwrap()
+        };
+        let short_depth = completed_depth(Duration::from_millis(1));
+        let long_depth = completed_depth(Duration::from_millis(100));
+        assert!(short_depth > 0);
+        assert!(long_depth >= short_depth, "{long_depth} < {short_depth}");
+    }
+
+    /// 終了ターンが 0 の盤面は始めから終わっているので、読む深さがない
+    #[test]
+    #[should_panic(expected = "cannot choose an action from a finished state")]
+    fn alpha_beta_rejects_finished_state() {
+        alpha_beta_iterative_deepening_action(&AlternateMazeState::<3, 3, 0>::new(0), Duration::from_millis(1));
+    }
+}
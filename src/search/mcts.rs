@@ -0,0 +1,379 @@
+use rand::rngs::SmallRng;
+use crate::SingleGameState;
+use crate::search::budget::SearchBudget;
+use crate::search::greedy::greedy_action;
+use crate::search::random::random_action;
+
+pub const C: f64 = 1.0;
+const EXPAND_THRESHOLD: u32 = 10;
+
+/// プレイアウトで毎ターンの行動を選ぶ方策
+pub type RolloutPolicy<S> = fn(&S, &mut SmallRng) -> <S as SingleGameState>::Action;
+
+/// 名前を付けたプレイアウトの方策 (`MctsTree::policy_stats` は関数ポインタではなく名前で方策を区別する)
+type NamedRolloutPolicy<S> = (&'static str, RolloutPolicy<S>);
+
+/// `MctsTree::new` が最初に使う `random_rollout_policy` の名前
+pub const RANDOM_ROLLOUT_POLICY_NAME: &str = "random";
+
+/// 合法手から一様ランダムに選ぶ (`mcts_action` の既定)
+pub fn random_rollout_policy<S: SingleGameState>(state: &S, rng: &mut SmallRng) -> S::Action {
+    random_action(state, rng)
+}
+
+/// `greedy_action` で選ぶ (乱数は使わない)
+pub fn greedy_rollout_policy<S: SingleGameState>(state: &S, _rng: &mut SmallRng) -> S::Action {
+    greedy_action(state)
+}
+
+/// 子ノードを選ぶときに使う、各行動の事前確率
+///
+/// `Fn(&S, S::Action) -> f64` はそのまま事前確率として使える。
+pub trait PolicyPrior<S: SingleGameState> {
+    fn prior(&self, state: &S, action: S::Action) -> f64;
+}
+
+impl<S: SingleGameState, F: Fn(&S, S::Action) -> f64> PolicyPrior<S> for F {
+    fn prior(&self, state: &S, action: S::Action) -> f64 {
+        self(state, action)
+    }
+}
+
+/// 全ての合法手に等しい確率を割り当てる
+pub fn uniform_prior<S: SingleGameState>(state: &S, _action: S::Action) -> f64 {
+    1.0 / state.legal_actions().len() as f64
+}
+
+fn playout<S: SingleGameState<ScoreType = i64>>(state: &mut S, rollout_policy: RolloutPolicy<S>, rng: &mut SmallRng) -> f64 {
+    while !state.is_done() {
+        state.advance(rollout_policy(state, rng));
+    }
+    state.evaluate_score();
+    state.evaluated_score() as f64
+}
+
+struct Node<S: SingleGameState> {
+    state: S,
+    w: f64,
+    n: u32,
+    /// 親ノードから見たこのノードへの行動の事前確率 (PUCT で使う)
+    prior: f64,
+    /// このノードからのプレイアウトで使う方策とその名前
+    rollout_policy: NamedRolloutPolicy<S>,
+    /// このノード自身から行ったプレイアウトの回数と価値の和 (子ノードを経由したものは含まない)
+    playout_n: u32,
+    playout_w: f64,
+    child_nodes: Vec<(S::Action, Node<S>)>,
+}
+
+impl<S: SingleGameState<ScoreType = i64>> Node<S> {
+    fn new(state: S, prior: f64, rollout_policy: NamedRolloutPolicy<S>) -> Self {
+        Self {
+            state,
+            w: 0.0,
+            n: 0,
+            prior,
+            rollout_policy,
+            playout_n: 0,
+            playout_w: 0.0,
+            child_nodes: Vec::new(),
+        }
+    }
+
+    /// プレイアウトはこのノードの方策で行い、新しく作る子ノードには `rollout_policy` を使わせる
+    ///
+    /// `policy_prior` を渡すと UCB1 の代わりに PUCT で子ノードを選ぶ。
+    fn evaluate(&mut self, c: f64, rollout_policy: NamedRolloutPolicy<S>, policy_prior: Option<&dyn PolicyPrior<S>>, max_value: &mut f64, rng: &mut SmallRng) -> f64 {
+        if self.child_nodes.is_empty() {
+            let mut state = self.state.clone();
+            let value = playout(&mut state, self.rollout_policy.1, rng);
+            self.w += value;
+            self.n += 1;
+            self.playout_w += value;
+            self.playout_n += 1;
+            *max_value = max_value.max(value);
+            if !self.state.is_done() && self.n == EXPAND_THRESHOLD {
+                self.expand(rollout_policy, policy_prior);
+            }
+            return value;
+        }
+        let child_node = match policy_prior {
+            Some(_) => self.next_child_node_puct(c, *max_value),
+            None => self.next_child_node(c, *max_value),
+        };
+        let value = child_node.evaluate(c, rollout_policy, policy_prior, max_value, rng);
+        self.w += value;
+        self.n += 1;
+        value
+    }
+
+    fn expand(&mut self, rollout_policy: NamedRolloutPolicy<S>, policy_prior: Option<&dyn PolicyPrior<S>>) {
+        for action in self.state.legal_actions() {
+            let prior = policy_prior.map_or(1.0, |policy_prior| policy_prior.prior(&self.state, action));
+            let mut state = self.state.clone();
+            state.advance(action);
+            self.child_nodes.push((action, Node::new(state, prior, rollout_policy)));
+        }
+    }
+
+    /// 部分木の各ノードから行ったプレイアウトの回数と価値の和を、方策の名前ごとに `stats` に足す
+    fn add_policy_stats(&self, stats: &mut Vec<(&'static str, u32, f64)>) {
+        if self.playout_n > 0 {
+            let name = self.rollout_policy.0;
+            match stats.iter_mut().find(|(stats_name, _, _)| *stats_name == name) {
+                Some((_, n, w)) => {
+                    *n += self.playout_n;
+                    *w += self.playout_w;
+                }
+                None => stats.push((name, self.playout_n, self.playout_w)),
+            }
+        }
+        for (_, child) in &self.child_nodes {
+            child.add_policy_stats(stats);
+        }
+    }
+
+    fn most_visited_action(&self) -> S::Action {
+        let mut best_action_searched_number = 0;
+        let mut best_action = None;
+        for (action, child) in &self.child_nodes {
+            if best_action.is_none() || child.n > best_action_searched_number {
+                best_action_searched_number = child.n;
+                best_action = Some(*action);
+            }
+        }
+        best_action.unwrap()
+    }
+
+    /// 評価値のスケールを `max_value` で [0, 1] 程度に揃えてから UCB1 を計算する
+    fn next_child_node(&mut self, c: f64, max_value: f64) -> &mut Node<S> {
+        if let Some(index) = self.child_nodes.iter().position(|(_, child)| child.n == 0) {
+            return &mut self.child_nodes[index].1;
+        }
+        let t = self.child_nodes.iter().map(|(_, child)| child.n as f64).sum::<f64>();
+        let scale = max_value.max(1.0);
+        let mut best_value = f64::NEG_INFINITY;
+        let mut best_index = 0;
+        for (i, (_, child)) in self.child_nodes.iter().enumerate() {
+            let ucb1_value = child.w / child.n as f64 / scale + c * (t.ln() / child.n as f64).sqrt();
+            if ucb1_value > best_value {
+                best_value = ucb1_value;
+                best_index = i;
+            }
+        }
+        &mut self.child_nodes[best_index].1
+    }
+
+    /// `Q + c * P * sqrt(N) / (1 + n)` が最大の子ノードを選ぶ (`Q` は UCB1 と同じく `max_value` で揃え、未訪問なら 0 とする)
+    fn next_child_node_puct(&mut self, c: f64, max_value: f64) -> &mut Node<S> {
+        let t = self.child_nodes.iter().map(|(_, child)| child.n as f64).sum::<f64>();
+        let scale = max_value.max(1.0);
+        let mut best_value = f64::NEG_INFINITY;
+        let mut best_index = 0;
+        for (i, (_, child)) in self.child_nodes.iter().enumerate() {
+            let q = if child.n == 0 { 0.0 } else { child.w / child.n as f64 / scale };
+            let puct_value = q + c * child.prior * t.sqrt() / (1.0 + child.n as f64);
+            if puct_value > best_value {
+                best_value = puct_value;
+                best_index = i;
+            }
+        }
+        &mut self.child_nodes[best_index].1
+    }
+}
+
+pub fn mcts_action<S: SingleGameState<ScoreType = i64>>(state: &S, simulation_count: u32, rng: &mut SmallRng) -> S::Action {
+    mcts_action_with_exploration(state, simulation_count, C, rng)
+}
+
+pub fn mcts_action_with_exploration<S: SingleGameState<ScoreType = i64>>(state: &S, simulation_count: u32, c: f64, rng: &mut SmallRng) -> S::Action {
+    mcts_action_with_policy(state, simulation_count, c, random_rollout_policy::<S>, rng)
+}
+
+/// プレイアウトの行動を `rollout_policy` で選ぶ MCTS
+pub fn mcts_action_with_policy<S: SingleGameState<ScoreType = i64>>(state: &S, simulation_count: u32, c: f64, rollout_policy: RolloutPolicy<S>, rng: &mut SmallRng) -> S::Action {
+    mcts(state, simulation_count, c, rollout_policy, None, rng)
+}
+
+/// 子ノードを `policy_prior` の事前確率を使った PUCT で選ぶ MCTS (`c_puct` は探索の強さ)
+pub fn mcts_action_with_prior<S: SingleGameState<ScoreType = i64>>(state: &S, simulation_count: u32, c_puct: f64, policy_prior: &dyn PolicyPrior<S>, rng: &mut SmallRng) -> S::Action {
+    mcts(state, simulation_count, c_puct, random_rollout_policy::<S>, Some(policy_prior), rng)
+}
+
+/// 終了状態や合法手がない状態 (`is_dead_end`) では根に子ノードがないので呼べない
+fn mcts<S: SingleGameState<ScoreType = i64>>(state: &S, simulation_count: u32, c: f64, rollout_policy: RolloutPolicy<S>, policy_prior: Option<&dyn PolicyPrior<S>>, rng: &mut SmallRng) -> S::Action {
+    assert!(!state.is_done() && !state.is_dead_end(), "cannot choose an action from a finished state");
+    // この木の方策ごとの統計は返さないので、名前は使わない
+    let rollout_policy = ("", rollout_policy);
+    let mut root_node = Node::new(state.clone(), 1.0, rollout_policy);
+    root_node.expand(rollout_policy, policy_prior);
+    let mut max_value = 0.0;
+    for _ in 0..simulation_count {
+        root_node.evaluate(c, rollout_policy, policy_prior, &mut max_value, rng);
+    }
+    root_node.most_visited_action()
+}
+
+/// 手を進めても探索した木を捨てずに、選んだ行動の子ノードを新しい根として使い続ける MCTS の木
+pub struct MctsTree<S: SingleGameState> {
+    root: Node<S>,
+    c: f64,
+    max_value: f64,
+    /// 新しく作るノードのプレイアウトで使う方策とその名前
+    rollout_policy: NamedRolloutPolicy<S>,
+}
+
+impl<S: SingleGameState<ScoreType = i64>> MctsTree<S>
+where
+    S::Action: PartialEq,
+{
+    pub fn new(state: &S, c: f64) -> Self {
+        let rollout_policy = (RANDOM_ROLLOUT_POLICY_NAME, random_rollout_policy::<S> as RolloutPolicy<S>);
+        Self { root: Node::new(state.clone(), 1.0, rollout_policy), c, max_value: 0.0, rollout_policy }
+    }
+
+    /// これから新しく作るノードのプレイアウトを `rollout_policy` で行う (既にあるノードは作ったときの方策を使い続ける)
+    ///
+    /// `policy_stats` は `name` で方策を区別するので、違う方策には違う名前を付ける。
+    pub fn set_rollout_policy(&mut self, name: &'static str, rollout_policy: RolloutPolicy<S>) {
+        self.rollout_policy = (name, rollout_policy);
+    }
+
+    /// 今の木に残っているノードから行ったプレイアウトの回数と価値の平均を、方策の名前ごとに木を深さ優先でたどって最初に見つけた順に返す
+    pub fn policy_stats(&self) -> Vec<(&'static str, u32, f64)> {
+        let mut stats = Vec::new();
+        self.root.add_policy_stats(&mut stats);
+        stats.into_iter().map(|(name, n, w)| (name, n, w / n as f64)).collect()
+    }
+
+    /// 根の訪問回数 (前の手までに根の部分木で行ったシミュレーションも含む)
+    pub fn root_visit_count(&self) -> u32 {
+        self.root.n
+    }
+
+    /// `action` の子ノードを新しい根にし、ほかの行動の部分木を捨てる (子ノードがまだなければ根から作り直す)
+    pub fn advance_root(&mut self, action: S::Action) {
+        let child_nodes = std::mem::take(&mut self.root.child_nodes);
+        match child_nodes.into_iter().find(|(child_action, _)| *child_action == action) {
+            Some((_, child)) => self.root = child,
+            None => {
+                let mut state = self.root.state.clone();
+                state.advance(action);
+                self.root = Node::new(state, 1.0, self.rollout_policy);
+            }
+        }
+    }
+}
+
+/// `tree` の根から `budget` が尽きるまでシミュレーションを追加し、根で最も多く訪問した行動を返す (`SearchBudget::Nodes` はこの手で追加するシミュレーションの回数)
+///
+/// 根が終了状態や合法手がない状態 (`is_dead_end`) では呼べない。
+pub fn mcts_action_with_tree<S: SingleGameState<ScoreType = i64>>(tree: &mut MctsTree<S>, budget: SearchBudget, rng: &mut SmallRng) -> S::Action {
+    assert!(!tree.root.state.is_done() && !tree.root.state.is_dead_end(), "cannot choose an action from a finished state");
+    let budget_keeper = budget.start();
+    if tree.root.child_nodes.is_empty() {
+        tree.root.expand(tree.rollout_policy, None);
+    }
+    let mut simulation_count = 0;
+    while !budget_keeper.is_over(simulation_count) {
+        tree.root.evaluate(tree.c, tree.rollout_policy, None, &mut tree.max_value, rng);
+        simulation_count += 1;
+    }
+    tree.root.most_visited_action()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+    use rand::SeedableRng;
+    use crate::maze::{Action, Coord, MazeParams, MazeState};
+    use super::*;
+
+    /// どの行動でも得点が取れない 1x3 の盤面では価値に差がないので、PUCT は事前確率を置いた向きに進む
+    #[test]
+    fn puct_follows_prior() {
+        let state = MazeState::from_points(vec![vec![0, 0, 0]], Coord { x: 1, y: 0 }, 1);
+        let only = |only_action: Action| move |_: &MazeState, action: Action| if action == only_action { 1.0 } else { 0.0 };
+        let mut rng = SmallRng::seed_from_u64(0);
+        for action in [Action::Left, Action::Right] {
+            assert_eq!(mcts_action_with_prior(&state, 100, C, &only(action), &mut rng), action);
+        }
+    }
+
+    /// 回数の上限ではこの手で追加したシミュレーションの回数だけ根の訪問回数が増え、時間切れでも合法手を返す
+    #[test]
+    fn tree_search_follows_budget() {
+        let state = MazeState::new(0, MazeParams { h: 5, w: 5, end_turn: 10 });
+        let mut rng = SmallRng::seed_from_u64(0);
+        let mut tree = MctsTree::new(&state, C);
+        mcts_action_with_tree(&mut tree, SearchBudget::Nodes(50), &mut rng);
+        assert_eq!(tree.root_visit_count(), 50);
+        mcts_action_with_tree(&mut tree, SearchBudget::Nodes(30), &mut rng);
+        assert_eq!(tree.root_visit_count(), 80);
+        let action = mcts_action_with_tree(&mut MctsTree::new(&state, C), SearchBudget::Time(Duration::ZERO), &mut rng);
+        assert!(state.legal_actions().contains(&action));
+    }
+
+    /// 木を使い回すと、1 手あたりに追加するシミュレーションの回数が同じでも、行動を選ぶときの根の訪問回数が増える
+    #[test]
+    fn tree_reuse_accumulates_root_visits() {
+        let simulation_count = 100;
+        let mut rng_for_action = SmallRng::seed_from_u64(0);
+        let mut state = MazeState::new(0, MazeParams { h: 10, w: 10, end_turn: 20 });
+        let mut tree = MctsTree::new(&state, C);
+        let mut root_visit_count_sum = 0;
+        let mut move_number = 0;
+        while !state.is_done() {
+            let action = mcts_action_with_tree(&mut tree, SearchBudget::Nodes(simulation_count), &mut rng_for_action);
+            assert!(tree.root_visit_count() as usize >= simulation_count);
+            root_visit_count_sum += tree.root_visit_count() as usize;
+            move_number += 1;
+            tree.advance_root(action);
+            state.advance(action);
+        }
+        assert!(root_visit_count_sum > simulation_count * move_number);
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot choose an action from a finished state")]
+    fn rejects_finished_state() {
+        let state = MazeState::from_board_string("5 5 0 @1").unwrap();
+        mcts_action(&state, 10, &mut SmallRng::seed_from_u64(0));
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot choose an action from a finished state")]
+    fn rejects_dead_end() {
+        let state = MazeState::from_board_string("5 0 0 #@#").unwrap();
+        mcts_action_with_prior(&state, 10, C, &uniform_prior::<MazeState>, &mut SmallRng::seed_from_u64(0));
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot choose an action from a finished state")]
+    fn tree_rejects_dead_end() {
+        let state = MazeState::from_board_string("5 0 0 #@#").unwrap();
+        mcts_action_with_tree(&mut MctsTree::new(&state, C), SearchBudget::Nodes(10), &mut SmallRng::seed_from_u64(0));
+    }
+
+    /// 途中で方策を変えると、それまでに作ったノードは元の方策、その後に作ったノードは新しい方策でプレイアウトし、
+    /// 方策ごとの回数の和は根の訪問回数に一致する
+    #[test]
+    fn policy_stats_follow_rollout_policy() {
+        let state = MazeState::new(0, MazeParams { h: 5, w: 5, end_turn: 10 });
+        let mut rng = SmallRng::seed_from_u64(0);
+        let mut tree = MctsTree::new(&state, C);
+        mcts_action_with_tree(&mut tree, SearchBudget::Nodes(50), &mut rng);
+        let stats = tree.policy_stats();
+        assert_eq!(stats.len(), 1);
+        assert_eq!(stats[0].0, RANDOM_ROLLOUT_POLICY_NAME);
+        assert_eq!(stats[0].1, 50);
+        tree.set_rollout_policy("greedy", greedy_rollout_policy::<MazeState>);
+        mcts_action_with_tree(&mut tree, SearchBudget::Nodes(200), &mut rng);
+        let stats = tree.policy_stats();
+        assert_eq!(stats.len(), 2);
+        assert_eq!(stats[1].0, "greedy");
+        assert!(stats[0].1 >= 50 && stats[1].1 > 0);
+        assert_eq!(stats.iter().map(|(_, n, _)| n).sum::<u32>(), tree.root_visit_count());
+        assert!(stats.iter().all(|&(_, _, value)| value >= 0.0));
+    }
+}
@@ -0,0 +1,109 @@
+use std::collections::BTreeMap;
+use std::time::Duration;
+use crate::SingleGameState;
+use crate::search::config::SearchConfig;
+use crate::time_keeper::TimeKeeper;
+
+/// 状態を持っておける数を `max_total_states` 個までに抑えたビームサーチ
+///
+/// 深さ `t` の層から評価の高い順に `beam_width` 個を展開して深さ `t + 1` の層に積み、展開し終えた層は捨てる。
+/// 積んでいる途中で 2 つの層の状態の数の合計が `max_total_states` を超えたら、大きいほうの層から評価が最も低い状態を捨てる。
+/// 各層は (評価値, 積んだ順番) をキーにした `BTreeMap` で持つので、展開するのは末尾、捨てるのは先頭の状態になり、どちらも O(log n) で済む。
+/// `max_total_states` は 1 以上でなければならず、合法手がない状態 (`is_dead_end`) では呼べない。
+pub fn memory_bounded_beam_search_action<S: SingleGameState>(state: &S, beam_width: usize, depth: usize, max_total_states: usize) -> S::Action {
+    memory_bounded_beam_search_action_with_config(state, &SearchConfig::new().beam_width(beam_width).beam_depth(depth).time_limit(Duration::MAX).build(), max_total_states)
+}
+
+/// `config` のビーム幅と深さで探索し、制限時間に達したらそれまでの最善の状態の行動を返す (ノード数の上限は使わない)
+pub fn memory_bounded_beam_search_action_with_config<S: SingleGameState>(state: &S, config: &SearchConfig, max_total_states: usize) -> S::Action {
+    memory_bounded_beam_search(state, config, max_total_states).0
+}
+
+/// 選んだ行動と、探索中に 2 つの層に同時に持っていた状態の数の最大値を返す
+fn memory_bounded_beam_search<S: SingleGameState>(state: &S, config: &SearchConfig, max_total_states: usize) -> (S::Action, usize) {
+    assert!(max_total_states > 0, "max_total_states must be positive");
+    let (beam_width, depth) = (config.beam_width, config.beam_depth);
+    let time_keeper = TimeKeeper::new(config.time_limit);
+    let mut layers: Vec<BTreeMap<(S::ScoreType, u64), S>> = (0..=depth).map(|_| BTreeMap::new()).collect();
+    let mut order = 0;
+    layers[0].insert((state.evaluated_score(), order), state.clone());
+    let mut max_stored_states = 1;
+    let mut best_state: Option<S> = None;
+    for t in 0..depth {
+        let (now_layers, next_layers) = layers.split_at_mut(t + 1);
+        let (now_layer, next_layer) = (&mut now_layers[t], &mut next_layers[0]);
+        for _ in 0..beam_width {
+            let Some((_, now_state)) = now_layer.pop_last() else { break; };
+            for action in now_state.legal_actions() {
+                let mut next_state = now_state.clone();
+                next_state.advance(action);
+                next_state.evaluate_score();
+                if t == 0 {
+                    next_state.set_first_action(action);
+                }
+                order += 1;
+                next_layer.insert((next_state.evaluated_score(), order), next_state);
+                if now_layer.len() + next_layer.len() > max_total_states {
+                    if now_layer.len() > next_layer.len() {
+                        now_layer.pop_first();
+                    } else {
+                        next_layer.pop_first();
+                    }
+                }
+                max_stored_states = max_stored_states.max(now_layer.len() + next_layer.len());
+            }
+        }
+        now_layer.clear();
+        let Some((_, now_best_state)) = next_layer.last_key_value() else { break; };
+        best_state = Some(now_best_state.clone());
+        if now_best_state.is_done() || time_keeper.is_time_over() { break; }
+    }
+    let action = best_state.and_then(|best_state| best_state.first_action()).expect("no legal actions in a dead end");
+    (action, max_stored_states)
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::rngs::SmallRng;
+    use rand::{RngCore, SeedableRng};
+    use crate::maze::{MazeParams, MazeState};
+    use super::*;
+
+    /// 2 つの層に同時に持つ状態の数は `max_total_states` を超えない
+    #[test]
+    fn stored_states_never_exceed_cap() {
+        let params = MazeParams { h: 5, w: 5, end_turn: 10 };
+        let mut rng_for_construct = SmallRng::seed_from_u64(0);
+        for max_total_states in [1, 4, 10, 20] {
+            let config = SearchConfig::new().beam_width(5).beam_depth(10).time_limit(Duration::MAX).build();
+            let mut state = MazeState::new(rng_for_construct.next_u64(), params);
+            while !state.is_done() {
+                let (action, max_stored_states) = memory_bounded_beam_search(&state, &config, max_total_states);
+                assert!(max_stored_states <= max_total_states);
+                state.advance(action);
+            }
+        }
+    }
+
+    /// 上限が十分に大きければ、ふつうのビームサーチと同じ数だけ状態を持つ
+    #[test]
+    fn large_cap_keeps_more_states() {
+        let state = MazeState::new(0, MazeParams { h: 5, w: 5, end_turn: 10 });
+        let config = SearchConfig::new().beam_width(5).beam_depth(10).time_limit(Duration::MAX).build();
+        let (_, max_stored_states) = memory_bounded_beam_search(&state, &config, 1000);
+        assert!(max_stored_states > 20);
+    }
+
+    #[test]
+    #[should_panic(expected = "max_total_states must be positive")]
+    fn rejects_zero_cap() {
+        memory_bounded_beam_search_action(&MazeState::new(0, MazeParams { h: 3, w: 4, end_turn: 5 }), 2, 2, 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "no legal actions in a dead end")]
+    fn rejects_dead_end() {
+        let state = MazeState::from_board_string("5 0 0 #@#").unwrap();
+        memory_bounded_beam_search_action(&state, 2, 2, 10);
+    }
+}
@@ -0,0 +1,76 @@
+use crate::maze::{Action, ScoreType};
+use crate::alternate_maze::AlternateMazeState;
+
+const INF: ScoreType = 1_000_000_000;
+
+fn mini_max_score<const H: usize, const W: usize, const END_TURN: i32>(state: &AlternateMazeState<H, W, END_TURN>, depth: usize, node_count: &mut u64) -> ScoreType {
+    *node_count += 1;
+    if state.is_done() || depth == 0 {
+        return state.teban_evaluation();
+    }
+    let legal_actions = state.legal_actions();
+    if legal_actions.is_empty() {
+        return state.teban_evaluation();
+    }
+    let mut best_score = -INF;
+    for action in legal_actions {
+        let mut next_state = state.clone();
+        next_state.advance(action);
+        let score = -mini_max_score(&next_state, depth - 1, node_count);
+        if score > best_score {
+            best_score = score;
+        }
+    }
+    best_score
+}
+
+/// 探索した節点の数も合わせて返す (`depth` が 0 なら探索せずに最初の合法手を返す)
+pub fn mini_max_action_with_count<const H: usize, const W: usize, const END_TURN: i32>(state: &AlternateMazeState<H, W, END_TURN>, depth: usize) -> (Action, u64) {
+    if depth == 0 {
+        return (state.legal_actions()[0], 0);
+    }
+    let mut node_count = 0;
+    let mut best_action = None;
+    let mut best_score = -INF;
+    for action in state.legal_actions() {
+        let mut next_state = state.clone();
+        next_state.advance(action);
+        let score = -mini_max_score(&next_state, depth - 1, &mut node_count);
+        if best_action.is_none() || score > best_score {
+            best_action = Some(action);
+            best_score = score;
+        }
+    }
+    (best_action.unwrap(), node_count)
+}
+
+pub fn mini_max_action<const H: usize, const W: usize, const END_TURN: i32>(state: &AlternateMazeState<H, W, END_TURN>, depth: usize) -> Action {
+    mini_max_action_with_count(state, depth).0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 手で読み切れる 2x3 の盤面 (先手 A、後手 B) で、2 手読みの最善手を選ぶ
+    ///
+    /// ```text
+    /// 192
+    /// A3B
+    /// ```
+    ///
+    /// 上 (1 点) に動くと後手に 3 点を取られて負け、右 (3 点) に動けば後手は 2 点しか取れずに勝つ。
+    /// 探索する節点は先手の 2 手それぞれに後手の 2 手 (上と左) で 6 つ。
+    #[test]
+    fn tiny_board_picks_winning_action() {
+        let state = AlternateMazeState::<2, 3, 2>::from_points([[1, 9, 2], [0, 3, 0]]);
+        assert_eq!(state.legal_actions(), vec![Action::Right, Action::Up]);
+        assert_eq!(mini_max_action_with_count(&state, 2), (Action::Right, 6));
+        let mut next_state = state.clone();
+        next_state.advance(Action::Right);
+        assert_eq!(-mini_max_score(&next_state, 1, &mut 0), crate::alternate_maze::WIN_SCORE);
+        let mut next_state = state.clone();
+        next_state.advance(Action::Up);
+        assert_eq!(-mini_max_score(&next_state, 1, &mut 0), crate::alternate_maze::LOSE_SCORE);
+    }
+}
@@ -0,0 +1,65 @@
+pub mod random;
+pub mod greedy;
+pub mod beam;
+pub mod beam_stack;
+pub mod memory_bounded_beam;
+pub mod chokudai;
+pub mod best_first;
+pub mod bfs;
+pub mod brute_force;
+pub mod depth_limited;
+pub mod astar;
+pub mod parallel_beam;
+pub mod simulated_annealing;
+pub mod tabu;
+pub mod hill_climb;
+pub mod random_restart;
+pub mod mcts;
+pub mod playout;
+pub mod iterative_deepening;
+pub mod mini_max;
+pub mod alpha_beta;
+pub mod stats;
+pub mod budget;
+pub mod config;
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+    use rand::rngs::SmallRng;
+    use rand::SeedableRng;
+    use crate::SingleGameState;
+    use crate::maze::{Action, Coord, MazeState};
+    use crate::trace::play_game;
+    use super::*;
+
+    /// 1x1 の盤面や壁に囲まれた盤面は最初から行き止まりなので、どの方策でも 0 手で現在の得点のまま終わる
+    #[test]
+    fn dead_end_terminates() {
+        let boards = [
+            MazeState::from_points(vec![vec![0]], Coord { x: 0, y: 0 }, 5),
+            MazeState::from_board_string("5 0 0 #@#").unwrap(),
+        ];
+        for board in boards {
+            assert!(board.is_dead_end());
+            assert!(board.is_done());
+            let assert_terminates = |name: &str, policy: &mut dyn FnMut(&MazeState) -> Action| {
+                let (score, actions) = play_game(board.clone(), policy);
+                assert_eq!((score, actions.len()), (0, 0), "{name}");
+            };
+            let mut rng = SmallRng::seed_from_u64(0);
+            assert_terminates("random", &mut |state| random::random_action(state, &mut rng));
+            assert_terminates("greedy", &mut greedy::greedy_action);
+            assert_terminates("beam", &mut |state| beam::beam_search_action(state, 5, 5));
+            assert_terminates("chokudai", &mut |state| chokudai::chokudai_search_action(state, 1, 5, 5));
+            assert_terminates("beam stack", &mut |state| beam_stack::beam_stack_search_action(state, 5, Duration::from_millis(1)));
+            assert_terminates("best first", &mut |state| best_first::best_first_search_action(state, 100));
+            assert_terminates("bfs", &mut |state| bfs::bfs_action(state, 100));
+            assert_terminates("astar", &mut astar::astar_action);
+            assert_terminates("iterative deepening", &mut |state| iterative_deepening::iterative_deepening_action(state, 5));
+            assert_terminates("mcts", &mut |state| mcts::mcts_action(state, 100, &mut rng));
+            assert_terminates("hill climbing", &mut |state| hill_climb::hill_climbing_action(state, 2, &mut rng));
+            assert_terminates("simulated annealing", &mut |state| simulated_annealing::simulated_annealing_action(state, 10.0, 0.99, 100, &mut rng));
+        }
+    }
+}
@@ -0,0 +1,62 @@
+use std::collections::BinaryHeap;
+use std::time::Duration;
+use rayon::prelude::*;
+use crate::SingleGameState;
+use crate::search::config::SearchConfig;
+use crate::time_keeper::TimeKeeper;
+
+/// 各深さで選んだ `beam_width` 個の状態の展開を並列に行うビームサーチ
+pub fn parallel_beam_search_action<S>(state: &S, beam_width: usize, beam_depth: usize, time_threshold: Duration) -> S::Action
+where
+    S: SingleGameState + Ord + Send + Sync,
+    S::Action: Send + Sync,
+{
+    parallel_beam_search_action_with_config(state, &SearchConfig::new().beam_width(beam_width).beam_depth(beam_depth).time_limit(time_threshold).build())
+}
+
+/// `config` のビーム幅と深さで探索し、制限時間に達したら打ち切る (ノード数の上限は使わない)
+///
+/// 合法手がない状態 (`is_dead_end`) では呼べない。
+pub fn parallel_beam_search_action_with_config<S>(state: &S, config: &SearchConfig) -> S::Action
+where
+    S: SingleGameState + Ord + Send + Sync,
+    S::Action: Send + Sync,
+{
+    let time_keeper = TimeKeeper::new(config.time_limit);
+    let mut now_beam = BinaryHeap::new();
+    let mut best_state: Option<S> = None;
+    now_beam.push(state.clone());
+    for t in 0..config.beam_depth {
+        let now_states = (0..config.beam_width).map_while(|_| now_beam.pop()).collect::<Vec<_>>();
+        let next_states = now_states.par_iter().flat_map_iter(|now_state| {
+            now_state.legal_actions().into_iter().map(move |action| {
+                let mut next_state = now_state.clone();
+                next_state.advance(action);
+                next_state.evaluate_score();
+                if t == 0 {
+                    next_state.set_first_action(action);
+                }
+                next_state
+            })
+        }).collect::<Vec<_>>();
+        now_beam = BinaryHeap::from(next_states);
+        let Some(now_best_state) = now_beam.peek() else { break; };
+        best_state = Some(now_best_state.clone());
+        if now_best_state.is_done() { break; }
+        if time_keeper.is_time_over() { break; }
+    }
+    best_state.and_then(|best_state| best_state.first_action()).expect("no legal actions in a dead end")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::maze::MazeState;
+    use super::*;
+
+    #[test]
+    #[should_panic(expected = "no legal actions in a dead end")]
+    fn rejects_dead_end() {
+        let state = MazeState::from_board_string("5 0 0 #@#").unwrap();
+        parallel_beam_search_action(&state, 2, 2, Duration::from_millis(10));
+    }
+}
@@ -0,0 +1,45 @@
+use rand::rngs::SmallRng;
+use rand::RngCore;
+use crate::maze::ScoreType;
+use crate::alternate_maze::AlternateMazeState;
+
+/// 終局までランダムな合法手で `state` を進め、呼び出した時点の手番のプレイヤーから見た最終的な得点差を返す
+///
+/// 終局した状態を渡すとそのまま `teban_score` を返す。
+pub fn playout<const H: usize, const W: usize, const END_TURN: i32>(state: &mut AlternateMazeState<H, W, END_TURN>, rng: &mut SmallRng) -> ScoreType {
+    let is_first = state.is_first();
+    while !state.is_done() {
+        let legal_actions = state.legal_actions();
+        state.advance(legal_actions[rng.next_u32() as usize % legal_actions.len()]);
+    }
+    if state.is_first() == is_first {
+        state.teban_score()
+    } else {
+        -state.teban_score()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::SeedableRng;
+    use super::*;
+
+    /// 終局した状態からのプレイアウトは、状態を進めずにその時点の得点差を返す
+    #[test]
+    fn terminal_state_returns_current_score() {
+        let mut rng_for_action = SmallRng::seed_from_u64(0);
+        let mut rng_for_construct = SmallRng::seed_from_u64(0);
+        for _ in 0..100 {
+            let mut state = AlternateMazeState::<5, 5, 10>::new(rng_for_construct.next_u64());
+            while !state.is_done() {
+                let legal_actions = state.legal_actions();
+                state.advance(legal_actions[rng_for_action.next_u32() as usize % legal_actions.len()]);
+            }
+            let teban_score = state.teban_score();
+            let is_first = state.is_first();
+            assert_eq!(playout(&mut state, &mut rng_for_action), teban_score);
+            assert!(state.is_done());
+            assert_eq!(state.is_first(), is_first);
+        }
+    }
+}
@@ -0,0 +1,10 @@
+use rand::rngs::SmallRng;
+use rand::RngCore;
+use crate::SingleGameState;
+
+/// 合法手がない状態 (`is_dead_end`) では呼べない
+pub fn random_action<S: SingleGameState>(state: &S, rng: &mut SmallRng) -> S::Action {
+    let legal_actions = state.legal_actions();
+    assert!(!legal_actions.is_empty(), "no legal actions in a dead end");
+    legal_actions[rng.next_u32() as usize % legal_actions.len()]
+}
@@ -0,0 +1,51 @@
+use rand::rngs::SmallRng;
+use crate::maze::ScoreType;
+
+/// `make_initial` で作った `restarts` 個 (1 個以上) の初期解それぞれから `optimize` で改善し、得点が最も高い解とその得点を返す
+///
+/// 得点が同じなら先に見つけた解を残すので、同じ乱数から始めれば `restarts` を増やしても返す得点は下がらない。
+pub fn random_restart<S>(make_initial: impl Fn(&mut SmallRng) -> S, optimize: impl Fn(S) -> (S, ScoreType), restarts: usize, rng: &mut SmallRng) -> (S, ScoreType) {
+    assert!(restarts > 0, "restarts must be positive");
+    let mut best = None;
+    for _ in 0..restarts {
+        let (state, score) = optimize(make_initial(rng));
+        if best.as_ref().is_none_or(|(_, best_score)| score > *best_score) {
+            best = Some((state, score));
+        }
+    }
+    best.unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{RngCore, SeedableRng};
+    use crate::auto_move_maze::AutoMoveMazeState;
+    use crate::search::hill_climb::{hill_climb_from, HillClimbStrategy};
+    use super::*;
+
+    type State = AutoMoveMazeState<5, 5, 5, 3>;
+
+    /// 同じ乱数から始めると、やり直しの回数を増やしても最良の得点は下がらない
+    #[test]
+    fn more_restarts_never_lower_score() {
+        let mut rng_for_construct = SmallRng::seed_from_u64(0);
+        for _ in 0..10 {
+            let seed = rng_for_construct.next_u64();
+            let state = State::new(seed);
+            let make_initial = |rng: &mut SmallRng| {
+                let mut now_state = state.clone();
+                now_state.init(rng);
+                now_state
+            };
+            let optimize = |state: State| {
+                let (state, _, _) = hill_climb_from(state, HillClimbStrategy::FirstImproving, 10000);
+                let score = state.get_score();
+                (state, score)
+            };
+            let scores = (1..=10).map(|restarts| {
+                random_restart(make_initial, optimize, restarts, &mut SmallRng::seed_from_u64(seed)).1
+            }).collect::<Vec<_>>();
+            assert!(scores.windows(2).all(|pair| pair[0] <= pair[1]), "{scores:?}");
+        }
+    }
+}
@@ -0,0 +1,142 @@
+use rand::rngs::SmallRng;
+use rand::Rng;
+use crate::SingleGameState;
+use crate::auto_move_maze::AutoMoveMazeState;
+use crate::search::random::random_action;
+
+/// `actions` を先頭から実行した最終スコアを返す
+///
+/// 途中で合法でなくなった行動や足りない行動はランダムな合法手で埋め、ゲーム終了や行き止まり以降の行動は切り捨てる。
+pub(crate) fn play_actions<S>(state: &S, actions: &mut Vec<S::Action>, rng: &mut SmallRng) -> i64
+where
+    S: SingleGameState<ScoreType = i64>,
+    S::Action: PartialEq,
+{
+    let mut state = state.clone();
+    let mut turn = 0;
+    while !state.is_done() && !state.is_dead_end() {
+        if turn == actions.len() {
+            actions.push(random_action(&state, rng));
+        } else if !state.legal_actions().contains(&actions[turn]) {
+            actions[turn] = random_action(&state, rng);
+        }
+        state.advance(actions[turn]);
+        turn += 1;
+    }
+    actions.truncate(turn);
+    state.evaluate_score();
+    state.evaluated_score()
+}
+
+/// ランダムに選んだターンの行動をその時点の合法手からランダムに選び直す
+///
+/// 行動列が空なら何もしない。
+pub(crate) fn mutate<S: SingleGameState>(state: &S, actions: &mut [S::Action], rng: &mut SmallRng) {
+    if actions.is_empty() { return; }
+    let turn = rng.gen_range(0..actions.len());
+    let mut state = state.clone();
+    for &action in &actions[..turn] {
+        state.advance(action);
+    }
+    actions[turn] = random_action(&state, rng);
+}
+
+/// 終了状態や合法手がない状態 (`is_dead_end`) では呼べない
+pub fn simulated_annealing_action<S>(state: &S, initial_temp: f64, cooling_rate: f64, iterations: u32, rng: &mut SmallRng) -> S::Action
+where
+    S: SingleGameState<ScoreType = i64>,
+    S::Action: PartialEq,
+{
+    assert!(!state.is_done() && !state.is_dead_end(), "cannot choose an action from a finished state");
+    let mut now_actions = Vec::new();
+    let mut now_score = play_actions(state, &mut now_actions, rng);
+    let mut best_actions = now_actions.clone();
+    let mut best_score = now_score;
+    let mut temp = initial_temp;
+    for _ in 0..iterations {
+        let mut next_actions = now_actions.clone();
+        mutate(state, &mut next_actions, rng);
+        let next_score = play_actions(state, &mut next_actions, rng);
+        let delta = (now_score - next_score) as f64;
+        if delta <= 0.0 || rng.gen::<f64>() < (-delta / temp).exp() {
+            now_actions = next_actions;
+            now_score = next_score;
+            if now_score > best_score {
+                best_actions = now_actions.clone();
+                best_score = now_score;
+            }
+        }
+        temp *= cooling_rate;
+    }
+    best_actions[0]
+}
+
+/// キャラクターの配置を焼きなまし法で決める
+///
+/// 温度は `start_temp` から `end_temp` まで線形に下げ、悪化する遷移も確率 `exp(delta / temp)` で受け入れる。
+pub fn simulated_annealing<const H: usize, const W: usize, const END_TURN: i32, const CHARACTER_N: usize>(state: &AutoMoveMazeState<H, W, END_TURN, CHARACTER_N>, number: usize, start_temp: f64, end_temp: f64, rng: &mut SmallRng) -> AutoMoveMazeState<H, W, END_TURN, CHARACTER_N> {
+    let mut now_state = state.clone();
+    now_state.init(rng);
+    let mut best_score = now_state.get_score();
+    let mut now_score = best_score;
+    let mut best_state = now_state.clone();
+    for i in 0..number {
+        let mut next_state = now_state.clone();
+        next_state.transition(rng);
+        let next_score = next_state.get_score();
+        let temp = start_temp + (end_temp - start_temp) * (i as f64 / number as f64);
+        let probability = ((next_score - now_score) as f64 / temp).exp();
+        let is_force_next = probability > rng.gen::<f64>();
+        if next_score > best_score {
+            best_score = next_score;
+            best_state = next_state.clone();
+        }
+        if next_score > now_score || is_force_next {
+            now_score = next_score;
+            now_state = next_state;
+        }
+    }
+    best_state
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{RngCore, SeedableRng};
+    use crate::maze::{Action, MazeState};
+    use crate::search::hill_climb::hill_climb;
+    use super::*;
+
+    /// 悪化する遷移も受け入れて局所解から抜け出せるので、多くの盤面での平均得点は山登り法以上になる
+    #[test]
+    fn mean_score_is_at_least_hill_climb() {
+        let game_number = 100;
+        let mut rng_for_action = SmallRng::seed_from_u64(0);
+        let mut rng_for_construct = SmallRng::seed_from_u64(0);
+        let mut hill_climb_score_sum = 0;
+        let mut annealing_score_sum = 0;
+        for _ in 0..game_number {
+            let state = AutoMoveMazeState::<5, 5, 5, 3>::new(rng_for_construct.next_u64());
+            hill_climb_score_sum += hill_climb(&state, 10000, &mut rng_for_action).get_score();
+            annealing_score_sum += simulated_annealing(&state, 10000, 500.0, 10.0, &mut rng_for_action).get_score();
+        }
+        let hill_climb_score_mean = hill_climb_score_sum as f64 / game_number as f64;
+        let annealing_score_mean = annealing_score_sum as f64 / game_number as f64;
+        assert!(annealing_score_mean >= hill_climb_score_mean, "{annealing_score_mean} < {hill_climb_score_mean}");
+    }
+
+    /// 残り 1 ターンでも変異する行動があり、得点の上がる方を選ぶ
+    #[test]
+    fn chooses_last_action() {
+        let state = MazeState::from_board_string("3 2 0 1@5").unwrap();
+        let mut rng = SmallRng::seed_from_u64(0);
+        assert_eq!(simulated_annealing_action(&state, 10.0, 0.99, 100, &mut rng), Action::Right);
+    }
+
+    /// 終了状態からは行動を選べない
+    #[test]
+    #[should_panic(expected = "cannot choose an action from a finished state")]
+    fn rejects_finished_state() {
+        let state = MazeState::from_board_string("3 3 0 1@5").unwrap();
+        simulated_annealing_action(&state, 10.0, 0.99, 100, &mut SmallRng::seed_from_u64(0));
+    }
+}
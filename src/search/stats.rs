@@ -0,0 +1,19 @@
+use std::time::Duration;
+
+/// 1 回の探索でかけた手間
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct SearchStats {
+    /// 子を生成した (展開した) ノードの数
+    pub nodes_expanded: u64,
+    /// 根から数えて生成したノードの最大の深さ
+    pub max_depth_reached: usize,
+    pub elapsed: Duration,
+    /// Chokudai サーチで最後の深さまで走査し終えた回数 (走査を繰り返さない探索では 0)
+    pub passes_completed: u64,
+    /// ビームなどに同時に持っていた状態の数の最大 (数えない探索では 0)
+    pub peak_states: usize,
+    /// ビームに積んだ状態の数 (`hash` で重複を除いた探索では除いた後の数。数えない探索では 0)
+    pub states_pushed: u64,
+    /// `beam_search_auto` が選んだビーム幅 (選ばない探索では 0)
+    pub beam_width: usize,
+}
@@ -0,0 +1,83 @@
+use std::collections::VecDeque;
+use rand::rngs::SmallRng;
+use rand::Rng;
+use crate::SingleGameState;
+use crate::search::simulated_annealing::play_actions;
+
+/// 1 回の反復で試す近傍 (1 ターン分の行動を入れ替えた行動列) の数
+const NEIGHBOR_NUMBER: u32 = 10;
+
+/// 行動列全体を解とするタブーサーチ
+///
+/// 近傍は 1 ターン分の行動を別の合法手に入れ替えた行動列で、反復ごとに試した近傍のうち最も良いものに (悪くなっても) 移る。
+/// 移るときに `(ターン, 元の行動, 新しい行動)` をタブーリストに `tabu_tenure` 個まで残し、それを打ち消す入れ替えは禁じる。
+/// ただし、禁じた入れ替えでもそれまでの最良の解を上回るなら受け入れる。
+///
+/// 終了状態や合法手がない状態 (`is_dead_end`) では呼べない。
+pub fn tabu_search_action<S>(state: &S, iterations: u32, tabu_tenure: usize, rng: &mut SmallRng) -> S::Action
+where
+    S: SingleGameState<ScoreType = i64>,
+    S::Action: PartialEq,
+{
+    assert!(!state.is_done() && !state.is_dead_end(), "cannot choose an action from a finished state");
+    let mut now_actions = Vec::new();
+    let mut best_score = play_actions(state, &mut now_actions, rng);
+    let mut best_actions = now_actions.clone();
+    let mut tabu_list = VecDeque::with_capacity(tabu_tenure + 1);
+    for _ in 0..iterations {
+        let mut best_neighbor = None;
+        for _ in 0..NEIGHBOR_NUMBER {
+            let turn = rng.gen_range(0..now_actions.len());
+            let mut now_state = state.clone();
+            for &action in &now_actions[..turn] {
+                now_state.advance(action);
+            }
+            let old_action = now_actions[turn];
+            let legal_actions = now_state.legal_actions().into_iter().filter(|&action| action != old_action).collect::<Vec<_>>();
+            if legal_actions.is_empty() { continue; }
+            let new_action = legal_actions[rng.gen_range(0..legal_actions.len())];
+            let mut next_actions = now_actions.clone();
+            next_actions[turn] = new_action;
+            let next_score = play_actions(state, &mut next_actions, rng);
+            let is_tabu = tabu_list.contains(&(turn, new_action, old_action));
+            if is_tabu && next_score <= best_score { continue; }
+            if best_neighbor.as_ref().is_none_or(|(best_neighbor_score, _, _)| next_score > *best_neighbor_score) {
+                best_neighbor = Some((next_score, next_actions, (turn, old_action, new_action)));
+            }
+        }
+        let Some((next_score, next_actions, tabu_move)) = best_neighbor else { continue; };
+        if next_score > best_score {
+            best_actions = next_actions.clone();
+            best_score = next_score;
+        }
+        now_actions = next_actions;
+        tabu_list.push_back(tabu_move);
+        if tabu_list.len() > tabu_tenure {
+            tabu_list.pop_front();
+        }
+    }
+    best_actions[0]
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::SeedableRng;
+    use crate::maze::{Action, MazeState};
+    use super::*;
+
+    /// 残り 1 ターンでも入れ替える行動があり、得点の上がる方を選ぶ
+    #[test]
+    fn chooses_last_action() {
+        let state = MazeState::from_board_string("3 2 0 1@5").unwrap();
+        let mut rng = SmallRng::seed_from_u64(0);
+        assert_eq!(tabu_search_action(&state, 20, 3, &mut rng), Action::Right);
+    }
+
+    /// 行き止まりからは行動を選べない
+    #[test]
+    #[should_panic(expected = "cannot choose an action from a finished state")]
+    fn rejects_dead_end() {
+        let state = MazeState::from_board_string("3 0 0 #@#").unwrap();
+        tabu_search_action(&state, 20, 3, &mut SmallRng::seed_from_u64(0));
+    }
+}
@@ -0,0 +1,130 @@
+use std::fmt::{Debug, Formatter};
+use rand::rngs::SmallRng;
+use rand::{RngCore, SeedableRng};
+use crate::alternate_maze::PlayerInfo;
+use crate::maze::{Action, Coord};
+
+/// 二人のキャラクターが毎ターン同時に動いて得点を取り合う迷路
+///
+/// 盤面は左右対称で、二人が同じマスに入ったときはそのマスの得点を二人とも得る。
+#[derive(Clone)]
+pub struct SimultaneousMazeState<const H: usize, const W: usize, const END_TURN: i32> {
+    points: [[i32; W]; H],
+    turn: i32,
+    players: [PlayerInfo; 2],
+}
+
+impl<const H: usize, const W: usize, const END_TURN: i32> SimultaneousMazeState<H, W, END_TURN> {
+    pub fn new(seed: u64) -> Self {
+        let mut rng_for_construct = if seed < u64::MAX {
+            SmallRng::seed_from_u64(seed)
+        } else {
+            SmallRng::from_entropy()
+        };
+        let players = [
+            PlayerInfo { coord: Coord { x: W / 2 - 1, y: H / 2 }, game_score: 0 },
+            PlayerInfo { coord: Coord { x: W / 2 + 1, y: H / 2 }, game_score: 0 },
+        ];
+        let mut points = [[0; W]; H];
+        for (y, row) in points.iter_mut().enumerate() {
+            for x in 0..W / 2 + 1 {
+                if players.iter().any(|player| player.coord.y == y && (player.coord.x == x || player.coord.x == W - 1 - x)) { continue; }
+                let point = (rng_for_construct.next_u32() % 10) as i32;
+                row[x] = point;
+                row[W - 1 - x] = point;
+            }
+        }
+        Self {
+            points,
+            turn: 0,
+            players,
+        }
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.turn == END_TURN
+    }
+
+    /// 二人が同時に動いてから、それぞれのいるマスの得点を加える (同じマスなら二人とも得る)
+    pub fn advance(&mut self, action0: Action, action1: Action) {
+        for (player, action) in self.players.iter_mut().zip([action0, action1]) {
+            let (dx, dy) = action.delta();
+            player.coord.x = (player.coord.x as i32 + dx) as usize;
+            player.coord.y = (player.coord.y as i32 + dy) as usize;
+            player.game_score += self.points[player.coord.y][player.coord.x];
+        }
+        for player in &self.players {
+            self.points[player.coord.y][player.coord.x] = 0;
+        }
+        self.turn += 1;
+    }
+
+    pub fn legal_actions(&self, player_id: usize) -> Vec<Action> {
+        let player = &self.players[player_id];
+        let mut actions = Vec::new();
+        for &action in &Action::ALL[..4] {
+            let (dx, dy) = action.delta();
+            let ty = player.coord.y as i32 + dy;
+            let tx = player.coord.x as i32 + dx;
+            if ty >= 0 && ty < H as i32 && tx >= 0 && tx < W as i32 {
+                actions.push(action);
+            }
+        }
+        actions
+    }
+
+    /// プレイヤー 0 から見た勝率に使う得点 (勝ちなら 1、負けなら 0、引き分けなら 0.5)
+    pub fn white_score(&self) -> f64 {
+        match self.players[0].game_score.cmp(&self.players[1].game_score) {
+            std::cmp::Ordering::Greater => 1.0,
+            std::cmp::Ordering::Less => 0.0,
+            std::cmp::Ordering::Equal => 0.5,
+        }
+    }
+
+    pub fn players(&self) -> &[PlayerInfo; 2] {
+        &self.players
+    }
+}
+
+impl<const H: usize, const W: usize, const END_TURN: i32> Debug for SimultaneousMazeState<H, W, END_TURN> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let [a, b] = &self.players;
+        let s = (0..H).map(|h| {
+            (0..W).map(|w| {
+                let is_a = a.coord.y == h && a.coord.x == w;
+                let is_b = b.coord.y == h && b.coord.x == w;
+                if is_a && is_b {
+                    "C"
+                } else if is_a {
+                    "A"
+                } else if is_b {
+                    "B"
+                } else if self.points[h][w] > 0 {
+                    ["0", "1", "2", "3", "4", "5", "6", "7", "8", "9"][self.points[h][w] as usize]
+                } else {
+                    "."
+                }
+            }).collect::<Vec<_>>().join("")
+        }).collect::<Vec<_>>().join("\n");
+        writeln!(f, "turn:\t{}\nscore(A):\t{}\nscore(B):\t{}\n{}", self.turn, a.game_score, b.game_score, s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 二人が同じマスに入ると、そのマスの得点を二人とも得て、マスの得点は 0 になる
+    #[test]
+    fn both_players_take_shared_tile() {
+        let mut state = SimultaneousMazeState::<5, 5, 4>::new(0);
+        state.points[2][2] = 7;
+        let scores = state.players().each_ref().map(|player| player.game_score);
+        state.advance(Action::Right, Action::Left);
+        let [a, b] = state.players();
+        assert_eq!((&a.coord, &b.coord), (&Coord { x: 2, y: 2 }, &Coord { x: 2, y: 2 }));
+        assert_eq!((a.game_score, b.game_score), (scores[0] + 7, scores[1] + 7));
+        assert_eq!(state.points[2][2], 0);
+    }
+}
@@ -0,0 +1,49 @@
+use std::time::{Duration, Instant};
+
+/// 作成してから `threshold` が経過したかを判定する
+#[derive(Debug, Clone, Copy)]
+pub struct TimeKeeper {
+    start: Instant,
+    threshold: Duration,
+}
+
+impl TimeKeeper {
+    pub fn new(threshold: Duration) -> Self {
+        Self {
+            start: Instant::now(),
+            threshold,
+        }
+    }
+
+    pub fn is_time_over(&self) -> bool {
+        self.start.elapsed() >= self.threshold
+    }
+
+    /// 作成してから経過した時間
+    pub fn elapsed(&self) -> Duration {
+        self.start.elapsed()
+    }
+
+    /// 制限時間のうち経過した割合 (制限時間を過ぎると 1 を超える)
+    pub fn elapsed_fraction(&self) -> f64 {
+        self.start.elapsed().as_secs_f64() / self.threshold.as_secs_f64()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// `is_time_over` は作成直後には false で、初めて true になった時点では制限時間が過ぎている
+    #[test]
+    fn time_over_flips_after_threshold() {
+        for threshold in [Duration::from_millis(1), Duration::from_millis(50)] {
+            let time_keeper = TimeKeeper::new(threshold);
+            assert!(!time_keeper.is_time_over());
+            while !time_keeper.is_time_over() {}
+            assert!(time_keeper.elapsed() >= threshold);
+            assert!(time_keeper.elapsed_fraction() >= 1.0);
+            assert!(time_keeper.is_time_over());
+        }
+    }
+}
@@ -0,0 +1,141 @@
+use std::fmt::{Display, Formatter};
+use rand::rngs::SmallRng;
+use rand::{RngCore, SeedableRng};
+use crate::SingleGameState;
+use crate::evaluate::NamedAi;
+use crate::maze::{MazeParams, MazeState};
+
+/// 全員の Elo レーティングの平均
+const INITIAL_ELO: f64 = 1500.0;
+
+/// 全ての AI に同じ盤面を遊ばせ、得点が高いほうを勝ちとする総当たり戦
+pub struct Tournament<'a> {
+    pub params: MazeParams,
+    pub strategies: Vec<NamedAi<'a>>,
+}
+
+/// `Tournament::run` の結果 (どれも `strategies` の順に並べる)
+#[derive(Debug, Clone, PartialEq)]
+pub struct TournamentResult {
+    pub names: Vec<String>,
+    pub elo: Vec<f64>,
+    /// `win_rates[i][j]` は `i` 番目の AI が `j` 番目の AI に勝った割合 (引き分けは 0.5 勝、`i == j` なら 0.5)
+    pub win_rates: Vec<Vec<f64>>,
+    pub mean_scores: Vec<f64>,
+}
+
+impl<'a> Tournament<'a> {
+    pub fn new(params: MazeParams, strategies: Vec<NamedAi<'a>>) -> Self {
+        Self { params, strategies }
+    }
+
+    /// `seed` から作った `game_count` 個の盤面を全ての AI に遊ばせる
+    pub fn run(&mut self, game_count: u32, seed: u64) -> TournamentResult {
+        let n = self.strategies.len();
+        let mut rng_for_construct = SmallRng::seed_from_u64(seed);
+        let mut win_points = vec![vec![0.0; n]; n];
+        let mut mean_scores = vec![0.0; n];
+        for _ in 0..game_count {
+            let initial_state = MazeState::new(rng_for_construct.next_u64(), self.params);
+            let scores = self.strategies.iter_mut().map(|(_, ai)| {
+                let mut state = initial_state.clone();
+                while !state.is_done() {
+                    state.advance(ai(&state));
+                }
+                state.game_score()
+            }).collect::<Vec<_>>();
+            for i in 0..n {
+                mean_scores[i] += scores[i] as f64;
+                for j in 0..n {
+                    win_points[i][j] += match scores[i].cmp(&scores[j]) {
+                        std::cmp::Ordering::Greater => 1.0,
+                        std::cmp::Ordering::Equal => 0.5,
+                        std::cmp::Ordering::Less => 0.0,
+                    };
+                }
+            }
+        }
+        let win_rates = win_points.iter().map(|row| row.iter().map(|&point| point / game_count as f64).collect()).collect();
+        let elo = (0..n).map(|i| {
+            if n == 1 {
+                return INITIAL_ELO;
+            }
+            // 勝率が 0 や 1 でも有限になるよう、引き分けを 1 回足した勝率から Elo の差 400 log10(p / (1 - p)) を求めて平均する
+            let difference_sum = (0..n).filter(|&j| j != i).map(|j| {
+                let p = (win_points[i][j] + 0.5) / (game_count as f64 + 1.0);
+                400.0 * (p / (1.0 - p)).log10()
+            }).sum::<f64>();
+            INITIAL_ELO + difference_sum / (n - 1) as f64
+        }).collect();
+        for mean_score in &mut mean_scores {
+            *mean_score /= game_count as f64;
+        }
+        TournamentResult {
+            names: self.strategies.iter().map(|(name, _)| name.to_string()).collect(),
+            elo,
+            win_rates,
+            mean_scores,
+        }
+    }
+}
+
+/// AI ごとに Elo、平均得点、各 AI への勝率を 1 行に並べた表
+impl Display for TournamentResult {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let name_width = self.names.iter().map(|name| name.len()).max().unwrap_or(0).max("algorithm".len());
+        write!(f, "{:<name_width$}\t{:>7}\t{:>8}", "algorithm", "elo", "mean")?;
+        for i in 0..self.names.len() {
+            write!(f, "\tvs {i:<3}")?;
+        }
+        writeln!(f)?;
+        for (i, name) in self.names.iter().enumerate() {
+            write!(f, "{name:<name_width$}\t{:>7.1}\t{:>8.2}", self.elo[i], self.mean_scores[i])?;
+            for win_rate in &self.win_rates[i] {
+                write!(f, "\t{win_rate:>6.3}")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::search::beam::beam_search_action;
+    use crate::search::greedy::greedy_action;
+    use crate::search::random::random_action;
+    use super::*;
+
+    fn run_tournament() -> TournamentResult {
+        let params = MazeParams { h: 10, w: 10, end_turn: 20 };
+        let mut rng_for_random = SmallRng::seed_from_u64(0);
+        let strategies: Vec<NamedAi> = vec![
+            ("random", Box::new(move |state: &MazeState| random_action(state, &mut rng_for_random))),
+            ("greedy", Box::new(greedy_action)),
+            ("beam", Box::new(move |state: &MazeState| beam_search_action(state, 5, params.end_turn as usize))),
+        ];
+        Tournament::new(params, strategies).run(20, 0)
+    }
+
+    /// 自分自身への勝率は 0.5 で、2 つの AI の互いへの勝率を足すと 1 になる
+    #[test]
+    fn win_rates_are_complementary() {
+        let result = run_tournament();
+        let n = result.names.len();
+        for i in 0..n {
+            assert_eq!(result.win_rates[i][i], 0.5);
+            for j in 0..n {
+                assert!((result.win_rates[i][j] + result.win_rates[j][i] - 1.0).abs() < 1e-9);
+            }
+        }
+    }
+
+    /// Elo の平均は `INITIAL_ELO` で、ランダム、貪欲法、ビームサーチの順に高くなる
+    #[test]
+    fn elo_is_centered_and_ordered() {
+        let result = run_tournament();
+        let n = result.names.len();
+        assert!((result.elo.iter().sum::<f64>() / n as f64 - INITIAL_ELO).abs() < 1e-9);
+        assert!(result.elo[0] < result.elo[1] && result.elo[1] < result.elo[2], "{:?}", result.elo);
+    }
+}
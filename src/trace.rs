@@ -0,0 +1,180 @@
+use crate::SingleGameState;
+use crate::maze::{Action, Coord, MazeParams, MazeState};
+
+/// 1 ゲーム分の初期盤面のパラメータと選んだ行動の履歴
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GameTrace {
+    pub seed: u64,
+    pub height: usize,
+    pub width: usize,
+    pub end_turn: i32,
+    pub actions: Vec<Action>,
+}
+
+#[cfg(feature = "serde")]
+impl GameTrace {
+    /// NDJSON の 1 行として書き出す
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).unwrap()
+    }
+
+    pub fn from_json(line: &str) -> serde_json::Result<Self> {
+        serde_json::from_str(line)
+    }
+}
+
+/// ゲームを最後まで進め、最終得点と選んだ行動の列を返す
+pub fn play_game(mut state: MazeState, mut policy: impl FnMut(&MazeState) -> Action) -> (i32, Vec<Action>) {
+    let mut actions = Vec::new();
+    while !state.is_done() {
+        let action = policy(&state);
+        state.advance(action);
+        actions.push(action);
+    }
+    (state.game_score(), actions)
+}
+
+/// `initial` から `actions` を順に実行したときの各ターンの盤面を並べる
+pub fn render_replay(initial: &MazeState, actions: &[Action]) -> String {
+    let mut state = initial.clone();
+    let mut s = format!("{state:?}");
+    for &action in actions {
+        state.advance(action);
+        s += &format!("action:\t{action}\n{state:?}");
+    }
+    s
+}
+
+/// 1 ターン分の記録
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TurnRecord {
+    /// 行動を選んだときのターン数 (0 から数える)
+    pub turn: i32,
+    pub action: Action,
+    /// この行動で増えた得点
+    pub score_delta: i32,
+    /// 行動した後のキャラクターの位置
+    pub character: Coord,
+}
+
+/// ゲームを最後まで進め、各ターンの記録を並べる
+pub fn trace_game(mut state: MazeState, mut policy: impl FnMut(&MazeState) -> Action) -> Vec<TurnRecord> {
+    let mut records = Vec::new();
+    while !state.is_done() {
+        let turn = state.turn();
+        let game_score = state.game_score();
+        let action = policy(&state);
+        state.advance(action);
+        records.push(TurnRecord { turn, action, score_delta: state.game_score() - game_score, character: state.character().clone() });
+    }
+    records
+}
+
+/// ヘッダー付きの CSV にする (行動は `Right` などの名前で書く)
+pub fn to_csv(records: &[TurnRecord]) -> String {
+    let mut s = String::from("turn,action,score_delta,x,y\n");
+    for record in records {
+        s += &format!("{},{:?},{},{},{}\n", record.turn, record.action, record.score_delta, record.character.x, record.character.y);
+    }
+    s
+}
+
+/// オブジェクトの配列の JSON にする (行動は `"Right"` などの名前で書く)
+pub fn to_json(records: &[TurnRecord]) -> String {
+    let objects = records.iter().map(|record| {
+        format!(
+            "{{\"turn\":{},\"action\":\"{:?}\",\"score_delta\":{},\"x\":{},\"y\":{}}}",
+            record.turn, record.action, record.score_delta, record.character.x, record.character.y,
+        )
+    }).collect::<Vec<_>>();
+    format!("[{}]", objects.join(","))
+}
+
+pub fn record_game(seed: u64, params: MazeParams, ai: impl Fn(&MazeState) -> Action) -> GameTrace {
+    let (_, actions) = play_game(MazeState::new(seed, params), ai);
+    GameTrace { seed, height: params.h, width: params.w, end_turn: params.end_turn, actions }
+}
+
+pub fn replay_trace(trace: &GameTrace) -> MazeState {
+    let mut state = MazeState::new(trace.seed, MazeParams { h: trace.height, w: trace.width, end_turn: trace.end_turn });
+    for &action in &trace.actions {
+        state.advance(action);
+    }
+    state
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::search::beam::beam_search_action;
+    use crate::search::greedy::greedy_action;
+    use super::*;
+
+    /// 記録した行動列を再生すると、記録したときと同じ得点になる
+    #[test]
+    fn replay_reproduces_score() {
+        let params = MazeParams { h: 5, w: 6, end_turn: 10 };
+        let beam = |state: &MazeState| beam_search_action(state, 3, 10);
+        for seed in 0..20 {
+            let (score, _) = play_game(MazeState::new(seed, params), greedy_action);
+            assert_eq!(replay_trace(&record_game(seed, params, greedy_action)).game_score(), score);
+            let (score, _) = play_game(MazeState::new(seed, params), beam);
+            assert_eq!(replay_trace(&record_game(seed, params, beam)).game_score(), score);
+        }
+    }
+
+    /// 記録は終了ターン数と同じ個数で、得点の増分の合計は最終得点に一致し、CSV はヘッダーの分だけ 1 行多い
+    #[test]
+    fn trace_matches_play_game() {
+        let params = MazeParams { h: 30, w: 30, end_turn: 100 };
+        for seed in 0..20 {
+            let state = MazeState::new(seed, params);
+            let records = trace_game(state.clone(), greedy_action);
+            let (game_score, actions) = play_game(state, greedy_action);
+            assert_eq!(records.len(), params.end_turn as usize);
+            assert_eq!(records.iter().map(|record| record.score_delta).sum::<i32>(), game_score);
+            assert!(records.iter().map(|record| record.action).eq(actions));
+            assert_eq!(to_csv(&records).lines().count(), params.end_turn as usize + 1);
+        }
+    }
+
+    /// 一直線に 1, 2 点が並ぶ盤面で右に 2 回進んだ記録は、決まった JSON になる
+    #[test]
+    fn to_json_golden() {
+        let records = trace_game(MazeState::from_board_string("2 0 0 @12").unwrap(), |_| Action::Right);
+        assert_eq!(
+            to_json(&records),
+            r#"[{"turn":0,"action":"Right","score_delta":1,"x":1,"y":0},{"turn":1,"action":"Right","score_delta":2,"x":2,"y":0}]"#,
+        );
+        assert_eq!(to_json(&[]), "[]");
+    }
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod serde_tests {
+    use crate::search::greedy::greedy_action;
+    use super::*;
+
+    /// NDJSON の 1 行に書き出して読み戻すと元の記録と一致する
+    #[test]
+    fn json_round_trip() {
+        let trace = record_game(0, MazeParams { h: 5, w: 6, end_turn: 10 }, greedy_action);
+        assert_eq!(GameTrace::from_json(&trace.to_json()).unwrap(), trace);
+    }
+
+    /// 各ターンの記録の JSON を `serde_json` で読み戻すと、記録と同じ値が並ぶ
+    #[test]
+    fn records_json_round_trip() {
+        let records = trace_game(MazeState::new(0, MazeParams { h: 5, w: 6, end_turn: 10 }), greedy_action);
+        let value: serde_json::Value = serde_json::from_str(&to_json(&records)).unwrap();
+        let objects = value.as_array().unwrap();
+        assert_eq!(objects.len(), records.len());
+        for (object, record) in objects.iter().zip(&records) {
+            assert_eq!(object["turn"], record.turn);
+            assert_eq!(object["action"], format!("{:?}", record.action));
+            assert_eq!(object["score_delta"], record.score_delta);
+            assert_eq!(object["x"], record.character.x);
+            assert_eq!(object["y"], record.character.y);
+        }
+    }
+}